@@ -20,14 +20,14 @@ pub fn form_derive_impl(input: TokenStream) -> TokenStream {
     let output = quote! {
         impl arc::file::Save for #name {
             #[inline]
-            fn save(&self, path: &std::path::Path) {
-                arc::file::as_json(self, path);
+            fn save(&self, path: &std::path::Path) -> std::result::Result<(), arc::err::Error> {
+                arc::file::as_json(self, path)
             }
         }
 
         impl arc::file::Load for #name {
             #[inline]
-            fn load(path: &std::path::Path) -> Self {
+            fn load(path: &std::path::Path) -> std::result::Result<Self, arc::err::Error> {
                 arc::file::from_json(path)
             }
         }
@@ -52,7 +52,7 @@ pub fn form_load_derive_impl(input: TokenStream) -> TokenStream {
     let output = quote! {
         impl arc::file::Load for #name {
             #[inline]
-            fn load(path: &std::path::Path) -> Self {
+            fn load(path: &std::path::Path) -> std::result::Result<Self, arc::err::Error> {
                 arc::file::from_json(path)
             }
         }
@@ -77,15 +77,15 @@ pub fn json_derive_impl(input: TokenStream) -> TokenStream {
     let output = quote! {
         impl crate::file::Save for #name {
             #[inline]
-            fn save(&self, path: &std::path::Path) {
-                crate::file::as_json(self, path);
+            fn save(&self, path: &std::path::Path) -> std::result::Result<(), crate::err::Error> {
+                crate::file::as_json(self, path)
             }
         }
 
         impl crate::file::Load for #name {
             #[inline]
             #[must_use]
-            fn load(path: &std::path::Path) -> Self {
+            fn load(path: &std::path::Path) -> std::result::Result<Self, crate::err::Error> {
                 crate::file::from_json(path)
             }
         }
@@ -111,7 +111,7 @@ pub fn json_load_derive_impl(input: TokenStream) -> TokenStream {
         impl crate::file::Load for #name {
             #[inline]
             #[must_use]
-            fn load(path: &std::path::Path) -> Self {
+            fn load(path: &std::path::Path) -> std::result::Result<Self, crate::err::Error> {
                 crate::file::from_json(path)
             }
         }
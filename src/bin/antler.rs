@@ -2,6 +2,7 @@
 
 use arc::{
     args, columns,
+    err::Error,
     file::Load,
     fmt,
     img::{
@@ -32,26 +33,28 @@ struct Parameters {
     render: Settings,
 }
 
-fn main() {
+fn main() -> Result<(), Error> {
     fmt::title(&exec::name());
 
     fmt::section("Initialisation");
     let (in_dir, out_dir, params_filename) = init_dirs();
 
     fmt::section("Loading");
-    let params = load_parameters(&in_dir, &params_filename);
-    let scene = load_scene(&in_dir, &params);
-    let grid = build_grid(&params, &scene);
+    let params = load_parameters(&in_dir, &params_filename)?;
 
     fmt::section("Rendering");
     for (name, frame_settings) in params.render.frames() {
         fmt::sub_section(name);
-        let frame = load_frame_settings(&in_dir, &frame_settings);
+        let scene = load_scene(&in_dir, &params, frame_settings.time().unwrap_or(0.0))?;
+        let grid = build_grid(&params, &scene);
+        let frame = load_frame_settings(&in_dir, &frame_settings)?;
         let img = frame.image(painter::arctic::paint, &grid);
         save_frame(&out_dir, name, img);
     }
 
     fmt::section("Finished");
+
+    Ok(())
 }
 
 /// Get the directories.
@@ -76,7 +79,7 @@ fn init_dirs() -> (PathBuf, PathBuf, String) {
 }
 
 /// Load the parameters structure.
-fn load_parameters(in_dir: &Path, params_filename: &str) -> Parameters {
+fn load_parameters(in_dir: &Path, params_filename: &str) -> Result<Parameters, Error> {
     fmt::sub_section("Parameters");
     let params_path = in_dir.join(params_filename);
     {
@@ -87,8 +90,8 @@ fn load_parameters(in_dir: &Path, params_filename: &str) -> Parameters {
     Parameters::load(&params_path)
 }
 
-/// Load the rendering scene.
-fn load_scene(in_dir: &Path, params: &Parameters) -> Scene {
+/// Load the rendering scene at the given time.
+fn load_scene(in_dir: &Path, params: &Parameters, time: f64) -> Result<Scene, Error> {
     fmt::sub_section("Scene");
     let scene_path = in_dir.join(&format!("scenes/{}.json", params.render.scene()));
     {
@@ -96,7 +99,7 @@ fn load_scene(in_dir: &Path, params: &Parameters) -> Scene {
         values!(2 * COL_WIDTH, scene_path);
     }
 
-    let scene = SceneSettings::load(&scene_path).build(&in_dir.join("meshes"));
+    let scene = SceneSettings::load(&scene_path)?.build(&in_dir.join("meshes"), time)?;
     {
         let scene_minimum = scene.boundary().mins();
         let scene_maximum = scene.boundary().maxs();
@@ -121,7 +124,7 @@ fn load_scene(in_dir: &Path, params: &Parameters) -> Scene {
         );
     }
 
-    scene
+    Ok(scene)
 }
 
 /// Build the gridding scheme.
@@ -149,17 +152,23 @@ fn build_grid<'a>(params: &Parameters, scene: &'a Scene) -> Grid<'a> {
 }
 
 /// Load the frame settings.
-pub fn load_frame_settings(in_dir: &Path, frame: &FrameSettings) -> Frame {
-    let quality = load_quality(in_dir, frame);
-    let shader = load_shader(in_dir, frame);
-    let scheme = load_scheme(in_dir, frame);
+pub fn load_frame_settings(in_dir: &Path, frame: &FrameSettings) -> Result<Frame, Error> {
+    let quality = load_quality(in_dir, frame)?;
+    let shader = load_shader(in_dir, frame)?;
+    let scheme = load_scheme(in_dir, frame)?;
     let camera = build_camera(frame, &quality);
 
-    Frame::new(frame.aspect_ratio(), quality, shader, scheme, camera)
+    Ok(Frame::new(
+        frame.aspect_ratio(),
+        quality,
+        shader,
+        scheme,
+        camera,
+    ))
 }
 
 /// Load quality settings.
-pub fn load_quality(in_dir: &Path, frame: &FrameSettings) -> QualitySettings {
+pub fn load_quality(in_dir: &Path, frame: &FrameSettings) -> Result<QualitySettings, Error> {
     fmt::sub_sub_section("quality");
     let quality_path = in_dir.join(format!("quality/{}.json", frame.quality()));
     {
@@ -167,7 +176,7 @@ pub fn load_quality(in_dir: &Path, frame: &FrameSettings) -> QualitySettings {
         values!(2 * COL_WIDTH, quality_path);
     }
 
-    let quality = QualitySettings::load(&quality_path);
+    let quality = QualitySettings::load(&quality_path)?;
     {
         let target_pixels = quality.target_pixels();
         let samples_per_pixel = quality.samples_per_pixel();
@@ -196,11 +205,11 @@ pub fn load_quality(in_dir: &Path, frame: &FrameSettings) -> QualitySettings {
         );
     }
 
-    quality
+    Ok(quality)
 }
 
 /// Load shader settings.
-pub fn load_shader(in_dir: &Path, frame: &FrameSettings) -> ShaderSettings {
+pub fn load_shader(in_dir: &Path, frame: &FrameSettings) -> Result<ShaderSettings, Error> {
     fmt::sub_sub_section("shader");
     let shader_path = in_dir.join(format!("shaders/{}.json", frame.shader()));
     {
@@ -208,7 +217,7 @@ pub fn load_shader(in_dir: &Path, frame: &FrameSettings) -> ShaderSettings {
         values!(2 * COL_WIDTH, shader_path);
     }
 
-    let shader = ShaderSettings::load(&shader_path);
+    let shader = ShaderSettings::load(&shader_path)?;
     {
         let ambient_lighting = shader.light_weights().ambient();
         let diffuse_lighting = shader.light_weights().diffuse();
@@ -229,11 +238,11 @@ pub fn load_shader(in_dir: &Path, frame: &FrameSettings) -> ShaderSettings {
         );
     }
 
-    shader
+    Ok(shader)
 }
 
 /// Load a colour scheme.
-pub fn load_scheme(in_dir: &Path, frame: &FrameSettings) -> Scheme {
+pub fn load_scheme(in_dir: &Path, frame: &FrameSettings) -> Result<Scheme, Error> {
     fmt::sub_sub_section("scheme");
     let scheme_path = in_dir.join(format!("schemes/{}.json", frame.scheme()));
     {
@@ -241,7 +250,7 @@ pub fn load_scheme(in_dir: &Path, frame: &FrameSettings) -> Scheme {
         values!(2 * COL_WIDTH, scheme_path);
     }
 
-    let scheme = SchemeSettings::load(&scheme_path).build();
+    let scheme = SchemeSettings::load(&scheme_path)?.build();
     {
         let group_width = COL_WIDTH / 2;
         let term_width = arc::fmt::term_width();
@@ -261,7 +270,7 @@ pub fn load_scheme(in_dir: &Path, frame: &FrameSettings) -> Scheme {
         }
     }
 
-    scheme
+    Ok(scheme)
 }
 
 /// Build a camera.
@@ -2,10 +2,11 @@
 
 use arc::{
     args,
+    err::Error,
     file::{Load, Save, Verse as VerseForm},
     geom::Aabb,
     report,
-    sim::diff,
+    sim::{diff, vox},
     util::{banner, exec, init},
 };
 use attr::form;
@@ -19,7 +20,7 @@ struct Parameters {
     verse: VerseForm,
 }
 
-pub fn main() {
+pub fn main() -> Result<(), Error> {
     colog::init();
     banner::title(&exec::name());
 
@@ -31,13 +32,14 @@ pub fn main() {
 
     banner::section("Loading");
     info!("Loading parameters file...");
-    let params = Parameters::load(&params_path);
+    let params = Parameters::load(&params_path)?;
 
     info!("Loading universe files...");
-    let verse = params.verse.form(&in_dir);
+    let verse = params.verse.form(&in_dir)?;
 
-    info!("Constructing grid...");
-    let grid = diff::Grid::new(
+    info!("Voxelising...");
+    let vox = vox::Voxelisation::load_or_new(
+        &out_dir.join("voxelisation.json"),
         params.res,
         params.bound,
         verse.inters(),
@@ -45,6 +47,9 @@ pub fn main() {
         verse.surfs(),
     );
 
+    info!("Constructing grid...");
+    let grid = diff::Grid::new(&vox);
+
     banner::section("Overview");
     verse.overview();
 
@@ -53,34 +58,38 @@ pub fn main() {
         info!("Saving {} material map.", key);
         grid.mats()
             .map(|mat| if mat == &key { 1.0 } else { 0.0 })
-            .save(&out_dir.join(format!("mat_map_{}.nc", key)));
+            .save(&out_dir.join(format!("mat_map_{}.nc", key)))?;
     }
     for key in verse.states().map().keys() {
         info!("Saving {} state map.", key);
         grid.states()
             .map(|state| if state == &key { 1.0 } else { 0.0 })
-            .save(&out_dir.join(format!("state_map_{}.nc", key)));
+            .save(&out_dir.join(format!("state_map_{}.nc", key)))?;
     }
 
     banner::section("Simulation");
     let mut concs = grid.concs(verse.states(), verse.specs());
     let viscs = grid.visc(verse.mats());
-    let ala_index = verse.specs().index_of_key(&arc::ord::SpecKey::new("a"));
+    let ala_index = verse
+        .specs()
+        .try_index_of_key(&arc::ord::SpecKey::new("a"))?;
     let total_steps = 100;
     for n in 0..total_steps {
         println!("n: {}", n);
         concs
             .map(|cs| *cs.get(ala_index).expect("Invalid index."))
-            .save(&out_dir.join(format!("a_{}.nc", n)));
+            .save(&out_dir.join(format!("a_{}.nc", n)))?;
         diff::run(1.0, &grid, verse.specs(), &mut concs, &viscs);
     }
     concs
         .map(|cs| *cs.get(ala_index).expect("Invalid index."))
-        .save(&out_dir.join(format!("ala_{}.nc", total_steps)));
+        .save(&out_dir.join(format!("ala_{}.nc", total_steps)))?;
 
     banner::section("Post-Analysis");
 
     banner::section("Finished");
+
+    Ok(())
 }
 
 /// Initialise the directories.
@@ -2,6 +2,7 @@
 
 use arc::{
     args,
+    err::Error,
     file::Load,
     ord::{ReactKey, ReactSet, SpecSet},
     report,
@@ -30,7 +31,7 @@ struct Parameters {
     iterations: u64,
 }
 
-pub fn main() {
+pub fn main() -> Result<(), Error> {
     colog::init();
     banner::title(&exec::name());
 
@@ -42,10 +43,10 @@ pub fn main() {
 
     banner::section("Loading");
     info!("Loading parameters file...");
-    let params = Parameters::load(&params_path);
+    let params = Parameters::load(&params_path)?;
 
-    let reacts = ReactSet::load(&in_dir.join("reactions"), &params.reacts, "json");
-    let specs = SpecSet::load(&in_dir.join("species"), &reacts.spec_keys(), "json");
+    let reacts = ReactSet::load(&in_dir.join("reactions"), &params.reacts, "json")?;
+    let specs = SpecSet::load(&in_dir.join("species"), &reacts.spec_keys(), "json")?;
     let mut concs = params.state.new_conc_arr(&specs);
     let _sources = params.state.new_source_arr(&specs);
 
@@ -91,6 +92,8 @@ pub fn main() {
     // pb.finish_with_message("Kinetics complete.");
 
     banner::section("Finished");
+
+    Ok(())
 }
 
 /// Initialise the directories.
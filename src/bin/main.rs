@@ -2,15 +2,17 @@
 
 use arc::{
     args,
+    err::Error,
     file::{Load, Save, Verse as VerseForm},
     geom::Aabb,
     ord::{LightKey, SpecKey},
     report,
-    sim::{diff, kin, mcrt},
+    sim::{boundary::Boundary, diff, kin, mcrt, variance::Variance, vox},
     util::{banner, exec, init},
 };
 use attr::form;
 use log::info;
+use rand::{thread_rng, Rng};
 
 #[form]
 struct Parameters {
@@ -19,9 +21,10 @@ struct Parameters {
     res: [usize; 3],
     num_phot: u64,
     light: LightKey,
+    seed: Option<u64>,
 }
 
-pub fn main() {
+pub fn main() -> Result<(), Error> {
     colog::init();
     banner::title(&exec::name());
 
@@ -38,60 +41,80 @@ pub fn main() {
 
     banner::section("Loading");
     info!("Loading parameters file...");
-    let params = Parameters::load(&params_path);
+    let params = Parameters::load(&params_path)?;
+
+    let seed = params.seed.unwrap_or_else(|| thread_rng().gen());
+    report!(seed, "random seed");
 
     info!("Loading universe files...");
-    let verse = params.verse.form(&in_dir);
+    let verse = params.verse.form(&in_dir)?;
 
     banner::section("Overview");
     verse.overview();
 
+    banner::section("Voxelisation");
+    let vox = vox::Voxelisation::load_or_new(
+        &out_dir.join("voxelisation.json"),
+        params.res,
+        params.bound,
+        verse.inters(),
+        verse.regions(),
+        verse.surfs(),
+    );
+
     banner::section("MCRT");
     let lm = {
-        let mcrt_grid = mcrt::Grid::new(
-            params.res,
-            params.bound.clone(),
-            verse.inters(),
-            verse.surfs(),
-        );
+        let mcrt_grid = mcrt::Grid::new(&vox, None, Boundary::periodic_xy(), verse.mats());
 
         info!("Saving interface map.");
-        mcrt_grid.interfaces().save(&out_dir.join("interfaces.nc"));
+        mcrt_grid
+            .interfaces()
+            .save(&out_dir.join("interfaces.nc"))?;
+        info!("Saving acceleration maps.");
+        mcrt_grid.tris().save(&out_dir.join("tris.nc"))?;
+        mcrt_grid.accel().save(&out_dir.join("accel.nc"))?;
+        mcrt_grid.depths().save(&out_dir.join("depths.nc"))?;
+        let (num_accel, accel_depth) = mcrt_grid.accel_stats();
+        report!(num_accel, "accelerated cells");
+        report!(accel_depth, "max acceleration depth");
 
         mcrt::run(
-            params.num_phot as u64,
-            verse.lights().get(&params.light),
+            mcrt::Checkpoint::new(
+                seed,
+                params.num_phot,
+                0,
+                mcrt::LightMap::new(&mcrt_grid, None, None, verse.dets()),
+                Variance::standard(),
+            ),
+            &out_dir.join("checkpoint.json"),
+            None,
+            &Variance::standard(),
+            verse.lights().try_get(&params.light)?,
             &mcrt_grid,
             verse.surfs(),
             verse.mats(),
+            verse.dets(),
         )
     };
-    lm.save(&out_dir);
+    lm.save(&out_dir)?;
 
     banner::section("Diffusion");
     let (mut concs, mults) = {
-        info!("Constructing grid...");
-        let diff_grid = diff::Grid::new(
-            params.res,
-            params.bound,
-            verse.inters(),
-            verse.regions(),
-            verse.surfs(),
-        );
+        let diff_grid = diff::Grid::new(&vox);
 
         for key in verse.mats().map().keys() {
             info!("Saving {} material map.", key);
             diff_grid
                 .mats()
                 .map(|mat| if mat == &key { 1.0 } else { 0.0 })
-                .save(&out_dir.join(format!("mat_map_{}.nc", key)));
+                .save(&out_dir.join(format!("mat_map_{}.nc", key)))?;
         }
         for key in verse.states().map().keys() {
             info!("Saving {} state map.", key);
             diff_grid
                 .states()
                 .map(|state| if state == &key { 1.0 } else { 0.0 })
-                .save(&out_dir.join(format!("state_map_{}.nc", key)));
+                .save(&out_dir.join(format!("state_map_{}.nc", key)))?;
         }
 
         let mut concs = diff_grid.concs(verse.states(), verse.specs());
@@ -102,7 +125,7 @@ pub fn main() {
         for (i, key) in verse.specs().map().keys().enumerate() {
             concs
                 .map(|cs| *cs.get(i).expect("Invalid index."))
-                .save(&out_dir.join(format!("diff_{}_{}.nc", key, 0)));
+                .save(&out_dir.join(format!("diff_{}_{}.nc", key, 0)))?;
         }
         for n in 0..total_steps {
             println!("n: {}/{}", n, total_steps);
@@ -111,7 +134,7 @@ pub fn main() {
             for (i, key) in verse.specs().map().keys().enumerate() {
                 concs
                     .map(|cs| *cs.get(i).expect("Invalid index."))
-                    .save(&out_dir.join(format!("diff_{}_{}.nc", key, n + 1)));
+                    .save(&out_dir.join(format!("diff_{}_{}.nc", key, n + 1)))?;
             }
         }
 
@@ -120,7 +143,7 @@ pub fn main() {
         (concs, mults)
     };
 
-    let udens_index = verse.specs().index_of_key(&SpecKey::new("udens"));
+    let udens_index = verse.specs().try_index_of_key(&SpecKey::new("udens"))?;
     for (cs, abs_dens) in concs.iter_mut().zip(&lm.abs_dens()) {
         *cs.get_mut(udens_index).expect("Invalid index.") += abs_dens / 186_470_120_150_714_370.0;
         // Jesus what a gross number - consider replacing with a named constant?
@@ -139,7 +162,7 @@ pub fn main() {
     for (i, key) in verse.specs().map().keys().enumerate() {
         let kns = concs.map(|cs| *cs.get(i).expect("Invalid index."));
         println!("key: {}", kns.sum());
-        kns.save(&out_dir.join(format!("kin_{}_{}.nc", key, 0)));
+        kns.save(&out_dir.join(format!("kin_{}_{}.nc", key, 0)))?;
     }
     for k in 0..total_steps {
         println!("k: {}/{}", k, total_steps);
@@ -158,10 +181,12 @@ pub fn main() {
         for (i, key) in verse.specs().map().keys().enumerate() {
             let kns = concs.map(|cs| *cs.get(i).expect("Invalid index."));
             println!("key: {}", kns.sum());
-            kns.save(&out_dir.join(format!("kin_{}_{}.nc", key, k + 1)));
+            kns.save(&out_dir.join(format!("kin_{}_{}.nc", key, k + 1)))?;
         }
     }
     // for concs in concs.iter_mut() {}
 
     banner::section("Finished");
+
+    Ok(())
 }
@@ -0,0 +1,105 @@
+//! Mesh validation and repair binary.
+
+use arc::{
+    args,
+    err::Error,
+    file::{Load, Save},
+    geom::{MeshReport, PolyMesh},
+    report,
+    util::{banner, exec},
+};
+use colog;
+use log::{info, warn};
+use std::{env, fmt::Debug, path::PathBuf};
+
+/// Maximum number of individual defects listed for each kind.
+const MAX_LISTED: usize = 10;
+
+pub fn main() -> Result<(), Error> {
+    colog::init();
+    banner::title(&exec::name());
+
+    banner::section("Initialisation");
+    let (mesh_path, out_path) = initialisation();
+    report!(mesh_path.display(), "mesh path");
+    if let Some(out_path) = &out_path {
+        report!(out_path.display(), "repaired mesh path");
+    }
+
+    banner::section("Loading");
+    info!("Loading mesh file...");
+    let mesh = PolyMesh::load(&mesh_path)?;
+    report!(mesh.verts().len(), "vertices");
+    report!(mesh.norms().len(), "normals");
+    report!(mesh.num_faces(), "faces");
+    for name in mesh.group_names() {
+        info!("\t{}", name);
+    }
+
+    banner::section("Checking");
+    let valid = overview(&mesh.check());
+
+    if let Some(out_path) = out_path {
+        banner::section("Repairing");
+        let repaired = mesh.repaired();
+        report!(repaired.num_faces(), "faces");
+        if !overview(&repaired.check()) {
+            warn!("Remaining defects must be fixed by hand.");
+        }
+        repaired.save(&out_path)?;
+        info!("Repaired mesh saved.");
+    } else if !valid {
+        info!("Supply an output path to write a repaired copy.");
+    }
+
+    banner::section("Finished");
+
+    Ok(())
+}
+
+/// Initialise the input mesh path and the optional repaired mesh path.
+fn initialisation() -> (PathBuf, Option<PathBuf>) {
+    args!(_bin_path: String;
+        mesh_path: PathBuf);
+
+    (mesh_path, env::args().nth(2).map(PathBuf::from))
+}
+
+/// Report the findings of a mesh check.
+/// Return true if the mesh is a valid boundary.
+fn overview(report: &MeshReport) -> bool {
+    report!(report.num_verts, "vertices");
+    report!(report.num_tris, "triangles");
+    report!(report.volume, "signed volume");
+
+    list("degenerate triangles", &report.degenerate);
+    list("boundary edges", &report.boundary_edges);
+    list("non-manifold edges", &report.non_manifold_edges);
+    list("inconsistent edges", &report.inconsistent_edges);
+    list("flipped normals", &report.flipped_normals);
+    list("self-intersections", &report.self_intersections);
+
+    if report.is_closed() && report.volume <= 0.0 {
+        warn!("Closed surface faces inwards.");
+    }
+
+    let valid = report.is_valid();
+    if valid {
+        info!("Mesh is a valid boundary.");
+    } else {
+        warn!("Mesh is not a valid boundary.");
+    }
+
+    valid
+}
+
+/// Report the number of defects of a kind, and list the first few.
+fn list<T: Debug>(desc: &str, items: &[T]) {
+    report!(items.len(), desc);
+    for item in items.iter().take(MAX_LISTED) {
+        warn!("\t{:?}", item);
+    }
+    if items.len() > MAX_LISTED {
+        warn!("\t...");
+    }
+}
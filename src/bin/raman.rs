@@ -3,16 +3,25 @@
 use ::std::fs::OpenOptions;
 use arc::{
     args,
-    file::{Load, Save, Verse as VerseForm},
+    err::Error,
+    file::{Load, Redirect, Save, Verse as VerseForm},
     geom::Aabb,
-    ord::LightKey,
+    math::Binner,
+    ord::{DetKey, LightKey},
     report,
-    sim::raman,
+    sim::{
+        boundary::{Boundary, FACE_NAMES},
+        raman,
+        variance::{Variance, WeightWindow},
+        vox,
+    },
     util::{banner, exec, init},
+    world::Instrument,
 };
 use attr::form;
 use colog;
 use log::info;
+use rand::{thread_rng, Rng};
 use std::{
     io::{BufWriter, Write},
     path::PathBuf,
@@ -25,56 +34,131 @@ struct Parameters {
     verse: VerseForm,
     num_phot: f64,
     light: LightKey,
+    seed: Option<u64>,
+    target: Option<raman::Target>,
+    binner: Option<Binner>,
+    time_binner: Option<Binner>,
+    refine: Option<raman::Refine>,
+    boundary: Option<Boundary>,
+    variance: Option<Variance>,
+    settings: Redirect<raman::Settings>,
 }
 
-pub fn main() {
+pub fn main() -> Result<(), Error> {
     colog::init();
     banner::title(&exec::name());
 
     banner::section("initialisation");
-    let (in_dir, out_dir, params_path) = initialisation();
+    let (in_dir, out_dir, params_path, resume, time) = initialisation()?;
     report!(in_dir.display(), "input directory");
     report!(out_dir.display(), "output directory");
     report!(params_path.display(), "parameters path");
+    report!(resume, "resuming");
+    let checkpoint_path = out_dir.join("checkpoint.json");
 
     banner::section("Loading");
     info!("Loading parameters file...");
-    let params = Parameters::load(&params_path);
+    let mut params = Parameters::load(&params_path)?;
+    if let Some(boundary) = &params.boundary {
+        boundary.validate()?;
+    }
+    if time.is_some() {
+        *params.verse.time_mut() = time;
+    }
+    report!(params.verse.time().unwrap_or(0.0), "time");
 
     info!("Loading universe files...");
-    let verse = params.verse.form(&in_dir);
+    let verse = params.verse.form(&in_dir)?;
+    let dets = params.settings.data().dets(verse.dets())?;
+
+    info!("Voxelising...");
+    let vox = vox::Voxelisation::load_or_new(
+        &out_dir.join("voxelisation.json"),
+        params.res,
+        params.bound,
+        verse.inters(),
+        verse.regions(),
+        verse.surfs(),
+    );
 
     info!("Constructing grid...");
-    let grid = raman::Grid::new(params.res, params.bound, verse.inters(), verse.surfs());
+    let boundary = params.boundary.clone().unwrap_or_else(Boundary::escape);
+    let grid = raman::Grid::new(&vox, params.refine.as_ref(), boundary, verse.mats());
+
+    let variance = params.variance.clone().unwrap_or_else(Variance::standard);
+    variance.validate(grid.res())?;
+
+    let light_map = raman::LightMap::new(
+        &grid,
+        params.binner.clone(),
+        params.time_binner.clone(),
+        &dets,
+    );
+    let state = if resume {
+        info!("Loading checkpoint...");
+        let state = raman::Checkpoint::load(&checkpoint_path)?;
+        state.validate(params.seed, params.num_phot as u64, &light_map, &variance)?;
+        state
+    } else {
+        let seed = params.seed.unwrap_or_else(|| thread_rng().gen());
+        raman::Checkpoint::new(seed, params.num_phot as u64, 0, light_map, variance.clone())
+    };
+    report!(state.seed(), "random seed");
+    report!(state.complete(), "photons complete");
 
     banner::section("Overview");
     verse.overview();
 
     banner::section("Pre-Analysis");
     info!("Saving interface map.");
-    grid.interfaces().save(&out_dir.join("interfaces.nc"));
+    grid.interfaces().save(&out_dir.join("interfaces.nc"))?;
+    info!("Saving acceleration maps.");
+    grid.tris().save(&out_dir.join("tris.nc"))?;
+    grid.accel().save(&out_dir.join("accel.nc"))?;
+    grid.depths().save(&out_dir.join("depths.nc"))?;
+    let (num_accel, accel_depth) = grid.accel_stats();
+    report!(num_accel, "accelerated cells");
+    report!(accel_depth, "max acceleration depth");
     for (key, map) in grid.mat_maps(verse.mats()).map() {
         info!("Saving {} material map.", key);
-        map.save(&out_dir.join(format!("mat_map_{}.nc", key)));
+        map.save(&out_dir.join(format!("mat_map_{}.nc", key)))?;
     }
 
     banner::section("Simulation");
     let lm = raman::run(
-        params.num_phot as u64,
-        verse.lights().get(&params.light),
+        state,
+        &checkpoint_path,
+        params.target.as_ref(),
+        &variance,
+        params.settings.data(),
+        verse.lights().try_get(&params.light)?,
         &grid,
         verse.surfs(),
         verse.mats(),
-    );
+        &dets,
+    )?;
 
     banner::section("Post-Analysis");
-    lm.save(&out_dir);
+    lm.save(&out_dir)?;
+    info!("Saving weight window targets.");
+    WeightWindow::generate(&lm.dist_travelled_dens()).save(&out_dir.join("weight_targets.nc"))?;
+    for (name, total) in FACE_NAMES.iter().zip(lm.exits().totals()) {
+        let desc = format!("{} exit energy", name);
+        report!(total, desc);
+    }
     let total_shifts = lm.recs().map(|r| r.shifts()).sum();
     let total_det_raman = lm.recs().map(|r| r.det_raman()).sum();
     let total_ram_laser = lm.recs().map(|r| r.ram_laser()).sum();
     report!("Total weights of Raman made", total_shifts);
-    report!("Total weights of detected Raman", total_det_raman);
+    report!("Total power of detected Raman", total_det_raman);
     report!("Total created Raman", total_ram_laser);
+    if let Some(probes) = params.settings.data().probes() {
+        for name in probes.keys() {
+            if let Some(Instrument::Fibre(fibre)) = lm.dets().get(&DetKey::new(name)) {
+                report!(fibre.total(), name);
+            }
+        }
+    }
 
     let mut file = BufWriter::new(
         OpenOptions::new()
@@ -85,15 +169,29 @@ pub fn main() {
     writeln!(file, "{}, {}", total_shifts, total_det_raman).unwrap();
 
     banner::section("Finished");
+
+    Ok(())
 }
 
 /// Initialise the directories.
-fn initialisation() -> (PathBuf, PathBuf, PathBuf) {
+/// Passing the trailing flag "--resume" continues from the checkpoint in the output directory.
+/// Passing the trailing flag "--time=<t>" evaluates keyframed surface transforms at time t.
+fn initialisation() -> Result<(PathBuf, PathBuf, PathBuf, bool, Option<f64>), Error> {
     args!(_bin_path: String;
         params_name: String);
+    let resume = std::env::args().skip(2).any(|arg| arg == "--resume");
+    let time = match std::env::args()
+        .skip(2)
+        .find(|arg| arg.starts_with("--time="))
+    {
+        Some(arg) => Some(arg["--time=".len()..].parse::<f64>().map_err(|err| {
+            Error::invalid("--time", &format!("unable to parse time flag: {}", err))
+        })?),
+        None => None,
+    };
 
     let (in_dir, out_dir) = init::io_dirs(None, None);
     let params_path = &in_dir.join(params_name);
 
-    (in_dir, out_dir, params_path.to_path_buf())
+    Ok((in_dir, out_dir, params_path.to_path_buf(), resume, time))
 }
@@ -2,13 +2,21 @@
 
 use crate::{
     access,
+    err::Error,
     file::Save,
     math::{Binner, Range},
 };
 use ndarray::Array1;
-use std::{fs::File, io::Write, ops::AddAssign, path::Path};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::Write,
+    ops::{AddAssign, MulAssign},
+    path::Path,
+};
 
 /// Static range, constant bin width, Histogram.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Histogram {
     /// Binner.
     binner: Binner,
@@ -79,18 +87,27 @@ impl AddAssign<&Self> for Histogram {
     }
 }
 
+impl MulAssign<f64> for Histogram {
+    #[inline]
+    fn mul_assign(&mut self, rhs: f64) {
+        self.counts *= rhs;
+    }
+}
+
 impl Save for Histogram {
     #[inline]
     #[allow(clippy::cast_precision_loss)]
-    fn save(&self, path: &Path) {
-        let mut file = File::create(path).expect("Unable to create histogram file.");
+    fn save(&self, path: &Path) -> Result<(), Error> {
+        let mut file = File::create(path).map_err(|err| Error::io(path, err))?;
 
         let bin_width = self.binner.bin_width();
         let min = self.binner.range().min();
 
         for (iter, value) in self.counts.iter().enumerate() {
             let x = (iter as f64 + 0.5).mul_add(bin_width, min);
-            writeln!(file, "{:>31}, {:>31}", x, value).expect("Failed to write to file.");
+            writeln!(file, "{:>31}, {:>31}", x, value).map_err(|err| Error::io(path, err))?;
         }
+
+        Ok(())
     }
 }
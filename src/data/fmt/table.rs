@@ -1,6 +1,6 @@
 //! Table implementation.
 
-use crate::{access, file::Save};
+use crate::{access, err::Error, file::Save};
 use ndarray::{Array2, ArrayView1};
 use std::{
     fmt::{Debug, Display, Formatter, Result},
@@ -89,9 +89,8 @@ impl<T: Display> Display for Table<T> {
 
 impl<T: Debug + Display> Save for Table<T> {
     #[inline]
-    fn save(&self, path: &Path) {
-        let mut file =
-            BufWriter::new(File::create(path).expect("Unable to create output csv file."));
-        write!(file, "{}", self).expect("Failed to write to csv file.");
+    fn save(&self, path: &Path) -> std::result::Result<(), Error> {
+        let mut file = BufWriter::new(File::create(path).map_err(|err| Error::io(path, err))?);
+        write!(file, "{}", self).map_err(|err| Error::io(path, err))
     }
 }
@@ -0,0 +1,213 @@
+//! Error implementation.
+
+use std::{
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
+    io,
+    path::{Path, PathBuf},
+};
+
+/// Crate error enumeration.
+pub enum Error {
+    /// File could not be read, created or written.
+    Io {
+        /// File path.
+        path: PathBuf,
+        /// Underlying error.
+        source: io::Error,
+    },
+    /// File contents could not be parsed.
+    Parse {
+        /// File path.
+        path: PathBuf,
+        /// Line number, counted from one, if known.
+        line: Option<usize>,
+        /// Column number, counted from one, if known.
+        col: Option<usize>,
+        /// Description of the problem.
+        msg: String,
+    },
+    /// Data could not be serialised.
+    Serialise {
+        /// File path.
+        path: PathBuf,
+        /// Description of the problem.
+        msg: String,
+    },
+    /// NetCDF file could not be written.
+    NetCdf {
+        /// File path.
+        path: PathBuf,
+        /// Description of the problem.
+        msg: String,
+    },
+    /// Key does not exist within a set.
+    MissingKey {
+        /// Requested key.
+        key: String,
+        /// Name of the set.
+        set: String,
+    },
+    /// Keyed item is not valid.
+    Invalid {
+        /// Key of the item.
+        key: String,
+        /// Description of the problem.
+        msg: String,
+    },
+    /// Keyed item could not be loaded.
+    Load {
+        /// Key of the item.
+        key: String,
+        /// Underlying error.
+        source: Box<Error>,
+    },
+    /// Error with additional context.
+    Context {
+        /// Description of what was being attempted.
+        msg: String,
+        /// Underlying error.
+        source: Box<Error>,
+    },
+}
+
+impl Error {
+    /// Construct an input/output error.
+    #[inline]
+    #[must_use]
+    pub fn io(path: &Path, source: io::Error) -> Self {
+        Self::Io {
+            path: path.to_path_buf(),
+            source,
+        }
+    }
+
+    /// Construct a parse error at the given line.
+    #[inline]
+    #[must_use]
+    pub fn parse(path: &Path, line: Option<usize>, msg: &str) -> Self {
+        Self::Parse {
+            path: path.to_path_buf(),
+            line,
+            col: None,
+            msg: msg.to_string(),
+        }
+    }
+
+    /// Construct a parse error from a json5 error message.
+    /// The position is recovered from the "--> line:column" marker of the message, if present.
+    #[inline]
+    #[must_use]
+    pub fn json(path: &Path, err: &json5::Error) -> Self {
+        let msg = err.to_string();
+
+        let pos = msg.lines().find_map(|line| {
+            let mut nums = line.trim().strip_prefix("-->")?.trim().splitn(2, ':');
+            let line = nums.next()?.parse::<usize>().ok()?;
+            let col = nums.next()?.parse::<usize>().ok()?;
+            Some((line, col))
+        });
+        let desc = msg
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("= "))
+            .map_or_else(|| msg.trim().to_string(), str::to_string);
+
+        Self::Parse {
+            path: path.to_path_buf(),
+            line: pos.map(|(line, _)| line),
+            col: pos.map(|(_, col)| col),
+            msg: desc,
+        }
+    }
+
+    /// Construct a netcdf error.
+    #[inline]
+    #[must_use]
+    pub fn netcdf<E: Debug>(path: &Path, err: &E) -> Self {
+        Self::NetCdf {
+            path: path.to_path_buf(),
+            msg: format!("{:?}", err),
+        }
+    }
+
+    /// Construct an invalid item error.
+    #[inline]
+    #[must_use]
+    pub fn invalid(key: &str, msg: &str) -> Self {
+        Self::Invalid {
+            key: key.to_string(),
+            msg: msg.to_string(),
+        }
+    }
+
+    /// Wrap the error with a description of what was being attempted.
+    #[inline]
+    #[must_use]
+    pub fn context(self, msg: &str) -> Self {
+        Self::Context {
+            msg: msg.to_string(),
+            source: Box::new(self),
+        }
+    }
+}
+
+impl Display for Error {
+    #[inline]
+    fn fmt(&self, fmt: &mut Formatter) -> FmtResult {
+        match self {
+            Self::Io { path, source } => {
+                write!(fmt, "Unable to access {}: {}", path.display(), source)
+            }
+            Self::Parse {
+                path,
+                line,
+                col,
+                msg,
+            } => {
+                write!(fmt, "Unable to parse {}", path.display())?;
+                if let Some(line) = line {
+                    write!(fmt, " at line {}", line)?;
+                    if let Some(col) = col {
+                        write!(fmt, ", column {}", col)?;
+                    }
+                }
+                write!(fmt, ": {}", msg)
+            }
+            Self::Serialise { path, msg } => {
+                write!(fmt, "Unable to serialise {}: {}", path.display(), msg)
+            }
+            Self::NetCdf { path, msg } => {
+                write!(
+                    fmt,
+                    "Unable to write netcdf file {}: {}",
+                    path.display(),
+                    msg
+                )
+            }
+            Self::MissingKey { key, set } => {
+                write!(fmt, "Key '{}' does not exist within the {} set.", key, set)
+            }
+            Self::Invalid { key, msg } => write!(fmt, "'{}' is invalid: {}", key, msg),
+            Self::Load { key, source } => write!(fmt, "Unable to load '{}'.\n\t{}", key, source),
+            Self::Context { msg, source } => write!(fmt, "{}\n\t{}", msg, source),
+        }
+    }
+}
+
+/// Errors are shown in their readable form, so that they are legible when returned from main.
+impl Debug for Error {
+    #[inline]
+    fn fmt(&self, fmt: &mut Formatter) -> FmtResult {
+        Display::fmt(self, fmt)
+    }
+}
+
+impl std::error::Error for Error {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            Self::Load { source, .. } | Self::Context { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,5 @@
+//! Error module.
+
+pub mod error;
+
+pub use self::error::*;
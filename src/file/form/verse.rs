@@ -2,10 +2,12 @@
 
 use crate::{
     access,
-    file::Surface as SurfaceForm,
+    err::Error,
+    file::{Detector as DetectorForm, Surface as SurfaceForm},
+    geom::Surface,
     ord::{
-        InterKey, InterSet, LightKey, LightSet, MatSet, MeshSet, ReactKey, ReactSet, RegionKey,
-        RegionSet, Set, SpecSet, StateSet, SurfKey, SurfSet,
+        DetKey, DetSet, InterKey, InterSet, LightKey, LightSet, MatSet, MeshSet, ReactKey,
+        ReactSet, RegionKey, RegionSet, Set, SpecSet, StateSet, SurfKey, SurfSet,
     },
     world::Verse as WorldVerse,
 };
@@ -23,6 +25,10 @@ pub struct Verse {
     reacts: Option<Vec<ReactKey>>,
     /// List of lights.
     lights: Option<Vec<LightKey>>,
+    /// List of detectors.
+    dets: Option<Vec<DetKey>>,
+    /// Time at which keyframed surface transforms are evaluated.
+    time: Option<f64>,
 }
 
 impl Verse {
@@ -30,11 +36,13 @@ impl Verse {
     access!(regions, Option<Vec<RegionKey>>);
     access!(reacts, Option<Vec<ReactKey>>);
     access!(lights, Option<Vec<LightKey>>);
+    access!(dets, Option<Vec<DetKey>>);
+    access!(time, time_mut, Option<f64>);
 
     /// Form a new instance.
+    /// Fails if any of the referenced resources can not be loaded, or if a light is given a surface it can not emit from.
     #[inline]
-    #[must_use]
-    pub fn form(&self, in_dir: &Path) -> WorldVerse {
+    pub fn form(&self, in_dir: &Path) -> Result<WorldVerse, Error> {
         let mut inter_keys = if let Some(keys) = &self.inters {
             keys.clone()
         } else {
@@ -42,7 +50,7 @@ impl Verse {
         };
         inter_keys.sort();
         inter_keys.dedup();
-        let inters: InterSet = Set::load(&in_dir.join("interfaces"), &inter_keys, "json");
+        let inters: InterSet = Set::load(&in_dir.join("interfaces"), &inter_keys, "json")?;
 
         let mut region_keys = if let Some(keys) = &self.regions {
             keys.clone()
@@ -51,7 +59,7 @@ impl Verse {
         };
         region_keys.sort();
         region_keys.dedup();
-        let regions: RegionSet = Set::load(&in_dir.join("regions"), &region_keys, "json");
+        let regions: RegionSet = Set::load(&in_dir.join("regions"), &region_keys, "json")?;
 
         let mut react_keys = if let Some(keys) = &self.reacts {
             keys.clone()
@@ -60,7 +68,7 @@ impl Verse {
         };
         react_keys.sort();
         react_keys.dedup();
-        let reacts: ReactSet = Set::load(&in_dir.join("reactions"), &react_keys, "json");
+        let reacts: ReactSet = Set::load(&in_dir.join("reactions"), &react_keys, "json")?;
 
         let mut light_keys = if let Some(keys) = &self.lights {
             keys.clone()
@@ -69,23 +77,50 @@ impl Verse {
         };
         light_keys.sort();
         light_keys.dedup();
-        let lights: LightSet = Set::load(&in_dir.join("lights"), &light_keys, "json");
+        let lights: LightSet = Set::load(&in_dir.join("lights"), &light_keys, "json")?;
+
+        let mut det_keys = if let Some(keys) = &self.dets {
+            keys.clone()
+        } else {
+            Vec::new()
+        };
+        det_keys.sort();
+        det_keys.dedup();
+        let proto_dets: Set<DetKey, DetectorForm> =
+            Set::load(&in_dir.join("detectors"), &det_keys, "json")?;
+        let dets = DetSet::build(&proto_dets);
 
         let mut mat_keys = inters.mat_keys();
         mat_keys.sort();
         mat_keys.dedup();
-        let mats: MatSet = Set::load(&in_dir.join("materials"), &mat_keys, "json");
+        let mats: MatSet =
+            Set::load(&in_dir.join("materials"), &mat_keys, "json").map_err(|err| {
+                if let Error::Load { key, .. } = &err {
+                    let users: Vec<_> = inters
+                        .mat_users(key)
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect();
+                    let msg = format!(
+                        "Material '{}' referenced by interface(s): {}",
+                        key,
+                        users.join(", ")
+                    );
+                    return err.context(&msg);
+                }
+                err
+            })?;
 
         let mut state_keys = regions.state_keys();
         state_keys.sort();
         state_keys.dedup();
-        let states: StateSet = Set::load(&in_dir.join("states"), &state_keys, "json");
+        let states: StateSet = Set::load(&in_dir.join("states"), &state_keys, "json")?;
 
         let mut spec_keys = reacts.spec_keys();
         spec_keys.append(&mut states.spec_keys());
         spec_keys.sort();
         spec_keys.dedup();
-        let specs: SpecSet = Set::load(&in_dir.join("species"), &spec_keys, "json");
+        let specs: SpecSet = Set::load(&in_dir.join("species"), &spec_keys, "json")?;
 
         let mut surf_keys = inters.surf_keys();
         surf_keys.append(&mut regions.surf_keys());
@@ -93,19 +128,32 @@ impl Verse {
         surf_keys.sort();
         surf_keys.dedup();
         let proto_surfs: Set<SurfKey, SurfaceForm> =
-            Set::load(&in_dir.join("surfaces"), &surf_keys, "json");
+            Set::load(&in_dir.join("surfaces"), &surf_keys, "json")?;
 
         let mut mesh_keys: Vec<_> = proto_surfs
             .map()
             .values()
-            .map(|surf| surf.mesh().clone())
+            .flat_map(SurfaceForm::mesh_keys)
             .collect();
         mesh_keys.sort();
         mesh_keys.dedup();
-        let meshes: MeshSet = Set::load(&in_dir.join("meshes"), &mesh_keys, "obj");
+        let meshes = MeshSet::load_meshes(&in_dir.join("meshes"), &mesh_keys)?;
 
-        let surfs = SurfSet::build(&proto_surfs, &meshes);
+        let surfs = SurfSet::build(&proto_surfs, &meshes, self.time.unwrap_or(0.0))?;
+        for (key, light) in lights.map() {
+            if matches!(
+                surfs.try_get(light.surf())?,
+                Surface::Plane(_) | Surface::Csg(_)
+            ) {
+                return Err(Error::invalid(
+                    key.str(),
+                    "lights can not emit from an infinite plane or a constructive solid geometry",
+                ));
+            }
+        }
 
-        WorldVerse::new(inters, regions, reacts, lights, mats, states, specs, surfs)
+        Ok(WorldVerse::new(
+            inters, regions, reacts, lights, dets, mats, states, specs, surfs,
+        ))
     }
 }
@@ -1,38 +1,121 @@
 //! Load trait.
 
+use crate::err::Error;
 use json5;
+use ndarray::{Array2, Array3};
 use serde::Deserialize;
-use std::{collections::BTreeMap, fs::read_to_string, path::Path};
+use std::{collections::BTreeMap, fs::read_to_string, path::Path, sync::Arc};
 
 /// Types implementing this trait can be loaded from a file.
-pub trait Load {
+pub trait Load: Sized {
     /// Deserialize the type from a given file.
-    fn load(path: &Path) -> Self;
+    /// Fails if the file can not be read or parsed.
+    fn load(path: &Path) -> Result<Self, Error>;
+}
+
+impl<T: Load> Load for Arc<T> {
+    #[inline]
+    fn load(path: &Path) -> Result<Self, Error> {
+        Ok(Self::new(T::load(path)?))
+    }
+}
+
+impl Load for Array3<f64> {
+    /// Load a datacube from the data variable of a netcdf file, as written by the corresponding save.
+    #[inline]
+    fn load(path: &Path) -> Result<Self, Error> {
+        let file = netcdf::open(path).map_err(|err| Error::netcdf(path, &err))?;
+        let var = file
+            .variable("data")
+            .ok_or_else(|| Error::parse(path, None, "Missing data variable."))?;
+
+        let shape: Vec<usize> = var.dimensions().iter().map(|dim| dim.len()).collect();
+        if shape.len() != 3 {
+            return Err(Error::parse(
+                path,
+                None,
+                "Expected a three-dimensional data variable.",
+            ));
+        }
+
+        let mut values = vec![0.0; var.len()];
+        var.values_to(&mut values, None, None)
+            .map_err(|err| Error::netcdf(path, &err))?;
+
+        Self::from_shape_vec((shape[0], shape[1], shape[2]), values)
+            .map_err(|_| Error::parse(path, None, "Unable to form datacube."))
+    }
 }
 
 /// Deserialise the type in json format.
+/// Fails if the file can not be read, or its contents can not be parsed.
 #[inline]
-#[must_use]
-pub fn from_json<T>(path: &Path) -> T
+pub fn from_json<T>(path: &Path) -> Result<T, Error>
 where
     for<'de> T: Deserialize<'de>,
 {
-    json5::from_str(
-        &read_to_string(path).unwrap_or_else(|_| panic!("Unable to read file: {}", path.display())),
+    let text = read_to_string(path).map_err(|err| Error::io(path, err))?;
+
+    json5::from_str(&text).map_err(|err| Error::json(path, &err))
+}
+
+/// Deserialise a table of numeric values from a comma-separated file.
+/// Blank lines, lines starting with '#', and a non-numeric header line are ignored.
+/// Fails if the file can not be read, or contains non-numeric or ragged rows.
+#[inline]
+pub fn from_csv(path: &Path) -> Result<Array2<f64>, Error> {
+    let text = read_to_string(path).map_err(|err| Error::io(path, err))?;
+
+    let mut rows: Vec<Vec<f64>> = Vec::new();
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let row: Result<Vec<f64>, _> = line.split(',').map(|x| x.trim().parse::<f64>()).collect();
+        match row {
+            Ok(row) => rows.push(row),
+            Err(_) if rows.is_empty() => continue,
+            Err(_) => {
+                return Err(Error::parse(
+                    path,
+                    Some(n + 1),
+                    "Unable to parse numeric values.",
+                ))
+            }
+        }
+    }
+
+    let num_cols = rows.first().map_or(0, Vec::len);
+    if let Some(n) = rows.iter().position(|row| row.len() != num_cols) {
+        return Err(Error::parse(
+            path,
+            None,
+            &format!("Row {} has an inconsistent number of columns.", n + 1),
+        ));
+    }
+
+    Ok(
+        Array2::from_shape_vec((rows.len(), num_cols), rows.concat())
+            .expect("Unable to form table."),
     )
-    .expect("Unable to parse json file.")
 }
 
 /// Load a map of instances.
+/// Fails if any of the instances can not be loaded.
 #[inline]
-#[must_use]
-pub fn map<T: Load>(dir: &Path, names: &[String], ext: &str) -> BTreeMap<String, T> {
+pub fn map<T: Load>(dir: &Path, names: &[String], ext: &str) -> Result<BTreeMap<String, T>, Error> {
     let mut map = BTreeMap::new();
 
     for name in names {
         let path = dir.join(name).with_extension(ext);
-        map.insert(name.to_string(), T::load(&path));
+        let item = T::load(&path).map_err(|err| Error::Load {
+            key: name.to_string(),
+            source: Box::new(err),
+        })?;
+        map.insert(name.to_string(), item);
     }
 
-    map
+    Ok(map)
 }
@@ -1,31 +1,50 @@
 //! Redirection during json file loading.
 
-use crate::file::Load;
-use std::path::Path;
+use crate::{access, file::Load};
+use serde::{de::Error as _, Deserialize, Serialize};
+use std::path::PathBuf;
 
 /// Redirection wrapper structure.
-#[derive(Debug)]
+/// Deserialised from a file path, relative to the input directory, from which the data is loaded.
+#[derive(Debug, Clone, PartialEq)]
 pub struct Redirect<T> {
+    /// Path to the data file.
+    path: PathBuf,
     /// Wrapped data.
     data: T,
 }
 
 impl<T> Redirect<T> {
+    access!(path, PathBuf);
+    access!(data, T);
+
     /// Construct a new instance.
     #[inline]
     #[must_use]
-    pub fn new(data: T) -> Self {
-        Self { data }
+    pub fn new(path: PathBuf, data: T) -> Self {
+        Self { path, data }
     }
 }
 
-impl<'de, T: Load> serde::Deserialize<'de> for Redirect<T> {
+impl<'de, T: Load> Deserialize<'de> for Redirect<T> {
     #[inline]
-    fn deserialize<D>(_deserializer: D) -> Result<Self, D::Error>
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        let path = Path::new("beans.txt");
-        Ok(Self::new(T::load(path)))
+        let path = PathBuf::deserialize(deserializer)?;
+        let data = T::load(&path).map_err(D::Error::custom)?;
+
+        Ok(Self::new(path, data))
+    }
+}
+
+impl<T> Serialize for Redirect<T> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.path.serialize(serializer)
     }
 }
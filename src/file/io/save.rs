@@ -1,7 +1,10 @@
 //! Save trait.
 
-use crate::list::Cartesian::{X, Y, Z};
-use ndarray::{Array2, Array3};
+use crate::{
+    err::Error,
+    list::Cartesian::{X, Y, Z},
+};
+use ndarray::{Array1, Array2, Array3, Array4};
 use netcdf::variable::Numeric;
 use serde::Serialize;
 use serde_json::to_string;
@@ -10,23 +13,26 @@ use std::{fmt::Debug, fs::write, path::Path};
 /// Types implementing this trait can be saved to file.
 pub trait Save {
     /// Serialise the type to a given file.
-    fn save(&self, path: &Path);
+    /// Fails if the file can not be written.
+    fn save(&self, path: &Path) -> Result<(), Error>;
 }
 
 /// Serialise the type in json format.
+/// Fails if the instance can not be serialised, or the file can not be written.
 #[inline]
-pub fn as_json<T: Serialize>(instance: &T, path: &Path) {
-    write(
-        path,
-        to_string(instance).expect("Unable to serialise object."),
-    )
-    .expect("Unable to write json file.");
+pub fn as_json<T: Serialize>(instance: &T, path: &Path) -> Result<(), Error> {
+    let text = to_string(instance).map_err(|err| Error::Serialise {
+        path: path.to_path_buf(),
+        msg: err.to_string(),
+    })?;
+
+    write(path, text).map_err(|err| Error::io(path, err))
 }
 
 impl<T: Debug + Numeric> Save for Array2<T> {
     #[inline]
-    fn save(&self, path: &Path) {
-        let mut file = netcdf::create(path).expect("Unable to create file.");
+    fn save(&self, path: &Path) -> Result<(), Error> {
+        let mut file = netcdf::create(path).map_err(|err| Error::netcdf(path, &err))?;
 
         let shape = self.shape();
 
@@ -35,30 +41,32 @@ impl<T: Debug + Numeric> Save for Array2<T> {
             dim1_name,
             *shape.get(X as usize).expect("Invalid dimension index."),
         )
-        .expect("Unable to add X dimension.");
+        .map_err(|err| Error::netcdf(path, &err))?;
         let dim2_name = "y";
         file.add_dimension(
             dim2_name,
             *shape.get(Y as usize).expect("Invalid dimension index."),
         )
-        .expect("Unable to add Y dimension.");
+        .map_err(|err| Error::netcdf(path, &err))?;
 
         let mut var = file
             .add_variable::<T>("data", &[dim1_name, dim2_name])
-            .expect("Unable to add dataslice entry.");
+            .map_err(|err| Error::netcdf(path, &err))?;
         var.put_values(
             self.as_slice().expect("Unable to write dataslice values."),
             None,
             None,
         )
-        .expect("Unable to store dataslice values.");
+        .map_err(|err| Error::netcdf(path, &err))?;
+
+        Ok(())
     }
 }
 
 impl<T: Debug + Numeric> Save for Array3<T> {
     #[inline]
-    fn save(&self, path: &Path) {
-        let mut file = netcdf::create(path).expect("Unable to create file.");
+    fn save(&self, path: &Path) -> Result<(), Error> {
+        let mut file = netcdf::create(path).map_err(|err| Error::netcdf(path, &err))?;
 
         let shape = self.shape();
 
@@ -67,28 +75,115 @@ impl<T: Debug + Numeric> Save for Array3<T> {
             dim1_name,
             *shape.get(X as usize).expect("Invalid dimension index."),
         )
-        .expect("Unable to add X dimension.");
+        .map_err(|err| Error::netcdf(path, &err))?;
         let dim2_name = "y";
         file.add_dimension(
             dim2_name,
             *shape.get(Y as usize).expect("Invalid dimension index."),
         )
-        .expect("Unable to add Y dimension.");
+        .map_err(|err| Error::netcdf(path, &err))?;
         let dim3_name = "z";
         file.add_dimension(
             dim3_name,
             *shape.get(Z as usize).expect("Invalid dimension index."),
         )
-        .expect("Unable to add Z dimension.");
+        .map_err(|err| Error::netcdf(path, &err))?;
 
         let mut var = file
             .add_variable::<T>("data", &[dim1_name, dim2_name, dim3_name])
-            .expect("Unable to add datacube entry.");
+            .map_err(|err| Error::netcdf(path, &err))?;
         var.put_values(
             self.as_slice().expect("Unable to write datacube values."),
             None,
             None,
         )
-        .expect("Unable to store datacube values.");
+        .map_err(|err| Error::netcdf(path, &err))?;
+
+        Ok(())
     }
 }
+
+/// Serialise a spectral datacube, with a labelled wavelength dimension, in netcdf format.
+/// Fails if the file can not be written.
+#[inline]
+pub fn as_spectral<T: Debug + Numeric>(
+    cube: &Array4<T>,
+    wavelengths: &Array1<f64>,
+    path: &Path,
+) -> Result<(), Error> {
+    as_labelled(cube, "wavelength", wavelengths, path)
+}
+
+/// Serialise a time-gated datacube, with a labelled time dimension, in netcdf format.
+/// Fails if the file can not be written.
+#[inline]
+pub fn as_temporal<T: Debug + Numeric>(
+    cube: &Array4<T>,
+    times: &Array1<f64>,
+    path: &Path,
+) -> Result<(), Error> {
+    as_labelled(cube, "time", times, path)
+}
+
+/// Serialise a datacube, with a labelled fourth dimension, in netcdf format.
+/// Fails if the file can not be written.
+#[inline]
+fn as_labelled<T: Debug + Numeric>(
+    cube: &Array4<T>,
+    label: &str,
+    coords: &Array1<f64>,
+    path: &Path,
+) -> Result<(), Error> {
+    let mut file = netcdf::create(path).map_err(|err| Error::netcdf(path, &err))?;
+
+    let shape = cube.shape();
+    debug_assert!(shape.get(3) == Some(&coords.len()));
+
+    let dim1_name = "x";
+    file.add_dimension(
+        dim1_name,
+        *shape.get(X as usize).expect("Invalid dimension index."),
+    )
+    .map_err(|err| Error::netcdf(path, &err))?;
+    let dim2_name = "y";
+    file.add_dimension(
+        dim2_name,
+        *shape.get(Y as usize).expect("Invalid dimension index."),
+    )
+    .map_err(|err| Error::netcdf(path, &err))?;
+    let dim3_name = "z";
+    file.add_dimension(
+        dim3_name,
+        *shape.get(Z as usize).expect("Invalid dimension index."),
+    )
+    .map_err(|err| Error::netcdf(path, &err))?;
+    let dim4_name = label;
+    file.add_dimension(dim4_name, coords.len())
+        .map_err(|err| Error::netcdf(path, &err))?;
+
+    let mut coord_var = file
+        .add_variable::<f64>(dim4_name, &[dim4_name])
+        .map_err(|err| Error::netcdf(path, &err))?;
+    coord_var
+        .put_values(
+            coords
+                .as_slice()
+                .expect("Unable to write coordinate values."),
+            None,
+            None,
+        )
+        .map_err(|err| Error::netcdf(path, &err))?;
+
+    let mut var = file
+        .add_variable::<T>("data", &[dim1_name, dim2_name, dim3_name, dim4_name])
+        .map_err(|err| Error::netcdf(path, &err))?;
+    var.put_values(
+        cube.as_slice()
+            .expect("Unable to write labelled datacube values."),
+        None,
+        None,
+    )
+    .map_err(|err| Error::netcdf(path, &err))?;
+
+    Ok(())
+}
@@ -0,0 +1,153 @@
+//! Detector building implementation.
+
+use crate::{
+    data::Histogram,
+    geom::{Aperture, Ray},
+    math::Binner,
+    world::{Ccd, Fibre, Instrument, Spectrometer},
+};
+use attr::json;
+use nalgebra::{Point3, Unit, Vector3};
+
+/// Detector building enumeration.
+#[json]
+pub enum Detector {
+    /// Planar charge-coupled device.
+    Ccd {
+        /// Centre of the sensor.
+        centre: Point3<f64>,
+        /// Sensor normal, facing the sample.
+        norm: Vector3<f64>,
+        /// Horizontal sensor axis.
+        horz: Vector3<f64>,
+        /// Half-widths of the sensor along the horizontal and vertical axes. [m]
+        half_widths: [f64; 2],
+        /// Image resolution.
+        res: [usize; 2],
+        /// Optional number of angle of incidence bins, otherwise angles are not resolved.
+        ang_bins: Option<usize>,
+        /// Maximum angle of incidence accepted. [rad]
+        max_ang: f64,
+        /// Optional linear polariser axis.
+        polariser: Option<Vector3<f64>>,
+        /// Optional time of flight binning of the temporal point spread function. [s]
+        tpsf: Option<Binner>,
+    },
+    /// Optical fibre.
+    Fibre {
+        /// Centre of the fibre face.
+        pos: Point3<f64>,
+        /// Direction the fibre faces.
+        dir: Vector3<f64>,
+        /// Numerical aperture.
+        na: f64,
+        /// Core radius. [m]
+        radius: f64,
+        /// Optional linear polariser axis.
+        polariser: Option<Vector3<f64>>,
+        /// Optional time of flight binning of the temporal point spread function. [s]
+        tpsf: Option<Binner>,
+    },
+    /// Fibre-coupled spectrometer.
+    Spectrometer {
+        /// Centre of the fibre face.
+        pos: Point3<f64>,
+        /// Direction the fibre faces.
+        dir: Vector3<f64>,
+        /// Numerical aperture.
+        na: f64,
+        /// Core radius. [m]
+        radius: f64,
+        /// Optional linear polariser axis.
+        polariser: Option<Vector3<f64>>,
+        /// Optional time of flight binning of the temporal point spread function. [s]
+        tpsf: Option<Binner>,
+        /// Wavelength range. [m]
+        range: [f64; 2],
+        /// Number of wavelength bins.
+        bins: u64,
+    },
+}
+
+impl Detector {
+    /// Build an instrument.
+    #[inline]
+    #[must_use]
+    pub fn build(&self) -> Instrument {
+        match self {
+            Self::Ccd {
+                centre,
+                norm,
+                horz,
+                half_widths,
+                res,
+                ang_bins,
+                max_ang,
+                polariser,
+                tpsf,
+            } => Instrument::Ccd(Ccd::new(
+                *centre,
+                Unit::new_normalize(*norm),
+                horz,
+                *half_widths,
+                *res,
+                ang_bins.unwrap_or(1),
+                *max_ang,
+                *polariser,
+                histogram(tpsf),
+            )),
+            Self::Fibre {
+                pos,
+                dir,
+                na,
+                radius,
+                polariser,
+                tpsf,
+            } => Instrument::Fibre(fibre(pos, dir, *na, *radius, polariser, tpsf)),
+            Self::Spectrometer {
+                pos,
+                dir,
+                na,
+                radius,
+                polariser,
+                tpsf,
+                range: [min, max],
+                bins,
+            } => Instrument::Spectrometer(Spectrometer::new(
+                fibre(pos, dir, *na, *radius, polariser, tpsf),
+                Histogram::new(*min, *max, *bins),
+            )),
+        }
+    }
+}
+
+/// Build a fibre from its face and numerical aperture.
+#[inline]
+#[must_use]
+fn fibre(
+    pos: &Point3<f64>,
+    dir: &Vector3<f64>,
+    na: f64,
+    radius: f64,
+    polariser: &Option<Vector3<f64>>,
+    tpsf: &Option<Binner>,
+) -> Fibre {
+    debug_assert!(na > 0.0);
+    debug_assert!(na < 1.0);
+
+    Fibre::new(
+        Aperture::new(Ray::new(*pos, Unit::new_normalize(*dir)), na.asin()),
+        radius,
+        *polariser,
+        histogram(tpsf),
+    )
+}
+
+/// Build an empty histogram from an optional binner.
+#[inline]
+#[must_use]
+fn histogram(binner: &Option<Binner>) -> Option<Histogram> {
+    binner
+        .as_ref()
+        .map(|binner| Histogram::new(binner.range().min(), binner.range().max(), binner.bins()))
+}
@@ -1,7 +1,8 @@
 //! Json sub-module.
 
+pub mod detector;
 pub mod probability;
 pub mod surface;
 pub mod transform;
 
-pub use self::{probability::*, surface::*, transform::*};
+pub use self::{detector::*, probability::*, surface::*, transform::*};
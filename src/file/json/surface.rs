@@ -1,36 +1,204 @@
-//! Mesh implementation.
+//! Surface building implementation.
 
 use crate::{
     access,
+    err::Error,
     file::Transform as FileTransform,
-    geom::{Mesh, Transform},
-    ord::{MeshKey, MeshSet},
+    geom::{
+        Csg as CsgGeom, CsgOp, Cuboid, Cylinder, Disc, Plane, Sphere, Surface as SurfaceGeom,
+        Transform,
+    },
+    ord::{MeshKey, MeshSet, SurfKey},
 };
 use attr::json;
+use nalgebra::{Point3, Unit, Vector3};
 
-/// Mesh building structure.
+/// Analytic primitive building enumeration.
+#[json]
+pub enum Primitive {
+    /// Sphere.
+    Sphere {
+        /// Central point.
+        centre: Point3<f64>,
+        /// Radius.
+        radius: f64,
+    },
+    /// Capped cylinder.
+    Cylinder {
+        /// Centre of the base cap.
+        base: Point3<f64>,
+        /// Vector from the base cap centre to the top cap centre.
+        axis: Vector3<f64>,
+        /// Radius.
+        radius: f64,
+    },
+    /// Cuboid, aligned with the axes before any transform is applied.
+    Cuboid {
+        /// Minimum bound.
+        mins: Point3<f64>,
+        /// Maximum bound.
+        maxs: Point3<f64>,
+    },
+    /// Flat disc.
+    Disc {
+        /// Central point.
+        centre: Point3<f64>,
+        /// Normal.
+        norm: Vector3<f64>,
+        /// Radius.
+        radius: f64,
+    },
+    /// Infinite plane.
+    Plane {
+        /// Point on the plane.
+        pos: Point3<f64>,
+        /// Outward normal.
+        norm: Vector3<f64>,
+    },
+}
+
+impl Primitive {
+    /// Build the primitive surface.
+    #[inline]
+    #[must_use]
+    pub fn build(&self) -> SurfaceGeom {
+        match self {
+            Self::Sphere { centre, radius } => SurfaceGeom::Sphere(Sphere::new(*centre, *radius)),
+            Self::Cylinder { base, axis, radius } => SurfaceGeom::Cylinder(Cylinder::new(
+                *base,
+                Unit::new_normalize(*axis),
+                axis.norm(),
+                *radius,
+            )),
+            Self::Cuboid { mins, maxs } => SurfaceGeom::Cuboid(Cuboid::new_aligned(*mins, *maxs)),
+            Self::Disc {
+                centre,
+                norm,
+                radius,
+            } => SurfaceGeom::Disc(Disc::new(*centre, Unit::new_normalize(*norm), *radius)),
+            Self::Plane { pos, norm } => {
+                SurfaceGeom::Plane(Plane::new(*pos, Unit::new_normalize(*norm)))
+            }
+        }
+    }
+}
+
+/// Constructive solid geometry building enumeration.
+/// Parts are themselves surfaces, and so may be meshes, primitives or further combinations.
+#[json]
+pub enum Csg {
+    /// Points inside any of the parts.
+    Union(Vec<Surface>),
+    /// Points inside all of the parts.
+    Intersection(Vec<Surface>),
+    /// Points inside the first part but none of the others.
+    Difference(Vec<Surface>),
+}
+
+impl Csg {
+    /// Reference the operation and the parts.
+    #[inline]
+    #[must_use]
+    fn op_parts(&self) -> (CsgOp, &Vec<Surface>) {
+        match self {
+            Self::Union(parts) => (CsgOp::Union, parts),
+            Self::Intersection(parts) => (CsgOp::Intersection, parts),
+            Self::Difference(parts) => (CsgOp::Difference, parts),
+        }
+    }
+
+    /// Get a list of all mesh keys used by the parts.
+    #[inline]
+    #[must_use]
+    pub fn mesh_keys(&self) -> Vec<MeshKey> {
+        self.op_parts()
+            .1
+            .iter()
+            .flat_map(Surface::mesh_keys)
+            .collect()
+    }
+
+    /// Build the combined surface, with any keyframed transforms evaluated at the given time.
+    /// Fails if there are no parts, or if any part can not be built.
+    #[inline]
+    pub fn build(&self, key: &SurfKey, meshes: &MeshSet, time: f64) -> Result<SurfaceGeom, Error> {
+        let (op, parts) = self.op_parts();
+        if parts.is_empty() {
+            return Err(Error::invalid(
+                key.str(),
+                "constructive solid geometry must be given at least one part",
+            ));
+        }
+
+        Ok(SurfaceGeom::Csg(CsgGeom::new(
+            op,
+            parts
+                .iter()
+                .map(|part| part.build(key, meshes, time))
+                .collect::<Result<_, _>>()?,
+        )))
+    }
+}
+
+/// Surface building structure.
+/// Exactly one of a mesh, a primitive or a combination must be given.
 #[json]
 pub struct Surface {
     /// Base mesh key.
-    mesh: MeshKey,
+    mesh: Option<MeshKey>,
+    /// Analytic primitive.
+    prim: Option<Primitive>,
+    /// Constructive solid geometry.
+    csg: Option<Csg>,
     /// Optional transform to apply.
     trans: Option<FileTransform>,
 }
 
 impl Surface {
-    access!(mesh, mesh_mut, MeshKey);
+    access!(mesh, mesh_mut, Option<MeshKey>);
+    access!(prim, Option<Primitive>);
+    access!(csg, Option<Csg>);
     access!(trans, Option<FileTransform>);
 
-    /// Build a mesh.
+    /// Get a list of all mesh keys used by the surface, including those of any combined parts.
     #[inline]
     #[must_use]
-    pub fn build(&self, meshes: &MeshSet) -> Mesh {
-        let mut mesh: Mesh = (*meshes.get(&self.mesh)).clone();
+    pub fn mesh_keys(&self) -> Vec<MeshKey> {
+        let mut keys: Vec<_> = self.mesh.iter().cloned().collect();
+        if let Some(csg) = &self.csg {
+            keys.append(&mut csg.mesh_keys());
+        }
+
+        keys
+    }
+
+    /// Build a surface, with any keyframed transforms evaluated at the given time.
+    /// Fails if the surface is not given exactly one of a mesh, a primitive or a combination,
+    /// or if a mesh is missing.
+    #[inline]
+    pub fn build(&self, key: &SurfKey, meshes: &MeshSet, time: f64) -> Result<SurfaceGeom, Error> {
+        let mut surf = match (&self.mesh, &self.prim, &self.csg) {
+            (Some(mesh), None, None) => SurfaceGeom::Mesh(
+                (*meshes.try_get(mesh).map_err(|err| Error::Load {
+                    key: key.to_string(),
+                    source: Box::new(err),
+                })?)
+                .clone(),
+            ),
+            (None, Some(prim), None) => prim.build(),
+            (None, None, Some(csg)) => csg.build(key, meshes, time)?,
+            _ => {
+                return Err(Error::invalid(
+                    key.str(),
+                    "surfaces must be given exactly one of a mesh, a primitive or a csg",
+                ))
+            }
+        };
 
         if let Some(trans) = &self.trans {
-            mesh.transform(&trans.build());
+            surf.transform(&trans.build(time));
         }
 
-        mesh
+        Ok(surf)
     }
 }
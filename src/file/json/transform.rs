@@ -1,9 +1,11 @@
 //! Transform implementation.
 
 use attr::json;
-use nalgebra::{Similarity3, Translation3, UnitQuaternion, Vector3};
+use nalgebra::{Affine3, Matrix4, Translation3, UnitQuaternion, Vector3};
 
 /// Json parsable transform structure.
+/// Components are applied in the order: stretch, scale, rotation, translation, matrix.
+/// Any keyframed transform is then applied, followed by each chained transform in turn.
 #[json]
 pub struct Transform {
     /// Optional translation to apply.
@@ -12,24 +14,133 @@ pub struct Transform {
     rot: Option<Vector3<f64>>,
     /// Optional uniform scaling to apply.
     scale: Option<f64>,
+    /// Optional non-uniform scaling to apply along each axis.
+    stretch: Option<Vector3<f64>>,
+    /// Optional general affine matrix to apply, given as the upper three rows.
+    matrix: Option<[[f64; 4]; 3]>,
+    /// Optional time-keyed transforms, interpolated at the build time.
+    frames: Option<Vec<(f64, Transform)>>,
+    /// Optional transforms to apply afterwards, in order.
+    then: Option<Vec<Transform>>,
 }
 
 impl Transform {
-    /// Build a transformation.
+    /// Build a transformation at the given time.
     #[inline]
     #[must_use]
-    pub fn build(&self) -> Similarity3<f64> {
-        let trans = self
-            .trans
-            .unwrap_or_else(|| Translation3::new(0.0, 0.0, 0.0));
+    pub fn build(&self, time: f64) -> Affine3<f64> {
+        let mut mat = self.components();
+
+        if let Some(frames) = &self.frames {
+            mat = Self::interpolate(frames, time) * mat;
+        }
+
+        if let Some(then) = &self.then {
+            for trans in then {
+                mat = trans.build(time).to_homogeneous() * mat;
+            }
+        }
+
+        Affine3::from_matrix_unchecked(mat)
+    }
+
+    /// Form the homogeneous matrix of the static components.
+    #[inline]
+    #[must_use]
+    fn components(&self) -> Matrix4<f64> {
+        let stretch = Matrix4::new_nonuniform_scaling(&self.stretch());
+        let scale = Matrix4::new_scaling(self.scale.unwrap_or(1.0));
+        let rot = self.rotation().to_homogeneous();
+        let trans = self.translation().to_homogeneous();
+
+        self.matrix() * trans * rot * scale * stretch
+    }
+
+    /// Get the non-uniform scaling, or unit scaling if not given.
+    #[inline]
+    #[must_use]
+    fn stretch(&self) -> Vector3<f64> {
+        self.stretch.unwrap_or_else(|| Vector3::new(1.0, 1.0, 1.0))
+    }
+
+    /// Get the rotation, or the identity if not given.
+    #[inline]
+    #[must_use]
+    fn rotation(&self) -> UnitQuaternion<f64> {
         let rot = self.rot.unwrap_or_else(|| Vector3::new(0.0, 0.0, 0.0));
-        let rot = UnitQuaternion::from_euler_angles(
+
+        UnitQuaternion::from_euler_angles(
             rot.x.to_radians(),
             rot.y.to_radians(),
             rot.z.to_radians(),
+        )
+    }
+
+    /// Get the translation, or no translation if not given.
+    #[inline]
+    #[must_use]
+    fn translation(&self) -> Translation3<f64> {
+        self.trans
+            .unwrap_or_else(|| Translation3::new(0.0, 0.0, 0.0))
+    }
+
+    /// Get the homogeneous affine matrix, or the identity if not given.
+    #[inline]
+    #[must_use]
+    fn matrix(&self) -> Matrix4<f64> {
+        self.matrix.map_or_else(Matrix4::identity, |rows| {
+            Matrix4::from_fn(|r, c| {
+                if r < 3 {
+                    rows[r][c]
+                } else if c < 3 {
+                    0.0
+                } else {
+                    1.0
+                }
+            })
+        })
+    }
+
+    /// Interpolate the components of a list of keyframes at the given time.
+    /// Times before the first or after the last keyframe take the nearest keyframe.
+    #[inline]
+    #[must_use]
+    fn interpolate(frames: &[(f64, Self)], time: f64) -> Matrix4<f64> {
+        assert!(
+            !frames.is_empty(),
+            "Keyframed transforms must be given at least one frame."
+        );
+        assert!(
+            frames.windows(2).all(|pair| pair[0].0 < pair[1].0),
+            "Keyframe times must be strictly increasing."
+        );
+        assert!(
+            frames
+                .iter()
+                .all(|(_, frame)| frame.frames.is_none() && frame.then.is_none()),
+            "Keyframes may not themselves be keyframed or chained."
+        );
+
+        let next = frames.iter().position(|(t, _)| *t > time);
+        let (prev, next) = match next {
+            None => return frames[frames.len() - 1].1.components(),
+            Some(0) => return frames[0].1.components(),
+            Some(index) => (&frames[index - 1], &frames[index]),
+        };
+        let x = (time - prev.0) / (next.0 - prev.0);
+        let (a, b) = (&prev.1, &next.1);
+
+        let stretch = Matrix4::new_nonuniform_scaling(&a.stretch().lerp(&b.stretch(), x));
+        let scale = Matrix4::new_scaling(
+            a.scale
+                .unwrap_or(1.0)
+                .mul_add(1.0 - x, b.scale.unwrap_or(1.0) * x),
         );
-        let scale = self.scale.unwrap_or(1.0);
+        let rot = a.rotation().slerp(&b.rotation(), x).to_homogeneous();
+        let trans = Translation3::from(a.translation().vector.lerp(&b.translation().vector, x))
+            .to_homogeneous();
+        let matrix = (a.matrix() * (1.0 - x)) + (b.matrix() * x);
 
-        Similarity3::from_parts(trans, rot, scale)
+        matrix * trans * rot * scale * stretch
     }
 }
@@ -0,0 +1,332 @@
+//! Mesh validation.
+
+use crate::geom::{Face, PolyMesh, Ray, Triangle};
+use nalgebra::{Point3, Unit};
+use std::collections::HashMap;
+
+/// Fraction of the squared bounding diagonal below which a triangle's area is considered degenerate.
+pub(crate) const DEGENERATE_TOL: f64 = 1.0e-12;
+
+/// Mesh validation report.
+/// Triangle indices refer to the faces of the triangulated mesh, in group order.
+#[derive(Debug, Clone)]
+pub struct MeshReport {
+    /// Number of vertices.
+    pub num_verts: usize,
+    /// Number of triangles.
+    pub num_tris: usize,
+    /// Triangles with zero area, or repeated vertices.
+    pub degenerate: Vec<usize>,
+    /// Edges used by a single triangle, which border holes.
+    pub boundary_edges: Vec<[usize; 2]>,
+    /// Edges used by more than two triangles.
+    pub non_manifold_edges: Vec<[usize; 2]>,
+    /// Edges traversed in the same direction by both of their triangles.
+    pub inconsistent_edges: Vec<[usize; 2]>,
+    /// Triangles with a vertex normal facing away from their plane normal.
+    pub flipped_normals: Vec<usize>,
+    /// Pairs of triangles, sharing no vertices, which cross each other.
+    pub self_intersections: Vec<[usize; 2]>,
+    /// Signed enclosed volume, which is negative for an inward facing closed surface.
+    pub volume: f64,
+}
+
+impl MeshReport {
+    /// Check if the mesh is closed, with every edge shared by exactly two triangles.
+    #[inline]
+    #[must_use]
+    pub fn is_closed(&self) -> bool {
+        self.boundary_edges.is_empty() && self.non_manifold_edges.is_empty()
+    }
+
+    /// Check if the mesh is a valid boundary.
+    /// It must be closed, consistently and outwardly oriented, and free of degenerate or crossing triangles.
+    #[inline]
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.is_closed()
+            && self.degenerate.is_empty()
+            && self.inconsistent_edges.is_empty()
+            && self.flipped_normals.is_empty()
+            && self.self_intersections.is_empty()
+            && self.volume > 0.0
+    }
+}
+
+impl PolyMesh {
+    /// Construct a copy of the mesh with every face triangulated.
+    #[inline]
+    #[must_use]
+    pub fn triangulated(&self) -> Self {
+        let groups = self
+            .groups()
+            .iter()
+            .map(|(name, faces)| {
+                let tris = faces
+                    .iter()
+                    .flat_map(|face| {
+                        let corners: Vec<_> = face
+                            .verts()
+                            .iter()
+                            .map(|i| *self.verts().get(*i).expect("Invalid vertex index."))
+                            .collect();
+
+                        crate::geom::triangulate(&corners)
+                            .into_iter()
+                            .map(|tri| {
+                                Face::new(
+                                    tri.iter().map(|i| face.verts()[*i]).collect(),
+                                    face.norms()
+                                        .as_ref()
+                                        .map(|ns| tri.iter().map(|i| ns[*i]).collect()),
+                                )
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .collect();
+                (name.clone(), tris)
+            })
+            .collect();
+
+        Self::new(self.verts().clone(), self.norms().clone(), groups)
+    }
+
+    /// Check the mesh for defects which would break inside-outside tests against it.
+    #[inline]
+    #[must_use]
+    pub fn check(&self) -> MeshReport {
+        let mesh = self.triangulated();
+        let faces = mesh.tri_faces();
+        let tol = DEGENERATE_TOL * mesh.diag_sq();
+
+        let degenerate: Vec<_> = faces
+            .iter()
+            .enumerate()
+            .filter(|(_, face)| mesh.is_degenerate(face, tol))
+            .map(|(i, _)| i)
+            .collect();
+
+        let mut boundary_edges = Vec::new();
+        let mut non_manifold_edges = Vec::new();
+        let mut inconsistent_edges = Vec::new();
+        for (edge, uses) in mesh.edge_map(&faces, &degenerate) {
+            match uses.as_slice() {
+                [_] => boundary_edges.push(edge),
+                [(_, a), (_, b)] => {
+                    if a == b {
+                        inconsistent_edges.push(edge);
+                    }
+                }
+                _ => non_manifold_edges.push(edge),
+            }
+        }
+        boundary_edges.sort_unstable();
+        non_manifold_edges.sort_unstable();
+        inconsistent_edges.sort_unstable();
+
+        let flipped_normals = faces
+            .iter()
+            .enumerate()
+            .filter(|(i, face)| !degenerate.contains(i) && mesh.has_flipped_norm(face))
+            .map(|(i, _)| i)
+            .collect();
+
+        let volume = faces
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !degenerate.contains(i))
+            .map(|(_, face)| mesh.signed_volume(face))
+            .sum();
+
+        MeshReport {
+            num_verts: mesh.verts().len(),
+            num_tris: faces.len(),
+            self_intersections: mesh.self_intersections(&faces, &degenerate),
+            degenerate,
+            boundary_edges,
+            non_manifold_edges,
+            inconsistent_edges,
+            flipped_normals,
+            volume,
+        }
+    }
+
+    /// Get references to all faces, in group order.
+    #[inline]
+    #[must_use]
+    pub(crate) fn tri_faces(&self) -> Vec<&Face> {
+        self.groups()
+            .iter()
+            .flat_map(|(_, faces)| faces.iter())
+            .collect()
+    }
+
+    /// Calculate the squared length of the bounding box diagonal.
+    #[inline]
+    #[must_use]
+    pub(crate) fn diag_sq(&self) -> f64 {
+        let (mins, maxs) = bounds(self.verts().iter());
+
+        (maxs - mins).magnitude_squared()
+    }
+
+    /// Get the corner positions of a triangular face.
+    #[inline]
+    #[must_use]
+    pub(crate) fn corners(&self, face: &Face) -> [Point3<f64>; 3] {
+        let vert = |n: usize| {
+            *self
+                .verts()
+                .get(face.verts()[n])
+                .expect("Invalid vertex index.")
+        };
+
+        [vert(0), vert(1), vert(2)]
+    }
+
+    /// Check if a triangular face has repeated vertices, or an area within the given tolerance.
+    #[inline]
+    #[must_use]
+    pub(crate) fn is_degenerate(&self, face: &Face, tol: f64) -> bool {
+        let [a, b, c] = [face.verts()[0], face.verts()[1], face.verts()[2]];
+        if a == b || b == c || c == a {
+            return true;
+        }
+
+        let [pa, pb, pc] = self.corners(face);
+        let area = (pb - pa).cross(&(pc - pa)).magnitude() * 0.5;
+
+        !(area > tol) || !area.is_finite()
+    }
+
+    /// Check if any vertex normal of a triangular face points away from its plane normal.
+    #[inline]
+    #[must_use]
+    pub(crate) fn has_flipped_norm(&self, face: &Face) -> bool {
+        face.norms().as_ref().map_or(false, |ns| {
+            let plane_norm = *Triangle::new(self.corners(face)).plane_norm();
+            ns.iter().any(|n| {
+                let norm: &Unit<_> = self.norms().get(*n).expect("Invalid normal index.");
+                norm.dot(&plane_norm) < 0.0
+            })
+        })
+    }
+
+    /// Calculate the signed volume of the tetrahedron formed by a triangular face and the origin.
+    #[inline]
+    #[must_use]
+    pub(crate) fn signed_volume(&self, face: &Face) -> f64 {
+        let [a, b, c] = self.corners(face);
+
+        a.coords.dot(&b.coords.cross(&c.coords)) / 6.0
+    }
+
+    /// Map each undirected edge to the triangles using it, and whether they traverse it from low to high index.
+    /// Skipped triangles are ignored.
+    #[inline]
+    #[must_use]
+    pub(crate) fn edge_map(
+        &self,
+        faces: &[&Face],
+        skip: &[usize],
+    ) -> HashMap<[usize; 2], Vec<(usize, bool)>> {
+        let mut edges: HashMap<[usize; 2], Vec<(usize, bool)>> = HashMap::new();
+
+        for (i, face) in faces.iter().enumerate() {
+            if skip.contains(&i) {
+                continue;
+            }
+
+            for n in 0..3 {
+                let a = face.verts()[n];
+                let b = face.verts()[(n + 1) % 3];
+                edges
+                    .entry([a.min(b), a.max(b)])
+                    .or_insert_with(Vec::new)
+                    .push((i, a < b));
+            }
+        }
+
+        edges
+    }
+
+    /// Find pairs of triangles, sharing no vertices, which cross each other.
+    /// Candidate pairs are found by sweeping bounding boxes along the x-axis.
+    #[inline]
+    #[must_use]
+    fn self_intersections(&self, faces: &[&Face], skip: &[usize]) -> Vec<[usize; 2]> {
+        let mut boxes: Vec<_> = faces
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !skip.contains(i))
+            .map(|(i, face)| {
+                let corners = self.corners(face);
+                let (mins, maxs) = bounds(corners.iter());
+                (i, mins, maxs, Triangle::new(corners))
+            })
+            .collect();
+        boxes.sort_by(|a, b| a.1.x.partial_cmp(&b.1.x).expect("Invalid vertex position."));
+
+        let mut hits = Vec::new();
+        for (n, (i, mins, maxs, tri)) in boxes.iter().enumerate() {
+            for (j, other_mins, other_maxs, other) in boxes.iter().skip(n + 1) {
+                if other_mins.x > maxs.x {
+                    break;
+                }
+                if other_mins.y > maxs.y
+                    || other_maxs.y < mins.y
+                    || other_mins.z > maxs.z
+                    || other_maxs.z < mins.z
+                {
+                    continue;
+                }
+                if faces[*i]
+                    .verts()
+                    .iter()
+                    .any(|v| faces[*j].verts().contains(v))
+                {
+                    continue;
+                }
+
+                if edges_cross(tri, other) || edges_cross(other, tri) {
+                    hits.push([(*i).min(*j), (*i).max(*j)]);
+                }
+            }
+        }
+        hits.sort_unstable();
+
+        hits
+    }
+}
+
+/// Determine the bounding box of a set of points.
+#[inline]
+#[must_use]
+fn bounds<'a, I: Iterator<Item = &'a Point3<f64>>>(points: I) -> (Point3<f64>, Point3<f64>) {
+    let mut mins = Point3::new(std::f64::INFINITY, std::f64::INFINITY, std::f64::INFINITY);
+    let mut maxs = -mins;
+
+    for p in points {
+        mins = mins.inf(p);
+        maxs = maxs.sup(p);
+    }
+
+    (mins, maxs)
+}
+
+/// Check if any edge of the first triangle passes through the second.
+#[inline]
+#[must_use]
+fn edges_cross(tri: &Triangle, other: &Triangle) -> bool {
+    let verts = tri.verts();
+
+    (0..3).any(|n| {
+        let start = verts[n];
+        let end = verts[(n + 1) % 3];
+        let len = nalgebra::distance(&start, &end);
+
+        other
+            .intersection_coors(&Ray::new(start, Unit::new_normalize(end - start)))
+            .map_or(false, |(dist, _)| dist < len)
+    })
+}
@@ -0,0 +1,11 @@
+//! Mesh file input and output sub-module.
+
+pub mod check;
+pub mod obj;
+pub mod ply;
+pub mod poly_mesh;
+pub mod repair;
+pub mod stl;
+pub mod triangulate;
+
+pub use self::{check::*, poly_mesh::*, triangulate::*};
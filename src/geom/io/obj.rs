@@ -0,0 +1,173 @@
+//! Wavefront OBJ loading.
+
+use crate::{
+    err::Error,
+    geom::{Face, PolyMesh, DEFAULT_GROUP},
+};
+use nalgebra::{Point3, Unit, Vector3};
+use std::{
+    fs::{read_to_string, File},
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+impl PolyMesh {
+    /// Load a mesh from a wavefront file.
+    /// Polygonal faces of any size are accepted, with optional texture and normal indices, which may be negative.
+    /// Object and group statements split the faces into named groups.
+    /// Fails if the file can not be read, or a statement is malformed.
+    #[inline]
+    pub fn load_obj(path: &Path) -> Result<Self, Error> {
+        let text = read_to_string(path).map_err(|err| Error::io(path, err))?;
+        let text = text.replace("\\\r\n", " ").replace("\\\n", " ");
+
+        let mut verts = Vec::new();
+        let mut norms = Vec::new();
+        let mut num_tex = 0;
+        let mut groups: Vec<(String, Vec<Face>)> = vec![(DEFAULT_GROUP.to_string(), Vec::new())];
+
+        for (n, line) in text.lines().enumerate() {
+            let line = line
+                .split('#')
+                .next()
+                .expect("Missing line content.")
+                .trim();
+            let mut words = line.split_whitespace();
+
+            match words.next() {
+                Some("v") => {
+                    let [x, y, z] = parse_triple(&mut words, path, n)?;
+                    verts.push(Point3::new(x, y, z));
+                }
+                Some("vn") => {
+                    let [x, y, z] = parse_triple(&mut words, path, n)?;
+                    norms.push(Unit::new_normalize(Vector3::new(x, y, z)));
+                }
+                Some("vt") => num_tex += 1,
+                Some("o") | Some("g") => {
+                    let name = words.collect::<Vec<_>>().join(" ");
+                    let name = if name.is_empty() {
+                        DEFAULT_GROUP.to_string()
+                    } else {
+                        name
+                    };
+                    if groups.last().map_or(false, |(_, faces)| faces.is_empty()) {
+                        groups.pop();
+                    }
+                    groups.push((name, Vec::new()));
+                }
+                Some("f") => {
+                    let mut face_verts = Vec::new();
+                    let mut face_norms = Vec::new();
+                    for word in words {
+                        let mut refs = word.split('/');
+                        face_verts.push(resolve(refs.next(), verts.len(), path, n)?);
+                        if let Some(tex) = refs.next() {
+                            if !tex.is_empty() {
+                                let _ = resolve(Some(tex), num_tex, path, n)?;
+                            }
+                        }
+                        if let Some(norm) = refs.next() {
+                            face_norms.push(resolve(Some(norm), norms.len(), path, n)?);
+                        }
+                    }
+
+                    if face_verts.len() < 3 {
+                        return Err(Error::parse(
+                            path,
+                            Some(n + 1),
+                            "Face with fewer than three vertices.",
+                        ));
+                    }
+                    let face_norms = if face_norms.len() == face_verts.len() {
+                        Some(face_norms)
+                    } else {
+                        None
+                    };
+
+                    groups
+                        .last_mut()
+                        .expect("Missing face group.")
+                        .1
+                        .push(Face::new(face_verts, face_norms));
+                }
+                _ => {}
+            }
+        }
+
+        groups.retain(|(_, faces)| !faces.is_empty());
+
+        Ok(Self::new(verts, norms, groups))
+    }
+
+    /// Save the mesh as a wavefront file.
+    /// Each group is written as a named group statement.
+    /// Fails if the file can not be created or written.
+    #[inline]
+    pub fn save_obj(&self, path: &Path) -> Result<(), Error> {
+        let io_err = |err| Error::io(path, err);
+        let mut file = BufWriter::new(File::create(path).map_err(io_err)?);
+
+        for v in self.verts() {
+            writeln!(file, "v {} {} {}", v.x, v.y, v.z).map_err(io_err)?;
+        }
+        for n in self.norms() {
+            writeln!(file, "vn {} {} {}", n.x, n.y, n.z).map_err(io_err)?;
+        }
+
+        for (name, faces) in self.groups() {
+            writeln!(file, "g {}", name).map_err(io_err)?;
+            for face in faces {
+                write!(file, "f").map_err(io_err)?;
+                for (n, v) in face.verts().iter().enumerate() {
+                    match face.norms() {
+                        Some(norms) => write!(file, " {}//{}", v + 1, norms[n] + 1),
+                        None => write!(file, " {}", v + 1),
+                    }
+                    .map_err(io_err)?;
+                }
+                writeln!(file).map_err(io_err)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse three floating point values from a line.
+#[inline]
+fn parse_triple<'a, I: Iterator<Item = &'a str>>(
+    words: &mut I,
+    path: &Path,
+    n: usize,
+) -> Result<[f64; 3], Error> {
+    let mut next = || {
+        words
+            .next()
+            .ok_or_else(|| Error::parse(path, Some(n + 1), "Missing value."))?
+            .parse::<f64>()
+            .map_err(|_| Error::parse(path, Some(n + 1), "Unable to parse value."))
+    };
+
+    Ok([next()?, next()?, next()?])
+}
+
+/// Resolve a one-based, or negative relative, index into a zero-based index.
+#[inline]
+fn resolve(word: Option<&str>, count: usize, path: &Path, n: usize) -> Result<usize, Error> {
+    let index = word
+        .ok_or_else(|| Error::parse(path, Some(n + 1), "Missing index."))?
+        .parse::<isize>()
+        .map_err(|_| Error::parse(path, Some(n + 1), "Unable to parse index."))?;
+
+    let resolved = if index < 0 {
+        count as isize + index
+    } else {
+        index - 1
+    };
+    if resolved < 0 || (resolved as usize) >= count {
+        return Err(Error::parse(path, Some(n + 1), "Index out of range."));
+    }
+
+    Ok(resolved as usize)
+}
@@ -0,0 +1,254 @@
+//! Polygon file format loading.
+
+use crate::{
+    err::Error,
+    geom::{Face, PolyMesh, DEFAULT_GROUP},
+};
+use nalgebra::{Point3, Unit, Vector3};
+use std::{convert::TryInto, fs::read, path::Path};
+
+/// Body encoding.
+#[derive(Clone, Copy, PartialEq)]
+enum Format {
+    /// Whitespace separated text.
+    Ascii,
+    /// Little-endian binary.
+    LittleEndian,
+    /// Big-endian binary.
+    BigEndian,
+}
+
+/// Property type.
+enum Property {
+    /// Single value.
+    Scalar(String, String),
+    /// Counted list of values.
+    List(String, String, String),
+}
+
+/// Element description.
+struct Element {
+    /// Name.
+    name: String,
+    /// Number of entries.
+    count: usize,
+    /// Properties of each entry.
+    props: Vec<Property>,
+}
+
+/// Body value reader.
+struct Reader<'a> {
+    /// File path, used when reporting errors.
+    path: &'a Path,
+    /// Encoding.
+    format: Format,
+    /// Body contents.
+    bytes: &'a [u8],
+    /// Current position.
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    /// Read the next value of the given type.
+    /// Fails if the value can not be parsed, or the body ends early.
+    #[inline]
+    fn next(&mut self, kind: &str) -> Result<f64, Error> {
+        if self.format == Format::Ascii {
+            while self.pos < self.bytes.len() && self.bytes[self.pos].is_ascii_whitespace() {
+                self.pos += 1;
+            }
+            let start = self.pos;
+            while self.pos < self.bytes.len() && !self.bytes[self.pos].is_ascii_whitespace() {
+                self.pos += 1;
+            }
+
+            return String::from_utf8_lossy(&self.bytes[start..self.pos])
+                .parse::<f64>()
+                .map_err(|_| Error::parse(self.path, None, "Unable to parse polygon file value."));
+        }
+
+        let size = match kind {
+            "char" | "uchar" | "int8" | "uint8" => 1,
+            "short" | "ushort" | "int16" | "uint16" => 2,
+            "int" | "uint" | "float" | "int32" | "uint32" | "float32" => 4,
+            "double" | "float64" => 8,
+            _ => {
+                return Err(Error::parse(
+                    self.path,
+                    None,
+                    &format!("Unknown polygon file property type: {}", kind),
+                ))
+            }
+        };
+        if self.pos + size > self.bytes.len() {
+            return Err(Error::parse(
+                self.path,
+                None,
+                "Polygon file body is truncated.",
+            ));
+        }
+        let mut raw = self.bytes[self.pos..self.pos + size].to_vec();
+        self.pos += size;
+        if self.format == Format::BigEndian {
+            raw.reverse();
+        }
+
+        Ok(match kind {
+            "char" | "int8" => f64::from(raw[0] as i8),
+            "uchar" | "uint8" => f64::from(raw[0]),
+            "short" | "int16" => f64::from(i16::from_le_bytes(
+                raw[..].try_into().expect("Invalid value."),
+            )),
+            "ushort" | "uint16" => f64::from(u16::from_le_bytes(
+                raw[..].try_into().expect("Invalid value."),
+            )),
+            "int" | "int32" => f64::from(i32::from_le_bytes(
+                raw[..].try_into().expect("Invalid value."),
+            )),
+            "uint" | "uint32" => f64::from(u32::from_le_bytes(
+                raw[..].try_into().expect("Invalid value."),
+            )),
+            "float" | "float32" => f64::from(f32::from_le_bytes(
+                raw[..].try_into().expect("Invalid value."),
+            )),
+            _ => f64::from_le_bytes(raw[..].try_into().expect("Invalid value.")),
+        })
+    }
+}
+
+impl PolyMesh {
+    /// Load a mesh from an ASCII or binary polygon file.
+    /// Vertex normals are used if all of the nx, ny and nz properties are present.
+    /// Fails if the file can not be read, or its header or body is malformed.
+    #[inline]
+    pub fn load_ply(path: &Path) -> Result<Self, Error> {
+        let bytes = read(path).map_err(|err| Error::io(path, err))?;
+
+        let header_end = bytes
+            .windows(10)
+            .position(|w| w == b"end_header")
+            .ok_or_else(|| Error::parse(path, None, "Missing polygon file header."))?;
+        let body_start = bytes[header_end..]
+            .iter()
+            .position(|b| *b == b'\n')
+            .map_or(bytes.len(), |p| header_end + p + 1);
+        let (format, elements) =
+            parse_header(&String::from_utf8_lossy(&bytes[..header_end]), path)?;
+
+        let mut reader = Reader {
+            path,
+            format,
+            bytes: &bytes[body_start..],
+            pos: 0,
+        };
+
+        let mut verts = Vec::new();
+        let mut norms = Vec::new();
+        let mut faces = Vec::new();
+        for element in &elements {
+            for _ in 0..element.count {
+                let mut scalars = Vec::new();
+                let mut lists = Vec::new();
+                for prop in &element.props {
+                    match prop {
+                        Property::Scalar(name, kind) => {
+                            scalars.push((name.as_str(), reader.next(kind)?));
+                        }
+                        Property::List(name, count_kind, kind) => {
+                            let count = reader.next(count_kind)? as usize;
+                            let values = (0..count)
+                                .map(|_| reader.next(kind))
+                                .collect::<Result<Vec<_>, _>>()?;
+                            lists.push((name.as_str(), values));
+                        }
+                    }
+                }
+
+                let get = |key: &str| scalars.iter().find(|(k, _)| *k == key).map(|(_, v)| *v);
+                let require = |key: &str| {
+                    get(key).ok_or_else(|| {
+                        Error::parse(path, None, &format!("Missing vertex {} property.", key))
+                    })
+                };
+                match element.name.as_str() {
+                    "vertex" => {
+                        verts.push(Point3::new(require("x")?, require("y")?, require("z")?));
+                        if let (Some(nx), Some(ny), Some(nz)) = (get("nx"), get("ny"), get("nz")) {
+                            norms.push(Unit::new_normalize(Vector3::new(nx, ny, nz)));
+                        }
+                    }
+                    "face" => {
+                        let (_, indices) = lists
+                            .iter()
+                            .find(|(k, _)| *k == "vertex_indices" || *k == "vertex_index")
+                            .ok_or_else(|| {
+                                Error::parse(path, None, "Missing face vertex indices.")
+                            })?;
+                        faces.push(indices.iter().map(|i| *i as usize).collect::<Vec<_>>());
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let smooth = !norms.is_empty() && norms.len() == verts.len();
+        if faces.iter().flatten().any(|i| *i >= verts.len()) {
+            return Err(Error::parse(path, None, "Face vertex index out of range."));
+        }
+        let faces = faces
+            .into_iter()
+            .map(|face| {
+                let face_norms = if smooth { Some(face.clone()) } else { None };
+                Face::new(face, face_norms)
+            })
+            .collect();
+
+        Ok(Self::new(
+            verts,
+            if smooth { norms } else { Vec::new() },
+            vec![(DEFAULT_GROUP.to_string(), faces)],
+        ))
+    }
+}
+
+/// Parse the header into the body format and element descriptions.
+#[inline]
+fn parse_header(header: &str, path: &Path) -> Result<(Format, Vec<Element>), Error> {
+    let mut format = None;
+    let mut elements: Vec<Element> = Vec::new();
+
+    for (n, line) in header.lines().enumerate() {
+        let err = |msg: &str| Error::parse(path, Some(n + 1), msg);
+        let words: Vec<_> = line.split_whitespace().collect();
+        match words.as_slice() {
+            ["format", "ascii", ..] => format = Some(Format::Ascii),
+            ["format", "binary_little_endian", ..] => format = Some(Format::LittleEndian),
+            ["format", "binary_big_endian", ..] => format = Some(Format::BigEndian),
+            ["element", name, count] => elements.push(Element {
+                name: (*name).to_string(),
+                count: count.parse().map_err(|_| err("Invalid element count."))?,
+                props: Vec::new(),
+            }),
+            ["property", "list", count_kind, kind, name] => elements
+                .last_mut()
+                .ok_or_else(|| err("Property declared before any element."))?
+                .props
+                .push(Property::List(
+                    (*name).to_string(),
+                    (*count_kind).to_string(),
+                    (*kind).to_string(),
+                )),
+            ["property", kind, name] => elements
+                .last_mut()
+                .ok_or_else(|| err("Property declared before any element."))?
+                .props
+                .push(Property::Scalar((*name).to_string(), (*kind).to_string())),
+            _ => {}
+        }
+    }
+
+    Ok((
+        format.ok_or_else(|| Error::parse(path, None, "Missing polygon file format."))?,
+        elements,
+    ))
+}
@@ -0,0 +1,215 @@
+//! Polygon mesh implementation.
+
+use crate::{
+    access,
+    err::Error,
+    file::{Load, Save},
+    geom::{triangulate, SmoothTriangle, Triangle},
+};
+use log::warn;
+use nalgebra::{Point3, Unit, Vector3};
+use std::{collections::HashMap, path::Path};
+
+/// Name given to faces listed before any object or group statement.
+pub const DEFAULT_GROUP: &str = "default";
+
+/// Polygonal face structure.
+#[derive(Debug, Clone)]
+pub struct Face {
+    /// Vertex indices.
+    verts: Vec<usize>,
+    /// Optional vertex normal indices.
+    norms: Option<Vec<usize>>,
+}
+
+impl Face {
+    access!(verts, verts_mut, Vec<usize>);
+    access!(norms, Option<Vec<usize>>);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(verts: Vec<usize>, norms: Option<Vec<usize>>) -> Self {
+        debug_assert!(norms.as_ref().map_or(true, |ns| ns.len() == verts.len()));
+
+        Self { verts, norms }
+    }
+}
+
+/// Indexed polygon mesh structure.
+/// Intermediate form of loaded mesh files, which keeps the connectivity lost by a triangle list.
+#[derive(Debug, Clone)]
+pub struct PolyMesh {
+    /// Vertex positions.
+    verts: Vec<Point3<f64>>,
+    /// Vertex normals.
+    norms: Vec<Unit<Vector3<f64>>>,
+    /// Named groups of faces.
+    groups: Vec<(String, Vec<Face>)>,
+}
+
+impl PolyMesh {
+    access!(verts, verts_mut, Vec<Point3<f64>>);
+    access!(norms, Vec<Unit<Vector3<f64>>>);
+    access!(groups, groups_mut, Vec<(String, Vec<Face>)>);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(
+        verts: Vec<Point3<f64>>,
+        norms: Vec<Unit<Vector3<f64>>>,
+        groups: Vec<(String, Vec<Face>)>,
+    ) -> Self {
+        Self {
+            verts,
+            norms,
+            groups,
+        }
+    }
+
+    /// Construct a new instance from a triangle soup, merging coincident vertices.
+    #[inline]
+    #[must_use]
+    pub fn new_welded(groups: Vec<(String, Vec<[Point3<f64>; 3]>)>) -> Self {
+        let mut verts = Vec::new();
+        let mut indices: HashMap<[u64; 3], usize> = HashMap::new();
+
+        let groups = groups
+            .into_iter()
+            .map(|(name, tris)| {
+                let faces = tris
+                    .iter()
+                    .map(|tri| {
+                        Face::new(
+                            tri.iter()
+                                .map(|v| {
+                                    *indices
+                                        .entry([v.x.to_bits(), v.y.to_bits(), v.z.to_bits()])
+                                        .or_insert_with(|| {
+                                            verts.push(*v);
+                                            verts.len() - 1
+                                        })
+                                })
+                                .collect(),
+                            None,
+                        )
+                    })
+                    .collect();
+                (name, faces)
+            })
+            .collect();
+
+        Self::new(verts, Vec::new(), groups)
+    }
+
+    /// Get the total number of faces.
+    #[inline]
+    #[must_use]
+    pub fn num_faces(&self) -> usize {
+        self.groups.iter().map(|(_, faces)| faces.len()).sum()
+    }
+
+    /// Get the group names.
+    #[inline]
+    #[must_use]
+    pub fn group_names(&self) -> Vec<&str> {
+        self.groups.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    /// Triangulate the faces of all groups, or those of a single named group.
+    /// Faces without vertex normals are given the flat normal of each triangle.
+    /// Degenerate triangles are dropped.
+    /// Fails if the mesh does not contain the named group.
+    #[inline]
+    pub fn tris(&self, group: Option<&str>) -> Result<Vec<SmoothTriangle>, Error> {
+        if let Some(name) = group {
+            if !self.groups.iter().any(|(g, _)| g == name) {
+                return Err(Error::MissingKey {
+                    key: name.to_string(),
+                    set: "mesh group".to_string(),
+                });
+            }
+        }
+
+        let mut tris = Vec::with_capacity(self.num_faces());
+        let mut num_degenerate = 0;
+        for (_, faces) in self
+            .groups
+            .iter()
+            .filter(|(name, _)| group.map_or(true, |g| g == name))
+        {
+            for face in faces {
+                let corners: Vec<_> = face
+                    .verts
+                    .iter()
+                    .map(|i| *self.verts.get(*i).expect("Invalid vertex index."))
+                    .collect();
+
+                for [a, b, c] in triangulate(&corners) {
+                    let tri = Triangle::new([corners[a], corners[b], corners[c]]);
+                    if tri.area() <= 0.0 || !tri.plane_norm().iter().all(|x| x.is_finite()) {
+                        num_degenerate += 1;
+                        continue;
+                    }
+
+                    let norms = if let Some(norms) = &face.norms {
+                        [
+                            *self.norms.get(norms[a]).expect("Invalid normal index."),
+                            *self.norms.get(norms[b]).expect("Invalid normal index."),
+                            *self.norms.get(norms[c]).expect("Invalid normal index."),
+                        ]
+                    } else {
+                        let norm = *tri.plane_norm();
+                        [norm, norm, norm]
+                    };
+
+                    tris.push(SmoothTriangle::new(tri, norms));
+                }
+            }
+        }
+
+        if num_degenerate > 0 {
+            warn!("Dropped {} degenerate triangles.", num_degenerate);
+        }
+
+        Ok(tris)
+    }
+}
+
+impl Load for PolyMesh {
+    /// Load a mesh file, selecting the format by the file extension.
+    #[inline]
+    fn load(path: &Path) -> Result<Self, Error> {
+        match path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_lowercase)
+            .as_deref()
+        {
+            Some("obj") => Self::load_obj(path),
+            Some("stl") => Self::load_stl(path),
+            Some("ply") => Self::load_ply(path),
+            _ => Err(Error::parse(path, None, "Unsupported mesh file format.")),
+        }
+    }
+}
+
+impl Save for PolyMesh {
+    /// Save the mesh, selecting the format by the file extension.
+    #[inline]
+    fn save(&self, path: &Path) -> Result<(), Error> {
+        match path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_lowercase)
+            .as_deref()
+        {
+            Some("obj") => self.save_obj(path),
+            _ => Err(Error::Serialise {
+                path: path.to_path_buf(),
+                msg: "Unsupported mesh output format.".to_string(),
+            }),
+        }
+    }
+}
@@ -0,0 +1,188 @@
+//! Mesh repair.
+
+use crate::geom::{io::check::DEGENERATE_TOL, Face, PolyMesh};
+use std::collections::{HashMap, VecDeque};
+
+impl PolyMesh {
+    /// Construct a triangulated copy of the mesh with the defects which can be fixed automatically removed.
+    /// Coincident vertices are merged and degenerate triangles are dropped.
+    /// Triangle windings are made consistent across shared edges, and closed pieces are turned to face outwards.
+    /// Vertex normals which still disagree with the winding are replaced by flat shading.
+    /// Holes, non-manifold edges and self-intersections are left for the user.
+    #[inline]
+    #[must_use]
+    pub fn repaired(&self) -> Self {
+        let mut mesh = self.welded().triangulated();
+        let tol = DEGENERATE_TOL * mesh.diag_sq();
+
+        let degenerate: Vec<_> = mesh
+            .tri_faces()
+            .iter()
+            .map(|face| mesh.is_degenerate(face, tol))
+            .collect();
+        mesh.filter_faces(|n, face| {
+            if degenerate[n] {
+                None
+            } else {
+                Some(face.clone())
+            }
+        });
+
+        let flips = mesh.orientation_flips();
+        mesh.filter_faces(|n, face| {
+            Some(if flips[n] {
+                Face::new(
+                    face.verts().iter().rev().copied().collect(),
+                    face.norms()
+                        .as_ref()
+                        .map(|ns| ns.iter().rev().copied().collect()),
+                )
+            } else {
+                face.clone()
+            })
+        });
+
+        let flat: Vec<_> = mesh
+            .tri_faces()
+            .iter()
+            .map(|face| mesh.has_flipped_norm(face))
+            .collect();
+        mesh.filter_faces(|n, face| {
+            Some(if flat[n] {
+                Face::new(face.verts().clone(), None)
+            } else {
+                face.clone()
+            })
+        });
+
+        mesh
+    }
+
+    /// Replace, or remove, each face in group order.
+    /// Groups left without faces are removed.
+    #[inline]
+    fn filter_faces<F: FnMut(usize, &Face) -> Option<Face>>(&mut self, mut f: F) {
+        let mut n = 0;
+        for (_, faces) in self.groups_mut() {
+            *faces = faces
+                .iter()
+                .filter_map(|face| {
+                    n += 1;
+                    f(n - 1, face)
+                })
+                .collect();
+        }
+        self.groups_mut().retain(|(_, faces)| !faces.is_empty());
+    }
+
+    /// Construct a copy of the mesh with vertices at identical positions merged.
+    #[inline]
+    #[must_use]
+    fn welded(&self) -> Self {
+        let mut verts = Vec::new();
+        let mut indices: HashMap<[u64; 3], usize> = HashMap::new();
+        let map: Vec<_> = self
+            .verts()
+            .iter()
+            .map(|v| {
+                *indices
+                    .entry([v.x.to_bits(), v.y.to_bits(), v.z.to_bits()])
+                    .or_insert_with(|| {
+                        verts.push(*v);
+                        verts.len() - 1
+                    })
+            })
+            .collect();
+
+        let groups = self
+            .groups()
+            .iter()
+            .map(|(name, faces)| {
+                let faces = faces
+                    .iter()
+                    .map(|face| {
+                        Face::new(
+                            face.verts().iter().map(|i| map[*i]).collect(),
+                            face.norms().clone(),
+                        )
+                    })
+                    .collect();
+                (name.clone(), faces)
+            })
+            .collect();
+
+        Self::new(verts, self.norms().clone(), groups)
+    }
+
+    /// Determine which triangles must be reversed to orient each connected piece consistently.
+    /// Orientation spreads across manifold edges from the first triangle of each piece.
+    /// Closed pieces enclosing a negative volume are then reversed entirely.
+    #[inline]
+    #[must_use]
+    fn orientation_flips(&self) -> Vec<bool> {
+        let faces = self.tri_faces();
+        let edges = self.edge_map(&faces, &[]);
+
+        let mut neighbours = vec![Vec::new(); faces.len()];
+        let mut open = vec![false; faces.len()];
+        for uses in edges.values() {
+            match uses.as_slice() {
+                [(a, a_dir), (b, b_dir)] => {
+                    neighbours[*a].push((*b, a_dir == b_dir));
+                    neighbours[*b].push((*a, a_dir == b_dir));
+                }
+                _ => {
+                    for (i, _) in uses {
+                        open[*i] = true;
+                    }
+                }
+            }
+        }
+
+        let mut flips: Vec<Option<bool>> = vec![None; faces.len()];
+        for seed in 0..faces.len() {
+            if flips[seed].is_some() {
+                continue;
+            }
+
+            let mut piece = vec![seed];
+            let mut queue = VecDeque::new();
+            flips[seed] = Some(false);
+            queue.push_back(seed);
+            while let Some(i) = queue.pop_front() {
+                let flip = flips[i].expect("Missing orientation.");
+                for (j, same_dir) in &neighbours[i] {
+                    if flips[*j].is_none() {
+                        flips[*j] = Some(flip ^ same_dir);
+                        piece.push(*j);
+                        queue.push_back(*j);
+                    }
+                }
+            }
+
+            if piece.iter().all(|i| !open[*i]) {
+                let volume: f64 = piece
+                    .iter()
+                    .map(|i| {
+                        let vol = self.signed_volume(faces[*i]);
+                        if flips[*i] == Some(true) {
+                            -vol
+                        } else {
+                            vol
+                        }
+                    })
+                    .sum();
+                if volume < 0.0 {
+                    for i in piece {
+                        flips[i] = flips[i].map(|f| !f);
+                    }
+                }
+            }
+        }
+
+        flips
+            .into_iter()
+            .map(|f| f.expect("Missing orientation."))
+            .collect()
+    }
+}
@@ -0,0 +1,150 @@
+//! Stereolithography loading.
+
+use crate::{
+    err::Error,
+    geom::{PolyMesh, DEFAULT_GROUP},
+};
+use nalgebra::Point3;
+use std::{convert::TryInto, fs::read, path::Path};
+
+/// Size of the binary header in bytes.
+const HEADER_SIZE: usize = 80;
+
+/// Size of each binary facet record in bytes.
+const FACET_SIZE: usize = 50;
+
+impl PolyMesh {
+    /// Load a mesh from a binary or ASCII stereolithography file.
+    /// Coincident vertices are merged, and facet normals are ignored in favour of the vertex winding.
+    /// Fails if the file can not be read, or is truncated or malformed.
+    #[inline]
+    pub fn load_stl(path: &Path) -> Result<Self, Error> {
+        let bytes = read(path).map_err(|err| Error::io(path, err))?;
+
+        Ok(if is_binary(&bytes) {
+            Self::new_welded(vec![(
+                DEFAULT_GROUP.to_string(),
+                parse_binary(&bytes, path)?,
+            )])
+        } else {
+            Self::new_welded(parse_ascii(&String::from_utf8_lossy(&bytes), path)?)
+        })
+    }
+}
+
+/// Determine if the file contents are in the binary format.
+/// Binary files may also begin with "solid", so the facet count must match the file size.
+#[inline]
+#[must_use]
+fn is_binary(bytes: &[u8]) -> bool {
+    if bytes.len() < HEADER_SIZE + 4 {
+        return false;
+    }
+
+    let num_facets = u32::from_le_bytes(
+        bytes[HEADER_SIZE..HEADER_SIZE + 4]
+            .try_into()
+            .expect("Invalid facet count."),
+    ) as usize;
+
+    bytes.len() == HEADER_SIZE + 4 + (num_facets * FACET_SIZE) || !starts_with_solid(bytes)
+}
+
+/// Check if the file contents begin with the ASCII "solid" keyword.
+#[inline]
+#[must_use]
+fn starts_with_solid(bytes: &[u8]) -> bool {
+    let start = bytes
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or_else(|| bytes.len());
+
+    bytes[start..].starts_with(b"solid")
+}
+
+/// Parse the triangles of a binary file.
+#[inline]
+fn parse_binary(bytes: &[u8], path: &Path) -> Result<Vec<[Point3<f64>; 3]>, Error> {
+    let num_facets = u32::from_le_bytes(
+        bytes[HEADER_SIZE..HEADER_SIZE + 4]
+            .try_into()
+            .expect("Invalid facet count."),
+    ) as usize;
+    if bytes.len() < HEADER_SIZE + 4 + (num_facets * FACET_SIZE) {
+        return Err(Error::parse(
+            path,
+            None,
+            "Binary stereolithography file is truncated.",
+        ));
+    }
+
+    let value = |offset: usize| -> f64 {
+        f64::from(f32::from_le_bytes(
+            bytes[offset..offset + 4]
+                .try_into()
+                .expect("Invalid float value."),
+        ))
+    };
+    let point = |offset: usize| Point3::new(value(offset), value(offset + 4), value(offset + 8));
+
+    Ok((0..num_facets)
+        .map(|i| {
+            let start = HEADER_SIZE + 4 + (i * FACET_SIZE) + 12;
+            [point(start), point(start + 12), point(start + 24)]
+        })
+        .collect())
+}
+
+/// Parse the named solids of an ASCII file.
+#[inline]
+fn parse_ascii(text: &str, path: &Path) -> Result<Vec<(String, Vec<[Point3<f64>; 3]>)>, Error> {
+    let mut solids = Vec::new();
+    let mut name = DEFAULT_GROUP.to_string();
+    let mut tris = Vec::new();
+    let mut corners = Vec::with_capacity(3);
+
+    for (n, line) in text.lines().enumerate() {
+        let mut words = line.split_whitespace();
+        match words.next() {
+            Some("solid") => {
+                let solid = words.collect::<Vec<_>>().join(" ");
+                name = if solid.is_empty() {
+                    DEFAULT_GROUP.to_string()
+                } else {
+                    solid
+                };
+            }
+            Some("vertex") => {
+                let mut next = || {
+                    words
+                        .next()
+                        .ok_or_else(|| Error::parse(path, Some(n + 1), "Missing value."))?
+                        .parse::<f64>()
+                        .map_err(|_| Error::parse(path, Some(n + 1), "Unable to parse value."))
+                };
+                corners.push(Point3::new(next()?, next()?, next()?));
+            }
+            Some("endloop") => {
+                if corners.len() != 3 {
+                    return Err(Error::parse(
+                        path,
+                        Some(n + 1),
+                        "Facet without three vertices.",
+                    ));
+                }
+                tris.push([corners[0], corners[1], corners[2]]);
+                corners.clear();
+            }
+            Some("endsolid") => {
+                solids.push((name.clone(), std::mem::replace(&mut tris, Vec::new())));
+            }
+            _ => {}
+        }
+    }
+
+    if !tris.is_empty() {
+        solids.push((name, tris));
+    }
+
+    Ok(solids)
+}
@@ -0,0 +1,105 @@
+//! Polygon triangulation functions.
+
+use nalgebra::{Point3, Vector3};
+
+/// Determine the Newell normal of a polygon.
+/// The result is not normalised, and its magnitude is twice the polygon's area.
+#[inline]
+#[must_use]
+pub fn newell_norm(verts: &[Point3<f64>]) -> Vector3<f64> {
+    let mut norm = Vector3::zeros();
+
+    for (i, curr) in verts.iter().enumerate() {
+        let next = verts[(i + 1) % verts.len()];
+        norm.x += (curr.y - next.y) * (curr.z + next.z);
+        norm.y += (curr.z - next.z) * (curr.x + next.x);
+        norm.z += (curr.x - next.x) * (curr.y + next.y);
+    }
+
+    norm
+}
+
+/// Triangulate a simple planar polygon by ear clipping.
+/// Returned triangles index the given vertices, and preserve the polygon's winding.
+/// Polygons which can not be clipped, such as degenerate or self-intersecting ones, are triangulated as a fan.
+#[inline]
+#[must_use]
+pub fn triangulate(verts: &[Point3<f64>]) -> Vec<[usize; 3]> {
+    if verts.len() < 3 {
+        return Vec::new();
+    }
+    if verts.len() == 3 {
+        return vec![[0, 1, 2]];
+    }
+
+    let norm = newell_norm(verts);
+    if norm.magnitude_squared() <= 0.0 {
+        return fan(0..verts.len());
+    }
+
+    let mut remaining: Vec<usize> = (0..verts.len()).collect();
+    let mut tris = Vec::with_capacity(verts.len() - 2);
+
+    while remaining.len() > 3 {
+        let n = remaining.len();
+        let ear = (0..n).find(|&i| {
+            let prev = remaining[(i + n - 1) % n];
+            let curr = remaining[i];
+            let next = remaining[(i + 1) % n];
+
+            is_ear(verts, &norm, [prev, curr, next], &remaining)
+        });
+
+        if let Some(i) = ear {
+            tris.push([
+                remaining[(i + n - 1) % n],
+                remaining[i],
+                remaining[(i + 1) % n],
+            ]);
+            remaining.remove(i);
+        } else {
+            tris.append(&mut fan(remaining.into_iter()));
+            return tris;
+        }
+    }
+    tris.push([remaining[0], remaining[1], remaining[2]]);
+
+    tris
+}
+
+/// Triangulate the given vertex indices as a fan about the first.
+#[inline]
+#[must_use]
+fn fan<I: Iterator<Item = usize>>(indices: I) -> Vec<[usize; 3]> {
+    let indices: Vec<_> = indices.collect();
+
+    indices
+        .windows(2)
+        .skip(1)
+        .map(|pair| [indices[0], pair[0], pair[1]])
+        .collect()
+}
+
+/// Check if the corner formed by three consecutive vertices is an ear of the remaining polygon.
+/// The corner must be convex, and contain no other remaining vertex.
+#[inline]
+#[must_use]
+fn is_ear(
+    verts: &[Point3<f64>],
+    norm: &Vector3<f64>,
+    tri: [usize; 3],
+    remaining: &[usize],
+) -> bool {
+    let [a, b, c] = [verts[tri[0]], verts[tri[1]], verts[tri[2]]];
+
+    if (b - a).cross(&(c - b)).dot(norm) <= 0.0 {
+        return false;
+    }
+
+    !remaining.iter().filter(|i| !tri.contains(i)).any(|i| {
+        let p = verts[*i];
+        (b - a).cross(&(p - a)).dot(norm) >= 0.0
+            && (c - b).cross(&(p - b)).dot(norm) >= 0.0
+            && (a - c).cross(&(p - c)).dot(norm) >= 0.0
+    })
+}
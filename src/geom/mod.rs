@@ -1,6 +1,7 @@
 //! Geometry module.
 
+pub mod io;
 pub mod rt;
 pub mod surf;
 
-pub use self::{rt::*, surf::*};
+pub use self::{io::*, rt::*, surf::*};
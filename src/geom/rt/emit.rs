@@ -2,20 +2,20 @@
 
 use crate::geom::Ray;
 use nalgebra::{Point3, Unit, Vector3};
-use rand::{rngs::ThreadRng, Rng};
+use rand::Rng;
 use std::f64::consts::PI;
 
 /// Emit trait implementation.
 /// Types implementing this trait can cast rays.
 pub trait Emit {
     /// Cast a new ray from a random position on the surface with a corresponding normal direction.
-    fn cast(&self, rng: &mut ThreadRng) -> Ray;
+    fn cast<R: Rng>(&self, rng: &mut R) -> Ray;
 }
 
 impl Emit for Point3<f64> {
     #[inline]
     #[must_use]
-    fn cast(&self, rng: &mut ThreadRng) -> Ray {
+    fn cast<R: Rng>(&self, rng: &mut R) -> Ray {
         let theta = rng.gen_range(0.0, 2.0 * PI);
         let z = rng.gen_range(-1.0, 1.0);
 
@@ -2,7 +2,9 @@
 
 pub mod emit;
 pub mod ray;
+pub mod scan;
 pub mod side;
 pub mod trace;
+pub mod tri_tree;
 
-pub use self::{emit::*, ray::*, side::*, trace::*};
+pub use self::{emit::*, ray::*, scan::*, side::*, trace::*, tri_tree::*};
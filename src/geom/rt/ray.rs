@@ -2,10 +2,11 @@
 
 use crate::{access, geom::Emit};
 use nalgebra::{Point3, Rotation3, Unit, Vector3};
-use rand::rngs::ThreadRng;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 
 /// Ray structure.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Ray {
     /// Ray origin.
     pos: Point3<f64>,
@@ -54,7 +55,7 @@ impl Ray {
 impl Emit for Ray {
     #[inline]
     #[must_use]
-    fn cast(&self, _rng: &mut ThreadRng) -> Ray {
+    fn cast<R: Rng>(&self, _rng: &mut R) -> Ray {
         self.clone()
     }
 }
@@ -0,0 +1,180 @@
+//! Scanline implementation.
+
+use crate::{
+    access,
+    geom::{Aabb, Collide, Patch, Ray},
+};
+use nalgebra::{Point3, Unit, Vector3};
+use ndarray::Array3;
+use rayon::prelude::*;
+
+/// Fractional offset of scan rays from the column centres.
+/// Prevents rays from running exactly along the shared edges of triangulated faces.
+const JITTER: [f64; 2] = [1.414_213e-4, 1.732_051e-4];
+
+/// Scanline structure.
+/// Tagged surface patches are binned into columns by their footprint in the xy-plane,
+/// so that each z-directed scan ray only tests the patches lying above or below it.
+pub struct Scan<'a, T> {
+    /// Boundary.
+    bound: Aabb,
+    /// Column resolution.
+    res: [usize; 2],
+    /// Height from which all scan rays start.
+    base: f64,
+    /// Patches overlapping each column.
+    columns: Vec<Vec<(T, Patch<'a>)>>,
+}
+
+impl<'a, T: Copy> Scan<'a, T> {
+    access!(bound, Aabb);
+    access!(res, [usize; 2]);
+
+    /// Construct a new instance.
+    /// Patches lying above or below the boundary are kept, but those entirely outside its footprint are not.
+    /// Scan rays start below every patch of finite extent, while primitives are crossed along the whole ray line.
+    #[inline]
+    #[must_use]
+    pub fn new(bound: Aabb, res: [usize; 2], patches: &[(T, Patch<'a>)]) -> Self {
+        debug_assert!(res[0] > 0);
+        debug_assert!(res[1] > 0);
+
+        let mins = bound.mins();
+        let widths = bound.widths();
+        let col_index = |x: f64, n: usize, min: f64, width: f64| -> isize {
+            ((x - min) / width * n as f64).floor() as isize
+        };
+
+        let mut base = mins.z - widths.z;
+        let mut columns = vec![Vec::new(); res[0] * res[1]];
+        for (tag, patch) in patches {
+            let (lo, hi) = patch.bounding_box().mins_maxs();
+
+            let x0 = col_index(lo.x, res[0], mins.x, widths.x).max(0);
+            let x1 = col_index(hi.x, res[0], mins.x, widths.x).min(res[0] as isize - 1);
+            let y0 = col_index(lo.y, res[1], mins.y, widths.y).max(0);
+            let y1 = col_index(hi.y, res[1], mins.y, widths.y).min(res[1] as isize - 1);
+            if x0 > x1 || y0 > y1 {
+                continue;
+            }
+
+            if lo.z.is_finite() {
+                base = base.min(lo.z - widths.z);
+            }
+            for xi in x0..=x1 {
+                for yi in y0..=y1 {
+                    columns[xi as usize * res[1] + yi as usize].push((*tag, *patch));
+                }
+            }
+        }
+
+        Self {
+            bound,
+            res,
+            base,
+            columns,
+        }
+    }
+
+    /// Determine the column index containing the given position.
+    #[inline]
+    #[must_use]
+    fn column(&self, x: f64, y: f64) -> usize {
+        let mins = self.bound.mins();
+        let widths = self.bound.widths();
+
+        let xi =
+            (((x - mins.x) / widths.x * self.res[0] as f64).floor() as usize).min(self.res[0] - 1);
+        let yi =
+            (((y - mins.y) / widths.y * self.res[1] as f64).floor() as usize).min(self.res[1] - 1);
+
+        xi * self.res[1] + yi
+    }
+
+    /// Determine the ordered heights, sides and tags of the surface crossings above a position in the xy-plane.
+    /// The side is true if the scan ray leaves the surface at that crossing.
+    #[inline]
+    #[must_use]
+    fn hits(&self, x: f64, y: f64) -> Vec<(f64, bool, T)> {
+        let widths = self.bound.widths();
+        let x = x + (JITTER[0] * widths.x / self.res[0] as f64);
+        let y = y + (JITTER[1] * widths.y / self.res[1] as f64);
+
+        let ray = Ray::new(
+            Point3::new(x, y, self.base),
+            Unit::new_unchecked(Vector3::z()),
+        );
+
+        let mut hits: Vec<_> = self.columns[self.column(x, y)]
+            .iter()
+            .flat_map(|(tag, patch)| {
+                patch
+                    .crossings(&ray)
+                    .into_iter()
+                    .map(move |(dist, inside)| (self.base + dist, inside, *tag))
+            })
+            .collect();
+        hits.sort_by(|a, b| a.0.partial_cmp(&b.0).expect("Invalid scan height."));
+
+        hits
+    }
+
+    /// Determine the surface deciding the region containing a given height, and whether the height lies inside it.
+    /// The nearest crossing above is used, falling back to the nearest crossing below.
+    /// If there are no crossings the region can not be resolved.
+    #[inline]
+    #[must_use]
+    fn classify(hits: &[(f64, bool, T)], z: f64) -> Option<(T, bool)> {
+        if let Some((_, inside, tag)) = hits.iter().find(|(h, _, _)| *h > z) {
+            return Some((*tag, *inside));
+        }
+
+        hits.last().map(|(_, inside, tag)| (*tag, !*inside))
+    }
+
+    /// Determine the surface deciding the region containing a given position, and whether the position lies inside it.
+    #[inline]
+    #[must_use]
+    pub fn observe(&self, p: &Point3<f64>) -> Option<(T, bool)> {
+        debug_assert!(self.bound.contains(p));
+
+        Self::classify(&self.hits(p.x, p.y), p.z)
+    }
+}
+
+impl<'a, T: Copy + Send + Sync> Scan<'a, T> {
+    /// Determine the deciding surface and side at the centre of each cell of a grid spanning the boundary.
+    /// A single scan ray is cast along each column.
+    #[inline]
+    #[must_use]
+    pub fn cells(&self, res_z: usize) -> Array3<Option<(T, bool)>> {
+        let mins = self.bound.mins();
+        let widths = self.bound.widths();
+        let cell_size = Vector3::new(
+            widths.x / self.res[0] as f64,
+            widths.y / self.res[1] as f64,
+            widths.z / res_z as f64,
+        );
+
+        let columns: Vec<Vec<_>> = (0..(self.res[0] * self.res[1]))
+            .into_par_iter()
+            .map(|n| {
+                let xi = n / self.res[1];
+                let yi = n % self.res[1];
+
+                let hits = self.hits(
+                    mins.x + (cell_size.x * (xi as f64 + 0.5)),
+                    mins.y + (cell_size.y * (yi as f64 + 0.5)),
+                );
+
+                (0..res_z)
+                    .map(|zi| Self::classify(&hits, mins.z + (cell_size.z * (zi as f64 + 0.5))))
+                    .collect()
+            })
+            .collect();
+
+        Array3::from_shape_fn([self.res[0], self.res[1], res_z], |(xi, yi, zi)| {
+            columns[xi * self.res[1] + yi][zi]
+        })
+    }
+}
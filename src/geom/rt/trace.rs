@@ -51,3 +51,18 @@ pub trait Trace {
         }
     }
 }
+
+/// Determine the nearest crossing ahead of a ray, its side, and the outward normal there.
+/// Crossings are given as signed distances along the ray's line, in ascending order, with outward normals.
+/// The ray is inside the surface if it leaves through the crossing.
+#[inline]
+#[must_use]
+pub fn nearest_crossing(
+    ray: &Ray,
+    crossings: &[(f64, Unit<Vector3<f64>>)],
+) -> Option<(f64, bool, Unit<Vector3<f64>>)> {
+    crossings
+        .iter()
+        .find(|(dist, _)| *dist > 0.0)
+        .map(|(dist, norm)| (*dist, ray.dir().dot(norm) > 0.0, *norm))
+}
@@ -0,0 +1,179 @@
+//! Triangle octree implementation.
+
+use crate::geom::{Aabb, Collide, Patch, Ray};
+use nalgebra::Point3;
+
+/// Triangle octree enumeration.
+/// Partitions tagged surface patches, mostly mesh triangles, to accelerate nearest intersection queries.
+pub enum TriTree<'a, T> {
+    /// Branching cell.
+    Branch {
+        /// Boundary.
+        boundary: Aabb,
+        /// Children.
+        children: [Box<TriTree<'a, T>>; 8],
+    },
+    /// Terminal populated cell.
+    Leaf {
+        /// Boundary.
+        boundary: Aabb,
+        /// Intersecting patches.
+        tris: Vec<(T, Patch<'a>)>,
+    },
+    /// Terminal empty cell.
+    Empty {
+        /// Boundary.
+        boundary: Aabb,
+    },
+}
+
+impl<'a, T: Copy> TriTree<'a, T> {
+    /// Construct a new instance.
+    /// Cells are subdivided until they hold no more than the target number of patches, or the maximum depth is reached.
+    #[inline]
+    #[must_use]
+    pub fn new(
+        boundary: Aabb,
+        potential_tris: &[(T, Patch<'a>)],
+        tar_tris: usize,
+        max_depth: u32,
+    ) -> Self {
+        Self::new_child(boundary, potential_tris, tar_tris, max_depth, 0)
+    }
+
+    /// Initialise a child cell.
+    #[inline]
+    #[must_use]
+    fn new_child(
+        boundary: Aabb,
+        potential_tris: &[(T, Patch<'a>)],
+        tar_tris: usize,
+        max_depth: u32,
+        depth: u32,
+    ) -> Self {
+        debug_assert!(depth <= max_depth);
+
+        let tris: Vec<_> = potential_tris
+            .iter()
+            .filter(|(_, tri)| tri.overlap(&boundary))
+            .copied()
+            .collect();
+
+        if tris.is_empty() {
+            return Self::Empty { boundary };
+        }
+
+        if (tris.len() <= tar_tris) || (depth >= max_depth) {
+            return Self::Leaf { boundary, tris };
+        }
+
+        let hws = boundary.half_widths();
+        let mins = boundary.mins();
+        let make_child = |x: f64, y: f64, z: f64| {
+            let min = Point3::new(mins.x + x, mins.y + y, mins.z + z);
+            Box::new(Self::new_child(
+                Aabb::new(min, min + hws),
+                &tris,
+                tar_tris,
+                max_depth,
+                depth + 1,
+            ))
+        };
+
+        let children = [
+            make_child(0.0, 0.0, 0.0),
+            make_child(hws.x, 0.0, 0.0),
+            make_child(0.0, hws.y, 0.0),
+            make_child(hws.x, hws.y, 0.0),
+            make_child(0.0, 0.0, hws.z),
+            make_child(hws.x, 0.0, hws.z),
+            make_child(0.0, hws.y, hws.z),
+            make_child(hws.x, hws.y, hws.z),
+        ];
+
+        Self::Branch { boundary, children }
+    }
+
+    /// Reference the cell's boundary.
+    #[inline]
+    #[must_use]
+    pub fn boundary(&self) -> &Aabb {
+        match self {
+            Self::Branch { boundary, .. }
+            | Self::Leaf { boundary, .. }
+            | Self::Empty { boundary } => boundary,
+        }
+    }
+
+    /// Determine the number of terminal populated cells.
+    #[inline]
+    #[must_use]
+    pub fn num_leaves(&self) -> usize {
+        match self {
+            Self::Branch { children, .. } => children.iter().map(|c| c.num_leaves()).sum(),
+            Self::Leaf { .. } => 1,
+            Self::Empty { .. } => 0,
+        }
+    }
+
+    /// Determine the maximum depth of the tree.
+    #[inline]
+    #[must_use]
+    pub fn depth(&self) -> u32 {
+        match self {
+            Self::Branch { children, .. } => {
+                1 + children.iter().map(|c| c.depth()).max().unwrap_or(0)
+            }
+            Self::Leaf { .. } | Self::Empty { .. } => 0,
+        }
+    }
+
+    /// Determine the nearest hit along a ray, using the given patch test.
+    /// Cells are skipped if they are entered beyond the current nearest hit.
+    #[inline]
+    #[must_use]
+    pub fn nearest<O, F>(&self, ray: &Ray, hit: &F) -> Option<(f64, O)>
+    where
+        F: Fn(T, &Patch<'a>) -> Option<(f64, O)>,
+    {
+        let mut nearest = None;
+        self.nearest_within(ray, hit, &mut nearest);
+        nearest
+    }
+
+    /// Update the nearest hit with any closer hit found within this cell.
+    #[inline]
+    fn nearest_within<O, F>(&self, ray: &Ray, hit: &F, nearest: &mut Option<(f64, O)>)
+    where
+        F: Fn(T, &Patch<'a>) -> Option<(f64, O)>,
+    {
+        match self.boundary().entry_dist(ray) {
+            None => return,
+            Some(entry) => {
+                if let Some((dist, _)) = nearest {
+                    if entry > *dist {
+                        return;
+                    }
+                }
+            }
+        }
+
+        match self {
+            Self::Branch { children, .. } => {
+                for child in children.iter() {
+                    child.nearest_within(ray, hit, nearest);
+                }
+            }
+            Self::Leaf { tris, .. } => {
+                for (tag, tri) in tris {
+                    if let Some((dist, out)) = hit(*tag, tri) {
+                        if nearest.as_ref().map_or(true, |(d, _)| dist < *d) {
+                            *nearest = Some((dist, out));
+                        }
+                    }
+                }
+            }
+            Self::Empty { .. } => {}
+        }
+    }
+}
@@ -11,7 +11,7 @@ use std::cmp::Ordering;
 /// Axis-aligned bounding box geometry.
 /// Used for spatial partitioning.
 #[json]
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct Aabb {
     /// Minimum bound.
     mins: Point3<f64>,
@@ -109,6 +109,20 @@ impl Aabb {
         dist_sq
     }
 
+    /// Determine the distance along a ray's direction at which it enters the aabb.
+    /// Zero if the ray starts within the aabb.
+    #[inline]
+    #[must_use]
+    pub fn entry_dist(&self, ray: &Ray) -> Option<f64> {
+        let (t_min, t_max) = self.intersections(ray);
+
+        if t_max <= 0.0 || t_min > t_max {
+            return None;
+        }
+
+        Some(t_min.max(0.0))
+    }
+
     /// Determine the intersection distances along a ray's direction.
     #[must_use]
     fn intersections(&self, ray: &Ray) -> (f64, f64) {
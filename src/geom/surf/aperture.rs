@@ -1,35 +1,49 @@
-//! Rectangle implementation.
+//! Aperture implementation.
 
 use crate::{
-    access,
+    access, clone,
     geom::{Emit, Ray},
 };
-use std::f64::consts::FRAC_PI_2;
+use nalgebra::{Unit, Vector3};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::{FRAC_PI_2, PI};
 
 /// Aperture geometry.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Aperture {
     /// Ray.
     ray: Ray,
-    /// Numerical aperture.
+    /// Numerical aperture, expressed as the acceptance half-angle. [rad]
     na: f64,
 }
 
 impl Aperture {
     access!(ray, Ray);
-    access!(na, f64);
+    clone!(na, f64);
 
     /// Construct a new instance.
+    #[inline]
+    #[must_use]
     pub fn new(ray: Ray, na: f64) -> Self {
         debug_assert!(na > 0.0);
         debug_assert!(na < FRAC_PI_2);
 
         Self { ray, na }
     }
+
+    /// Determine if light travelling in the given direction falls within the acceptance cone.
+    #[inline]
+    #[must_use]
+    pub fn accepts(&self, dir: &Unit<Vector3<f64>>) -> bool {
+        -dir.dot(self.ray.dir()) >= self.na.cos()
+    }
 }
 
 impl Emit for Aperture {
-    fn cast(&self, rng: &mut ThreadRng) -> Ray {
+    #[inline]
+    #[must_use]
+    fn cast<R: Rng>(&self, rng: &mut R) -> Ray {
         let pitch = rng.gen_range(self.na.cos(), 1.0).acos();
         let roll = rng.gen_range(0.0, 2.0 * PI);
 
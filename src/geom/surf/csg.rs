@@ -0,0 +1,190 @@
+//! Constructive solid geometry implementation.
+
+use crate::{
+    access, clone,
+    geom::{nearest_crossing, Aabb, Collide, Emit, Ray, Surface, Trace, Transform},
+};
+use nalgebra::{Affine3, Unit, Vector3};
+use rand::Rng;
+
+/// Boolean operation enumeration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CsgOp {
+    /// Points inside any of the parts.
+    Union,
+    /// Points inside all of the parts.
+    Intersection,
+    /// Points inside the first part but none of the others.
+    Difference,
+}
+
+impl CsgOp {
+    /// Determine if a point is inside the combination, given whether it is inside each of the parts.
+    #[inline]
+    #[must_use]
+    pub fn combine(self, insides: &[bool]) -> bool {
+        match self {
+            Self::Union => insides.iter().any(|inside| *inside),
+            Self::Intersection => insides.iter().all(|inside| *inside),
+            Self::Difference => {
+                insides.first().copied().unwrap_or(false) && !insides[1..].iter().any(|x| *x)
+            }
+        }
+    }
+}
+
+/// Constructive solid geometry structure.
+/// The parts are treated as closed solids, and are resolved along each traced ray.
+#[derive(Debug, Clone)]
+pub struct Csg {
+    /// Operation.
+    op: CsgOp,
+    /// Combined parts.
+    parts: Vec<Surface>,
+}
+
+impl Csg {
+    clone!(op, CsgOp);
+    access!(parts, Vec<Surface>);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(op: CsgOp, parts: Vec<Surface>) -> Self {
+        debug_assert!(!parts.is_empty());
+
+        Self { op, parts }
+    }
+
+    /// Determine the signed distances along a ray's line at which it crosses the combined surface, and the outward normals there.
+    /// Each part is assumed to start in the state implied by its first crossing,
+    /// so crossings behind the ray are only exact when no part is a mesh.
+    #[inline]
+    #[must_use]
+    pub fn crossings(&self, ray: &Ray) -> Vec<(f64, Unit<Vector3<f64>>)> {
+        let leaving = |norm: &Unit<Vector3<f64>>| ray.dir().dot(norm) > 0.0;
+
+        let part_crossings: Vec<_> = self.parts.iter().map(|part| part.crossings(ray)).collect();
+
+        let mut insides: Vec<_> = part_crossings
+            .iter()
+            .map(|crossings| crossings.first().map_or(false, |(_, norm)| leaving(norm)))
+            .collect();
+
+        let mut events: Vec<_> = part_crossings
+            .into_iter()
+            .enumerate()
+            .flat_map(|(index, crossings)| {
+                crossings
+                    .into_iter()
+                    .map(move |(dist, norm)| (dist, index, norm))
+            })
+            .collect();
+        events.sort_by(|a, b| a.0.partial_cmp(&b.0).expect("Invalid crossing distance."));
+
+        let mut inside = self.op.combine(&insides);
+        let mut crossings = Vec::new();
+        for (dist, index, norm) in events {
+            insides[index] = !leaving(&norm);
+
+            let now = self.op.combine(&insides);
+            if now != inside {
+                inside = now;
+                crossings.push((
+                    dist,
+                    if self.op == CsgOp::Difference && index > 0 {
+                        -norm
+                    } else {
+                        norm
+                    },
+                ));
+            }
+        }
+
+        crossings
+    }
+}
+
+impl Trace for Csg {
+    #[inline]
+    #[must_use]
+    fn hit(&self, ray: &Ray) -> bool {
+        self.dist(ray).is_some()
+    }
+
+    #[inline]
+    #[must_use]
+    fn dist(&self, ray: &Ray) -> Option<f64> {
+        self.dist_inside_norm(ray).map(|(dist, _, _)| dist)
+    }
+
+    #[inline]
+    #[must_use]
+    fn dist_norm(&self, ray: &Ray) -> Option<(f64, Unit<Vector3<f64>>)> {
+        self.dist_inside_norm(ray)
+            .map(|(dist, _, norm)| (dist, norm))
+    }
+
+    #[inline]
+    #[must_use]
+    fn dist_inside(&self, ray: &Ray) -> Option<(f64, bool)> {
+        self.dist_inside_norm(ray)
+            .map(|(dist, inside, _)| (dist, inside))
+    }
+
+    #[inline]
+    #[must_use]
+    fn dist_inside_norm(&self, ray: &Ray) -> Option<(f64, bool, Unit<Vector3<f64>>)> {
+        nearest_crossing(ray, &self.crossings(ray))
+    }
+}
+
+impl Collide for Csg {
+    #[inline]
+    #[must_use]
+    fn bounding_box(&self) -> Aabb {
+        let mut boxes = self.parts.iter().map(Collide::bounding_box);
+        let first = boxes
+            .next()
+            .expect("Unable to bound an empty constructive solid geometry.");
+
+        match self.op {
+            CsgOp::Union => boxes.fold(first, |acc, aabb| {
+                Aabb::new(acc.mins().inf(aabb.mins()), acc.maxs().sup(aabb.maxs()))
+            }),
+            CsgOp::Intersection => boxes.fold(first, |acc, aabb| {
+                let mins = acc.mins().sup(aabb.mins());
+                Aabb::new(mins, acc.maxs().inf(aabb.maxs()).sup(&mins))
+            }),
+            CsgOp::Difference => first,
+        }
+    }
+
+    /// The test is conservative, and may report overlaps with boxes lying just off the surface.
+    #[inline]
+    #[must_use]
+    fn overlap(&self, aabb: &Aabb) -> bool {
+        if !self.bounding_box().overlap(aabb) {
+            return false;
+        }
+
+        self.parts.iter().any(|part| part.overlap(aabb))
+    }
+}
+
+impl Transform for Csg {
+    #[inline]
+    fn transform(&mut self, trans: &Affine3<f64>) {
+        for part in &mut self.parts {
+            part.transform(trans);
+        }
+    }
+}
+
+impl Emit for Csg {
+    #[inline]
+    #[must_use]
+    fn cast<R: Rng>(&self, _rng: &mut R) -> Ray {
+        panic!("Unable to emit from a constructive solid geometry.");
+    }
+}
@@ -0,0 +1,248 @@
+//! Oriented cuboid implementation.
+
+use crate::{
+    access,
+    geom::{nearest_crossing, perp_scalings, Aabb, Collide, Emit, Ray, Trace, Transform},
+};
+use nalgebra::{Affine3, Matrix3, Point3, Rotation3, Unit, UnitQuaternion, Vector3};
+use rand::Rng;
+
+/// Closed box geometry, which may be rotated away from the coordinate axes.
+#[derive(Debug, Clone)]
+pub struct Cuboid {
+    /// Central point.
+    centre: Point3<f64>,
+    /// Half-widths along the local axes.
+    hws: Vector3<f64>,
+    /// Rotation from the local axes to the global axes.
+    rot: UnitQuaternion<f64>,
+}
+
+impl Cuboid {
+    access!(centre, Point3<f64>);
+    access!(hws, Vector3<f64>);
+    access!(rot, UnitQuaternion<f64>);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(centre: Point3<f64>, hws: Vector3<f64>, rot: UnitQuaternion<f64>) -> Self {
+        debug_assert!(hws.iter().all(|x| *x > 0.0));
+
+        Self { centre, hws, rot }
+    }
+
+    /// Construct a new axis-aligned instance spanning the given bounds.
+    #[inline]
+    #[must_use]
+    pub fn new_aligned(mins: Point3<f64>, maxs: Point3<f64>) -> Self {
+        Self::new(
+            nalgebra::center(&mins, &maxs),
+            (maxs - mins) * 0.5,
+            UnitQuaternion::identity(),
+        )
+    }
+
+    /// Calculate the surface area.
+    #[inline]
+    #[must_use]
+    pub fn area(&self) -> f64 {
+        8.0 * ((self.hws.x * self.hws.y) + (self.hws.y * self.hws.z) + (self.hws.z * self.hws.x))
+    }
+
+    /// Calculate the volume.
+    #[inline]
+    #[must_use]
+    pub fn vol(&self) -> f64 {
+        8.0 * self.hws.x * self.hws.y * self.hws.z
+    }
+
+    /// Determine if the given point is contained.
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, p: &Point3<f64>) -> bool {
+        let local = self.rot.inverse_transform_vector(&(p - self.centre));
+
+        local
+            .iter()
+            .zip(self.hws.iter())
+            .all(|(x, hw)| x.abs() <= *hw)
+    }
+
+    /// Determine the outward normal of the face on the given local axis and side.
+    #[inline]
+    #[must_use]
+    fn face_norm(&self, axis: usize, positive: bool) -> Unit<Vector3<f64>> {
+        let mut norm = Vector3::zeros();
+        norm[axis] = if positive { 1.0 } else { -1.0 };
+
+        Unit::new_normalize(self.rot.transform_vector(&norm))
+    }
+
+    /// Determine the signed distances along a ray's line at which it crosses the surface, and the outward normals there.
+    #[inline]
+    #[must_use]
+    pub fn crossings(&self, ray: &Ray) -> Vec<(f64, Unit<Vector3<f64>>)> {
+        let pos = self
+            .rot
+            .inverse_transform_vector(&(ray.pos() - self.centre));
+        let dir = self.rot.inverse_transform_vector(ray.dir());
+
+        let mut entry = (std::f64::NEG_INFINITY, 0, false);
+        let mut exit = (std::f64::INFINITY, 0, false);
+        for axis in 0..3 {
+            if dir[axis] == 0.0 {
+                if pos[axis].abs() > self.hws[axis] {
+                    return Vec::new();
+                }
+                continue;
+            }
+
+            let near = (-self.hws[axis].copysign(dir[axis]) - pos[axis]) / dir[axis];
+            let far = (self.hws[axis].copysign(dir[axis]) - pos[axis]) / dir[axis];
+            if near > entry.0 {
+                entry = (near, axis, dir[axis] < 0.0);
+            }
+            if far < exit.0 {
+                exit = (far, axis, dir[axis] > 0.0);
+            }
+        }
+
+        if entry.0 >= exit.0 {
+            return Vec::new();
+        }
+
+        vec![
+            (entry.0, self.face_norm(entry.1, entry.2)),
+            (exit.0, self.face_norm(exit.1, exit.2)),
+        ]
+    }
+}
+
+impl Trace for Cuboid {
+    #[inline]
+    #[must_use]
+    fn hit(&self, ray: &Ray) -> bool {
+        self.dist(ray).is_some()
+    }
+
+    #[inline]
+    #[must_use]
+    fn dist(&self, ray: &Ray) -> Option<f64> {
+        self.dist_inside_norm(ray).map(|(dist, _, _)| dist)
+    }
+
+    #[inline]
+    #[must_use]
+    fn dist_norm(&self, ray: &Ray) -> Option<(f64, Unit<Vector3<f64>>)> {
+        self.dist_inside_norm(ray)
+            .map(|(dist, _, norm)| (dist, norm))
+    }
+
+    #[inline]
+    #[must_use]
+    fn dist_inside(&self, ray: &Ray) -> Option<(f64, bool)> {
+        self.dist_inside_norm(ray)
+            .map(|(dist, inside, _)| (dist, inside))
+    }
+
+    #[inline]
+    #[must_use]
+    fn dist_inside_norm(&self, ray: &Ray) -> Option<(f64, bool, Unit<Vector3<f64>>)> {
+        nearest_crossing(ray, &self.crossings(ray))
+    }
+}
+
+impl Collide for Cuboid {
+    #[inline]
+    #[must_use]
+    fn bounding_box(&self) -> Aabb {
+        let rot = self.rot.to_rotation_matrix();
+        let ext = rot.matrix().abs() * self.hws;
+
+        Aabb::new(self.centre - ext, self.centre + ext)
+    }
+
+    /// Separating axes are tested along the face normals of both boxes.
+    /// Edge cross-product axes are not tested, so the test may report overlaps with boxes lying just off the surface.
+    #[inline]
+    #[must_use]
+    fn overlap(&self, aabb: &Aabb) -> bool {
+        if !self.bounding_box().overlap(aabb) {
+            return false;
+        }
+
+        let rot = self.rot.to_rotation_matrix();
+        let rel = self
+            .rot
+            .inverse_transform_vector(&(aabb.centre() - self.centre));
+        let ext = rot.matrix().transpose().abs() * aabb.half_widths();
+
+        (0..3).all(|axis| rel[axis].abs() <= self.hws[axis] + ext[axis])
+    }
+}
+
+impl Transform for Cuboid {
+    #[inline]
+    fn transform(&mut self, trans: &Affine3<f64>) {
+        let axes: Vec<_> = (0..3).map(|axis| *self.face_norm(axis, true)).collect();
+        let scales = perp_scalings(trans, &axes)
+            .expect("Unable to apply a transform to a cuboid which would shear it.");
+
+        let mut cols: Vec<_> = axes
+            .iter()
+            .map(|axis| trans.transform_vector(axis).normalize())
+            .collect();
+        let mut hws = Vector3::new(
+            self.hws.x * scales[0],
+            self.hws.y * scales[1],
+            self.hws.z * scales[2],
+        );
+        if cols[0].cross(&cols[1]).dot(&cols[2]) < 0.0 {
+            cols.swap(0, 1);
+            hws.swap_rows(0, 1);
+        }
+
+        self.centre = trans.transform_point(&self.centre);
+        self.hws = hws;
+        self.rot = UnitQuaternion::from_rotation_matrix(&Rotation3::from_matrix_unchecked(
+            Matrix3::from_columns(&cols),
+        ));
+    }
+}
+
+impl Emit for Cuboid {
+    #[inline]
+    #[must_use]
+    fn cast<R: Rng>(&self, rng: &mut R) -> Ray {
+        let areas = [
+            self.hws.y * self.hws.z,
+            self.hws.z * self.hws.x,
+            self.hws.x * self.hws.y,
+        ];
+
+        let mut r = rng.gen_range(0.0, areas.iter().sum::<f64>());
+        let mut axis = 0;
+        while axis < 2 && r >= areas[axis] {
+            r -= areas[axis];
+            axis += 1;
+        }
+        let positive = rng.gen::<bool>();
+
+        let mut local = Vector3::new(
+            rng.gen_range(-self.hws.x, self.hws.x),
+            rng.gen_range(-self.hws.y, self.hws.y),
+            rng.gen_range(-self.hws.z, self.hws.z),
+        );
+        local[axis] = if positive {
+            self.hws[axis]
+        } else {
+            -self.hws[axis]
+        };
+
+        Ray::new(
+            self.centre + self.rot.transform_vector(&local),
+            self.face_norm(axis, positive),
+        )
+    }
+}
@@ -0,0 +1,229 @@
+//! Capped cylinder implementation.
+
+use crate::{
+    access, clone,
+    geom::{
+        nearest_crossing, perp_basis, perp_scalings, Aabb, Collide, Disc, Emit, Ray, Trace,
+        Transform, CONFORMAL_TOL,
+    },
+};
+use nalgebra::{Affine3, Point3, Unit, Vector3};
+use rand::Rng;
+use std::f64::consts::PI;
+
+/// Closed cylinder geometry, capped at both ends.
+#[derive(Debug, Clone)]
+pub struct Cylinder {
+    /// Centre of the base cap.
+    base: Point3<f64>,
+    /// Direction from the base cap to the top cap.
+    axis: Unit<Vector3<f64>>,
+    /// Length along the axis.
+    len: f64,
+    /// Radius.
+    rad: f64,
+}
+
+impl Cylinder {
+    access!(base, Point3<f64>);
+    access!(axis, Unit<Vector3<f64>>);
+    clone!(len, f64);
+    clone!(rad, f64);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(base: Point3<f64>, axis: Unit<Vector3<f64>>, len: f64, rad: f64) -> Self {
+        debug_assert!(len > 0.0);
+        debug_assert!(rad > 0.0);
+
+        Self {
+            base,
+            axis,
+            len,
+            rad,
+        }
+    }
+
+    /// Calculate the centre of the top cap.
+    #[inline]
+    #[must_use]
+    pub fn top(&self) -> Point3<f64> {
+        self.base + (self.axis.as_ref() * self.len)
+    }
+
+    /// Construct the base and top caps.
+    #[inline]
+    #[must_use]
+    fn caps(&self) -> [Disc; 2] {
+        [
+            Disc::new(self.base, -self.axis, self.rad),
+            Disc::new(self.top(), self.axis, self.rad),
+        ]
+    }
+
+    /// Calculate the surface area.
+    #[inline]
+    #[must_use]
+    pub fn area(&self) -> f64 {
+        2.0 * PI * self.rad * (self.len + self.rad)
+    }
+
+    /// Calculate the volume.
+    #[inline]
+    #[must_use]
+    pub fn vol(&self) -> f64 {
+        PI * self.rad.powi(2) * self.len
+    }
+
+    /// Determine if the given point is contained.
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, p: &Point3<f64>) -> bool {
+        let rel = p - self.base;
+        let h = rel.dot(&self.axis);
+
+        h >= 0.0
+            && h <= self.len
+            && (rel - (self.axis.as_ref() * h)).norm_squared() <= self.rad.powi(2)
+    }
+
+    /// Determine the signed distances along a ray's line at which it crosses the surface, and the outward normals there.
+    #[inline]
+    #[must_use]
+    pub fn crossings(&self, ray: &Ray) -> Vec<(f64, Unit<Vector3<f64>>)> {
+        let rel = ray.pos() - self.base;
+        let d_perp = ray.dir().as_ref() - (self.axis.as_ref() * ray.dir().dot(&self.axis));
+        let m_perp = rel - (self.axis.as_ref() * rel.dot(&self.axis));
+
+        let mut crossings = Vec::with_capacity(2);
+
+        let a = d_perp.norm_squared();
+        let b = m_perp.dot(&d_perp);
+        let c = m_perp.norm_squared() - self.rad.powi(2);
+        let disc = b.powi(2) - (a * c);
+        if a > 0.0 && disc > 0.0 {
+            let disc_sqrt = disc.sqrt();
+            for dist in &[(-b - disc_sqrt) / a, (-b + disc_sqrt) / a] {
+                let h = rel.dot(&self.axis) + (dist * ray.dir().dot(&self.axis));
+                if h >= 0.0 && h <= self.len {
+                    crossings.push((*dist, Unit::new_normalize(m_perp + (d_perp * *dist))));
+                }
+            }
+        }
+
+        for cap in &self.caps() {
+            crossings.append(&mut cap.crossings(ray));
+        }
+
+        crossings.sort_by(|a, b| a.0.partial_cmp(&b.0).expect("Invalid crossing distance."));
+        crossings
+    }
+}
+
+impl Trace for Cylinder {
+    #[inline]
+    #[must_use]
+    fn hit(&self, ray: &Ray) -> bool {
+        self.dist(ray).is_some()
+    }
+
+    #[inline]
+    #[must_use]
+    fn dist(&self, ray: &Ray) -> Option<f64> {
+        self.dist_inside_norm(ray).map(|(dist, _, _)| dist)
+    }
+
+    #[inline]
+    #[must_use]
+    fn dist_norm(&self, ray: &Ray) -> Option<(f64, Unit<Vector3<f64>>)> {
+        self.dist_inside_norm(ray)
+            .map(|(dist, _, norm)| (dist, norm))
+    }
+
+    #[inline]
+    #[must_use]
+    fn dist_inside(&self, ray: &Ray) -> Option<(f64, bool)> {
+        self.dist_inside_norm(ray)
+            .map(|(dist, inside, _)| (dist, inside))
+    }
+
+    #[inline]
+    #[must_use]
+    fn dist_inside_norm(&self, ray: &Ray) -> Option<(f64, bool, Unit<Vector3<f64>>)> {
+        nearest_crossing(ray, &self.crossings(ray))
+    }
+}
+
+impl Collide for Cylinder {
+    #[inline]
+    #[must_use]
+    fn bounding_box(&self) -> Aabb {
+        let [base, top] = self.caps();
+        let (base, top) = (base.bounding_box(), top.bounding_box());
+
+        Aabb::new(base.mins().inf(top.mins()), base.maxs().sup(top.maxs()))
+    }
+
+    /// The test is conservative, and may report overlaps with boxes lying just off the surface.
+    #[inline]
+    #[must_use]
+    fn overlap(&self, aabb: &Aabb) -> bool {
+        if !self.bounding_box().overlap(aabb) {
+            return false;
+        }
+
+        let rel = aabb.centre() - self.base;
+        let h = rel.dot(&self.axis).max(0.0).min(self.len);
+        let axis_dist = (rel - (self.axis.as_ref() * h)).norm();
+
+        axis_dist <= self.rad + aabb.half_widths().norm()
+    }
+}
+
+impl Transform for Cylinder {
+    #[inline]
+    fn transform(&mut self, trans: &Affine3<f64>) {
+        let (u, v) = perp_basis(&self.axis);
+        let scales = perp_scalings(
+            trans,
+            &[self.axis.into_inner(), u.into_inner(), v.into_inner()],
+        )
+        .filter(|scales| (scales[1] - scales[2]).abs() <= CONFORMAL_TOL * scales[1])
+        .expect("Unable to apply a transform to a cylinder which would make it elliptical.");
+
+        self.base = trans.transform_point(&self.base);
+        self.axis = Unit::new_normalize(trans.transform_vector(&self.axis));
+        self.len *= scales[0];
+        self.rad *= scales[1];
+    }
+}
+
+impl Emit for Cylinder {
+    #[inline]
+    #[must_use]
+    fn cast<R: Rng>(&self, rng: &mut R) -> Ray {
+        let side_area = 2.0 * PI * self.rad * self.len;
+        let cap_area = PI * self.rad.powi(2);
+
+        let r = rng.gen_range(0.0, side_area + (2.0 * cap_area));
+        if r >= side_area {
+            let [base, top] = self.caps();
+            return if r < side_area + cap_area {
+                base.cast(rng)
+            } else {
+                top.cast(rng)
+            };
+        }
+
+        let (u, v) = perp_basis(&self.axis);
+        let theta = rng.gen_range(0.0, 2.0 * PI);
+        let h = rng.gen_range(0.0, self.len);
+        let norm = Unit::new_normalize((u.as_ref() * theta.cos()) + (v.as_ref() * theta.sin()));
+
+        Ray::new(
+            self.base + (self.axis.as_ref() * h) + (norm.as_ref() * self.rad),
+            norm,
+        )
+    }
+}
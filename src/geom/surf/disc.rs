@@ -0,0 +1,171 @@
+//! Disc implementation.
+
+use crate::{
+    access, clone,
+    geom::{
+        nearest_crossing, perp_scalings, transform_norm, Aabb, Collide, Emit, Ray, Trace,
+        Transform, CONFORMAL_TOL,
+    },
+};
+use nalgebra::{Affine3, Point3, Unit, Vector3};
+use rand::Rng;
+use std::f64::consts::PI;
+
+/// Flat circular disc geometry.
+/// Like a triangle, rays travelling along the normal are considered to be leaving the inside.
+#[derive(Debug, Clone)]
+pub struct Disc {
+    /// Central point.
+    centre: Point3<f64>,
+    /// Normal.
+    norm: Unit<Vector3<f64>>,
+    /// Radius.
+    rad: f64,
+}
+
+impl Disc {
+    access!(centre, Point3<f64>);
+    access!(norm, Unit<Vector3<f64>>);
+    clone!(rad, f64);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(centre: Point3<f64>, norm: Unit<Vector3<f64>>, rad: f64) -> Self {
+        debug_assert!(rad > 0.0);
+
+        Self { centre, norm, rad }
+    }
+
+    /// Calculate the surface area.
+    #[inline]
+    #[must_use]
+    pub fn area(&self) -> f64 {
+        PI * self.rad.powi(2)
+    }
+
+    /// Determine the signed distances along a ray's line at which it crosses the surface, and the normals there.
+    #[inline]
+    #[must_use]
+    pub fn crossings(&self, ray: &Ray) -> Vec<(f64, Unit<Vector3<f64>>)> {
+        let d = ray.dir().dot(&self.norm);
+        if d == 0.0 {
+            return Vec::new();
+        }
+
+        let dist = (self.centre - ray.pos()).dot(&self.norm) / d;
+        let p = ray.pos() + (ray.dir().as_ref() * dist);
+        if nalgebra::distance_squared(&p, &self.centre) > self.rad.powi(2) {
+            return Vec::new();
+        }
+
+        vec![(dist, self.norm)]
+    }
+
+    /// Sample a uniformly distributed point on the disc.
+    #[inline]
+    #[must_use]
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> Point3<f64> {
+        let (u, v) = perp_basis(&self.norm);
+        let r = self.rad * rng.gen::<f64>().sqrt();
+        let theta = rng.gen_range(0.0, 2.0 * PI);
+
+        self.centre + (u.as_ref() * (r * theta.cos())) + (v.as_ref() * (r * theta.sin()))
+    }
+}
+
+/// Construct a pair of unit vectors perpendicular to the given direction and to each other.
+#[inline]
+#[must_use]
+pub fn perp_basis(dir: &Unit<Vector3<f64>>) -> (Unit<Vector3<f64>>, Unit<Vector3<f64>>) {
+    let other = if dir.x.abs() < 0.9 {
+        Vector3::x()
+    } else {
+        Vector3::y()
+    };
+    let u = Unit::new_normalize(dir.cross(&other));
+    let v = Unit::new_normalize(dir.cross(&u));
+
+    (u, v)
+}
+
+impl Trace for Disc {
+    #[inline]
+    #[must_use]
+    fn hit(&self, ray: &Ray) -> bool {
+        self.dist(ray).is_some()
+    }
+
+    #[inline]
+    #[must_use]
+    fn dist(&self, ray: &Ray) -> Option<f64> {
+        self.dist_inside_norm(ray).map(|(dist, _, _)| dist)
+    }
+
+    #[inline]
+    #[must_use]
+    fn dist_norm(&self, ray: &Ray) -> Option<(f64, Unit<Vector3<f64>>)> {
+        self.dist_inside_norm(ray)
+            .map(|(dist, _, norm)| (dist, norm))
+    }
+
+    #[inline]
+    #[must_use]
+    fn dist_inside(&self, ray: &Ray) -> Option<(f64, bool)> {
+        self.dist_inside_norm(ray)
+            .map(|(dist, inside, _)| (dist, inside))
+    }
+
+    #[inline]
+    #[must_use]
+    fn dist_inside_norm(&self, ray: &Ray) -> Option<(f64, bool, Unit<Vector3<f64>>)> {
+        nearest_crossing(ray, &self.crossings(ray))
+    }
+}
+
+impl Collide for Disc {
+    #[inline]
+    #[must_use]
+    fn bounding_box(&self) -> Aabb {
+        let ext = Vector3::new(
+            self.rad * (1.0 - self.norm.x.powi(2)).max(0.0).sqrt(),
+            self.rad * (1.0 - self.norm.y.powi(2)).max(0.0).sqrt(),
+            self.rad * (1.0 - self.norm.z.powi(2)).max(0.0).sqrt(),
+        );
+
+        Aabb::new(self.centre - ext, self.centre + ext)
+    }
+
+    #[inline]
+    #[must_use]
+    fn overlap(&self, aabb: &Aabb) -> bool {
+        let hws = aabb.half_widths();
+        let reach =
+            (self.norm.x * hws.x).abs() + (self.norm.y * hws.y).abs() + (self.norm.z * hws.z).abs();
+
+        (aabb.centre() - self.centre).dot(&self.norm).abs() <= reach
+            && self.bounding_box().overlap(aabb)
+    }
+}
+
+impl Transform for Disc {
+    #[inline]
+    fn transform(&mut self, trans: &Affine3<f64>) {
+        let (u, v) = perp_basis(&self.norm);
+        let scales = perp_scalings(trans, &[u.into_inner(), v.into_inner()])
+            .filter(|scales| (scales[0] - scales[1]).abs() <= CONFORMAL_TOL * scales[0])
+            .expect("Unable to apply a transform to a disc which would make it elliptical.");
+
+        self.centre = trans.transform_point(&self.centre);
+        self.norm = transform_norm(trans, &self.norm);
+        self.rad *= scales[0];
+    }
+}
+
+impl Emit for Disc {
+    #[inline]
+    #[must_use]
+    fn cast<R: Rng>(&self, rng: &mut R) -> Ray {
+        Ray::new(self.sample(rng), self.norm)
+    }
+}
@@ -2,12 +2,13 @@
 
 use crate::{
     access,
+    err::Error,
     file::Load,
     geom::{Aabb, Collide, Emit, Ray, SmoothTriangle, Trace, Transform},
     list::{Cartesian::X, Greek::Alpha},
 };
-use nalgebra::{Similarity3, Unit, Vector3};
-use rand::{rngs::ThreadRng, Rng};
+use nalgebra::{Affine3, Unit, Vector3};
+use rand::Rng;
 use std::path::Path;
 
 /// Mesh geometry.
@@ -163,7 +164,7 @@ impl Collide for Mesh {
 
 impl Transform for Mesh {
     #[inline]
-    fn transform(&mut self, trans: &Similarity3<f64>) {
+    fn transform(&mut self, trans: &Affine3<f64>) {
         for tri in &mut self.tris {
             tri.transform(trans);
         }
@@ -175,7 +176,7 @@ impl Transform for Mesh {
 impl Emit for Mesh {
     #[inline]
     #[must_use]
-    fn cast(&self, rng: &mut ThreadRng) -> Ray {
+    fn cast<R: Rng>(&self, rng: &mut R) -> Ray {
         let areas: ndarray::Array1<f64> = self.tris.iter().map(|tri| tri.tri().area()).collect();
         let total_area = areas.sum();
 
@@ -194,8 +195,7 @@ impl Emit for Mesh {
 
 impl Load for Mesh {
     #[inline]
-    #[must_use]
-    fn load(path: &Path) -> Self {
-        Self::new(SmoothTriangle::load_list(path))
+    fn load(path: &Path) -> Result<Self, Error> {
+        Ok(Self::new(SmoothTriangle::load_list(path)?))
     }
 }
@@ -1,16 +1,24 @@
 //! Surfaces sub-module.
 
 pub mod aabb;
+pub mod aperture;
 pub mod collide;
+pub mod csg;
+pub mod cuboid;
+pub mod cylinder;
+pub mod disc;
 pub mod mesh;
 pub mod parallelogram;
+pub mod plane;
 pub mod rectangle;
 pub mod smooth_triangle;
 pub mod sphere;
+pub mod surface;
 pub mod transform;
 pub mod triangle;
 
 pub use self::{
-    aabb::*, collide::*, mesh::*, parallelogram::*, rectangle::*, smooth_triangle::*, sphere::*,
+    aabb::*, aperture::*, collide::*, csg::*, cuboid::*, cylinder::*, disc::*, mesh::*,
+    parallelogram::*, plane::*, rectangle::*, smooth_triangle::*, sphere::*, surface::*,
     transform::*, triangle::*,
 };
@@ -6,7 +6,7 @@ use crate::{
     list::Greek::{Alpha, Beta, Gamma},
 };
 use nalgebra::{Point3, Unit, Vector3};
-use rand::{rngs::ThreadRng, Rng};
+use rand::Rng;
 
 /// Parallelogram geometry.
 /// Used to form `Rectangles`.
@@ -190,7 +190,7 @@ impl Trace for Parallelogram {
 impl Emit for Parallelogram {
     #[inline]
     #[must_use]
-    fn cast(&self, rng: &mut ThreadRng) -> Ray {
+    fn cast<R: Rng>(&self, rng: &mut R) -> Ray {
         let (edge_a_b, edge_a_c) = self.edges();
 
         let pos = self.verts.get(Alpha as usize).expect("Missing vertex.")
@@ -0,0 +1,114 @@
+//! Infinite plane implementation.
+
+use crate::{
+    access,
+    geom::{nearest_crossing, transform_norm, Aabb, Collide, Emit, Ray, Trace, Transform},
+};
+use nalgebra::{Affine3, Point3, Unit, Vector3};
+use rand::Rng;
+use std::f64::INFINITY;
+
+/// Infinite plane geometry.
+/// The normal points to the outside of the half-space it bounds.
+#[derive(Debug, Clone)]
+pub struct Plane {
+    /// Point on the plane.
+    pos: Point3<f64>,
+    /// Outward normal.
+    norm: Unit<Vector3<f64>>,
+}
+
+impl Plane {
+    access!(pos, Point3<f64>);
+    access!(norm, Unit<Vector3<f64>>);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub const fn new(pos: Point3<f64>, norm: Unit<Vector3<f64>>) -> Self {
+        Self { pos, norm }
+    }
+
+    /// Determine the signed distances along a ray's line at which it crosses the surface, and the outward normals there.
+    #[inline]
+    #[must_use]
+    pub fn crossings(&self, ray: &Ray) -> Vec<(f64, Unit<Vector3<f64>>)> {
+        let d = ray.dir().dot(&self.norm);
+        if d == 0.0 {
+            return Vec::new();
+        }
+
+        vec![((self.pos - ray.pos()).dot(&self.norm) / d, self.norm)]
+    }
+}
+
+impl Trace for Plane {
+    #[inline]
+    #[must_use]
+    fn hit(&self, ray: &Ray) -> bool {
+        self.dist(ray).is_some()
+    }
+
+    #[inline]
+    #[must_use]
+    fn dist(&self, ray: &Ray) -> Option<f64> {
+        self.dist_inside_norm(ray).map(|(dist, _, _)| dist)
+    }
+
+    #[inline]
+    #[must_use]
+    fn dist_norm(&self, ray: &Ray) -> Option<(f64, Unit<Vector3<f64>>)> {
+        self.dist_inside_norm(ray)
+            .map(|(dist, _, norm)| (dist, norm))
+    }
+
+    #[inline]
+    #[must_use]
+    fn dist_inside(&self, ray: &Ray) -> Option<(f64, bool)> {
+        self.dist_inside_norm(ray)
+            .map(|(dist, inside, _)| (dist, inside))
+    }
+
+    #[inline]
+    #[must_use]
+    fn dist_inside_norm(&self, ray: &Ray) -> Option<(f64, bool, Unit<Vector3<f64>>)> {
+        nearest_crossing(ray, &self.crossings(ray))
+    }
+}
+
+impl Collide for Plane {
+    #[inline]
+    #[must_use]
+    fn bounding_box(&self) -> Aabb {
+        Aabb::new(
+            Point3::new(-INFINITY, -INFINITY, -INFINITY),
+            Point3::new(INFINITY, INFINITY, INFINITY),
+        )
+    }
+
+    #[inline]
+    #[must_use]
+    fn overlap(&self, aabb: &Aabb) -> bool {
+        let hws = aabb.half_widths();
+        let reach =
+            (self.norm.x * hws.x).abs() + (self.norm.y * hws.y).abs() + (self.norm.z * hws.z).abs();
+
+        (aabb.centre() - self.pos).dot(&self.norm).abs() <= reach
+    }
+}
+
+impl Transform for Plane {
+    #[inline]
+    fn transform(&mut self, trans: &Affine3<f64>) {
+        self.pos = trans.transform_point(&self.pos);
+        self.norm = transform_norm(trans, &self.norm);
+    }
+}
+
+impl Emit for Plane {
+    #[inline]
+    #[must_use]
+    fn cast<R: Rng>(&self, _rng: &mut R) -> Ray {
+        panic!("Unable to emit from an infinite plane.");
+    }
+}
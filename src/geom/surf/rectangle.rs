@@ -6,7 +6,7 @@ use crate::{
     list::Cartesian::X,
 };
 use nalgebra::{Point3, Unit, Vector3};
-use rand::rngs::ThreadRng;
+use rand::Rng;
 use std::f64::consts::FRAC_PI_2;
 
 /// Maximum absolute deviation [rad].
@@ -100,7 +100,7 @@ impl Trace for Rectangle {
 impl Emit for Rectangle {
     #[inline]
     #[must_use]
-    fn cast(&self, rng: &mut ThreadRng) -> Ray {
+    fn cast<R: Rng>(&self, rng: &mut R) -> Ray {
         self.para.cast(rng)
     }
 }
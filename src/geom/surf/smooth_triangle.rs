@@ -2,17 +2,16 @@
 
 use crate::{
     access,
-    geom::{Aabb, Collide, Emit, Ray, Trace, Transform, Triangle},
+    err::Error,
+    file::Load,
+    geom::{
+        linear_part, transform_norm, Aabb, Collide, Emit, PolyMesh, Ray, Trace, Transform, Triangle,
+    },
     list::Greek::{Alpha, Beta, Gamma},
 };
-use nalgebra::{Point3, Similarity3, Unit, Vector3};
-use rand::{rngs::ThreadRng, Rng};
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-    path::Path,
-    result::Result,
-};
+use nalgebra::{Affine3, Point3, Unit, Vector3};
+use rand::Rng;
+use std::path::Path;
 
 /// Triangle geometry with Phong normal interpolation.
 /// Used to form meshes.
@@ -44,142 +43,12 @@ impl SmoothTriangle {
         Self::new(Triangle::new(verts), norms)
     }
 
-    /// Load a list of triangle from a wavefront file.
+    /// Load a list of triangles from a mesh file.
+    /// The format is selected by the file extension.
+    /// Fails if the mesh file can not be loaded.
     #[inline]
-    #[must_use]
-    #[allow(clippy::too_many_lines)]
-    pub fn load_list(path: &Path) -> Vec<Self> {
-        let vertex_lines: Vec<_> = BufReader::new(
-            File::open(path).unwrap_or_else(|_| panic!("Unable to open file: {}", path.display())),
-        )
-        .lines()
-        .map(Result::unwrap)
-        .filter(|line| line.starts_with("v "))
-        .collect();
-
-        let mut verts = Vec::with_capacity(vertex_lines.len());
-        for line in vertex_lines {
-            let mut words = line.split_whitespace();
-            words.next();
-
-            let px = words
-                .next()
-                .expect("Could not get the next word.")
-                .parse::<f64>()
-                .expect("Could not parse px value.");
-            let py = words
-                .next()
-                .expect("Could not get the next word.")
-                .parse::<f64>()
-                .expect("Could not parse py value.");
-            let pz = words
-                .next()
-                .expect("Could not get the next word.")
-                .parse::<f64>()
-                .expect("Could not parse pz value.");
-
-            verts.push(Point3::new(px, py, pz));
-        }
-
-        let normal_lines: Vec<_> = BufReader::new(File::open(path).expect("Unable to open file!"))
-            .lines()
-            .map(Result::unwrap)
-            .filter(|line| line.starts_with("vn "))
-            .collect();
-
-        let mut norms = Vec::with_capacity(normal_lines.len());
-        for line in normal_lines {
-            let mut words = line.split_whitespace();
-            words.next();
-
-            let nx = words
-                .next()
-                .expect("Missing normal value entry.")
-                .parse::<f64>()
-                .expect("Unable to parse string to usize.");
-            let ny = words
-                .next()
-                .expect("Missing normal value entry.")
-                .parse::<f64>()
-                .expect("Unable to parse string to usize.");
-            let nz = words
-                .next()
-                .expect("Missing normal value entry.")
-                .parse::<f64>()
-                .expect("Unable to parse string to usize.");
-
-            norms.push(Unit::new_normalize(Vector3::new(nx, ny, nz)));
-        }
-
-        let face_lines: Vec<_> = BufReader::new(File::open(path).expect("Unable to open file!"))
-            .lines()
-            .map(Result::unwrap)
-            .filter(|line| line.starts_with("f "))
-            .collect();
-
-        let mut faces = Vec::with_capacity(face_lines.len());
-        for line in face_lines {
-            let line = line.replace("//", " ");
-            let mut words = line.split_whitespace();
-            words.next();
-
-            let fx = words
-                .next()
-                .expect("Missing face index entry.")
-                .parse::<usize>()
-                .expect("Unable to parse string to usize.")
-                - 1;
-            let nx = words
-                .next()
-                .expect("Missing normal index entry.")
-                .parse::<usize>()
-                .expect("Unable to parse string to usize.")
-                - 1;
-            let fy = words
-                .next()
-                .expect("Missing face index entry.")
-                .parse::<usize>()
-                .expect("Unable to parse string to usize.")
-                - 1;
-            let ny = words
-                .next()
-                .expect("Missing normal index entry.")
-                .parse::<usize>()
-                .expect("Unable to parse string to usize.")
-                - 1;
-            let fz = words
-                .next()
-                .expect("Missing face index entry.")
-                .parse::<usize>()
-                .expect("Unable to parse string to usize.")
-                - 1;
-            let nz = words
-                .next()
-                .expect("Missing normal index entry.")
-                .parse::<usize>()
-                .expect("Unable to parse string to usize.")
-                - 1;
-
-            faces.push(((fx, fy, fz), (nx, ny, nz)));
-        }
-
-        let mut tris = Vec::with_capacity(faces.len());
-        for face in faces {
-            tris.push(Self::new(
-                Triangle::new([
-                    *verts.get((face.0).0).expect("Missing vertex."),
-                    *verts.get((face.0).1).expect("Missing vertex."),
-                    *verts.get((face.0).2).expect("Missing vertex."),
-                ]),
-                [
-                    *norms.get((face.1).0).expect("Missing normal."),
-                    *norms.get((face.1).1).expect("Missing normal."),
-                    *norms.get((face.1).2).expect("Missing normal."),
-                ],
-            ));
-        }
-
-        tris
+    pub fn load_list(path: &Path) -> Result<Vec<Self>, Error> {
+        PolyMesh::load(path)?.tris(None)
     }
 }
 
@@ -270,11 +139,14 @@ impl Collide for SmoothTriangle {
 
 impl Transform for SmoothTriangle {
     #[inline]
-    fn transform(&mut self, trans: &Similarity3<f64>) {
+    fn transform(&mut self, trans: &Affine3<f64>) {
         self.tri.transform(trans);
 
         for n in &mut self.norms {
-            *n = Unit::new_normalize(trans.transform_vector(n.as_ref()));
+            *n = transform_norm(trans, n);
+        }
+        if linear_part(trans).determinant() < 0.0 {
+            self.norms.swap(Beta as usize, Gamma as usize);
         }
     }
 }
@@ -282,7 +154,7 @@ impl Transform for SmoothTriangle {
 impl Emit for SmoothTriangle {
     #[inline]
     #[must_use]
-    fn cast(&self, rng: &mut ThreadRng) -> Ray {
+    fn cast<R: Rng>(&self, rng: &mut R) -> Ray {
         let mut u = rng.gen::<f64>();
         let mut v = rng.gen::<f64>();
 
@@ -2,10 +2,10 @@
 
 use crate::{
     access, clone,
-    geom::{Aabb, Collide, Emit, Ray, Trace},
+    geom::{uniform_scaling, Aabb, Collide, Emit, Ray, Trace, Transform},
 };
-use nalgebra::{Point3, Unit, Vector3};
-use rand::rngs::ThreadRng;
+use nalgebra::{Affine3, Point3, Unit, Vector3};
+use rand::Rng;
 use std::f64::consts::PI;
 
 /// Sphere geometry.
@@ -70,6 +70,26 @@ impl Sphere {
         let disc_sqrt = disc.sqrt();
         Some((-b - disc_sqrt, -b + disc_sqrt))
     }
+
+    /// Determine the signed distances along a ray's line at which it crosses the surface, and the outward normals there.
+    #[inline]
+    #[must_use]
+    pub fn crossings(&self, ray: &Ray) -> Vec<(f64, Unit<Vector3<f64>>)> {
+        let m = ray.pos() - self.pos;
+        let b = m.dot(ray.dir());
+        let c = (m.dot(&m)) - self.rad.powi(2);
+
+        let disc = b.powi(2) - c;
+        if disc <= 0.0 {
+            return Vec::new();
+        }
+
+        let disc_sqrt = disc.sqrt();
+        [-b - disc_sqrt, -b + disc_sqrt]
+            .iter()
+            .map(|dist| (*dist, Unit::new_normalize(m + (ray.dir().as_ref() * *dist))))
+            .collect()
+    }
 }
 
 impl Trace for Sphere {
@@ -148,10 +168,19 @@ impl Collide for Sphere {
     }
 }
 
+impl Transform for Sphere {
+    #[inline]
+    fn transform(&mut self, trans: &Affine3<f64>) {
+        self.pos = trans.transform_point(&self.pos);
+        self.rad *= uniform_scaling(trans)
+            .expect("Unable to apply a stretching or shearing transform to a sphere.");
+    }
+}
+
 impl Emit for Sphere {
     #[inline]
     #[must_use]
-    fn cast(&self, rng: &mut ThreadRng) -> Ray {
+    fn cast<R: Rng>(&self, rng: &mut R) -> Ray {
         let mut ray = self.pos.cast(rng);
         ray.travel(self.rad);
 
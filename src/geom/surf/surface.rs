@@ -0,0 +1,238 @@
+//! Surface implementation.
+
+use crate::geom::{
+    Aabb, Collide, Csg, Cuboid, Cylinder, Disc, Emit, Mesh, Plane, Ray, SmoothTriangle, Sphere,
+    Trace, Transform,
+};
+use nalgebra::{Affine3, Unit, Vector3};
+use rand::Rng;
+
+/// Surface geometry enumeration.
+/// Triangulated meshes, analytic primitives which are intersected exactly, or combinations of these.
+#[derive(Debug, Clone)]
+pub enum Surface {
+    /// Triangulated mesh.
+    Mesh(Mesh),
+    /// Sphere.
+    Sphere(Sphere),
+    /// Capped cylinder.
+    Cylinder(Cylinder),
+    /// Oriented cuboid.
+    Cuboid(Cuboid),
+    /// Flat disc.
+    Disc(Disc),
+    /// Infinite plane.
+    Plane(Plane),
+    /// Constructive solid geometry.
+    Csg(Csg),
+}
+
+impl Surface {
+    /// Split the surface into its traceable patches.
+    /// Meshes give one patch per triangle, while primitives and combinations are a single patch.
+    #[inline]
+    #[must_use]
+    pub fn patches(&self) -> Vec<Patch<'_>> {
+        match self {
+            Self::Mesh(mesh) => mesh.tris().iter().map(Patch::Tri).collect(),
+            _ => vec![Patch::Prim(self)],
+        }
+    }
+
+    /// Determine the signed distances along a ray's line at which it crosses the surface, and the outward normals there.
+    /// Mesh crossings are only found ahead of the ray.
+    #[inline]
+    #[must_use]
+    pub fn crossings(&self, ray: &Ray) -> Vec<(f64, Unit<Vector3<f64>>)> {
+        match self {
+            Self::Mesh(mesh) => {
+                let mut crossings: Vec<_> = mesh
+                    .tris()
+                    .iter()
+                    .filter_map(|tri| {
+                        tri.dist_inside(ray)
+                            .map(|(dist, _)| (dist, *tri.tri().plane_norm()))
+                    })
+                    .collect();
+                crossings
+                    .sort_by(|a, b| a.0.partial_cmp(&b.0).expect("Invalid crossing distance."));
+                crossings
+            }
+            Self::Sphere(sphere) => sphere.crossings(ray),
+            Self::Cylinder(cylinder) => cylinder.crossings(ray),
+            Self::Cuboid(cuboid) => cuboid.crossings(ray),
+            Self::Disc(disc) => disc.crossings(ray),
+            Self::Plane(plane) => plane.crossings(ray),
+            Self::Csg(csg) => csg.crossings(ray),
+        }
+    }
+}
+
+/// Run an expression against the geometry held by any surface variant.
+macro_rules! each {
+    ($surf: expr, $geom: ident => $e: expr) => {
+        match $surf {
+            Surface::Mesh($geom) => $e,
+            Surface::Sphere($geom) => $e,
+            Surface::Cylinder($geom) => $e,
+            Surface::Cuboid($geom) => $e,
+            Surface::Disc($geom) => $e,
+            Surface::Plane($geom) => $e,
+            Surface::Csg($geom) => $e,
+        }
+    };
+}
+
+impl Trace for Surface {
+    #[inline]
+    #[must_use]
+    fn hit(&self, ray: &Ray) -> bool {
+        each!(self, geom => geom.hit(ray))
+    }
+
+    #[inline]
+    #[must_use]
+    fn dist(&self, ray: &Ray) -> Option<f64> {
+        each!(self, geom => geom.dist(ray))
+    }
+
+    #[inline]
+    #[must_use]
+    fn dist_norm(&self, ray: &Ray) -> Option<(f64, Unit<Vector3<f64>>)> {
+        each!(self, geom => geom.dist_norm(ray))
+    }
+
+    #[inline]
+    #[must_use]
+    fn dist_inside(&self, ray: &Ray) -> Option<(f64, bool)> {
+        each!(self, geom => geom.dist_inside(ray))
+    }
+
+    #[inline]
+    #[must_use]
+    fn dist_inside_norm(&self, ray: &Ray) -> Option<(f64, bool, Unit<Vector3<f64>>)> {
+        each!(self, geom => geom.dist_inside_norm(ray))
+    }
+}
+
+impl Collide for Surface {
+    #[inline]
+    #[must_use]
+    fn bounding_box(&self) -> Aabb {
+        each!(self, geom => geom.bounding_box())
+    }
+
+    #[inline]
+    #[must_use]
+    fn overlap(&self, aabb: &Aabb) -> bool {
+        each!(self, geom => geom.overlap(aabb))
+    }
+}
+
+impl Transform for Surface {
+    #[inline]
+    fn transform(&mut self, trans: &Affine3<f64>) {
+        each!(self, geom => geom.transform(trans))
+    }
+}
+
+impl Emit for Surface {
+    #[inline]
+    #[must_use]
+    fn cast<R: Rng>(&self, rng: &mut R) -> Ray {
+        each!(self, geom => geom.cast(rng))
+    }
+}
+
+/// Traceable piece of a surface.
+#[derive(Debug, Clone, Copy)]
+pub enum Patch<'a> {
+    /// Single triangle of a mesh.
+    Tri(&'a SmoothTriangle),
+    /// Whole analytic primitive.
+    Prim(&'a Surface),
+}
+
+impl<'a> Patch<'a> {
+    /// Determine the signed distances along a ray's line at which it crosses the patch, and whether the ray leaves the inside there.
+    /// Triangle crossings are only found ahead of the ray.
+    #[inline]
+    #[must_use]
+    pub fn crossings(&self, ray: &Ray) -> Vec<(f64, bool)> {
+        match self {
+            Self::Tri(tri) => tri.dist_inside(ray).into_iter().collect(),
+            Self::Prim(surf) => surf
+                .crossings(ray)
+                .into_iter()
+                .map(|(dist, norm)| (dist, ray.dir().dot(&norm) > 0.0))
+                .collect(),
+        }
+    }
+}
+
+impl<'a> Trace for Patch<'a> {
+    #[inline]
+    #[must_use]
+    fn hit(&self, ray: &Ray) -> bool {
+        match self {
+            Self::Tri(tri) => tri.hit(ray),
+            Self::Prim(surf) => surf.hit(ray),
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    fn dist(&self, ray: &Ray) -> Option<f64> {
+        match self {
+            Self::Tri(tri) => tri.dist(ray),
+            Self::Prim(surf) => surf.dist(ray),
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    fn dist_norm(&self, ray: &Ray) -> Option<(f64, Unit<Vector3<f64>>)> {
+        match self {
+            Self::Tri(tri) => tri.dist_norm(ray),
+            Self::Prim(surf) => surf.dist_norm(ray),
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    fn dist_inside(&self, ray: &Ray) -> Option<(f64, bool)> {
+        match self {
+            Self::Tri(tri) => tri.dist_inside(ray),
+            Self::Prim(surf) => surf.dist_inside(ray),
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    fn dist_inside_norm(&self, ray: &Ray) -> Option<(f64, bool, Unit<Vector3<f64>>)> {
+        match self {
+            Self::Tri(tri) => tri.dist_inside_norm(ray),
+            Self::Prim(surf) => surf.dist_inside_norm(ray),
+        }
+    }
+}
+
+impl<'a> Collide for Patch<'a> {
+    #[inline]
+    #[must_use]
+    fn bounding_box(&self) -> Aabb {
+        match self {
+            Self::Tri(tri) => tri.bounding_box(),
+            Self::Prim(surf) => surf.bounding_box(),
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    fn overlap(&self, aabb: &Aabb) -> bool {
+        match self {
+            Self::Tri(tri) => tri.overlap(aabb),
+            Self::Prim(surf) => surf.overlap(aabb),
+        }
+    }
+}
@@ -1,9 +1,70 @@
 //! Transform trait.
 
-use nalgebra::Similarity3;
+use nalgebra::{Affine3, Matrix3, Unit, Vector3};
+
+/// Relative tolerance used when checking that a transform preserves right angles.
+pub(crate) const CONFORMAL_TOL: f64 = 1.0e-9;
 
 /// Types implementing this trait may be transformed.
 pub trait Transform {
     /// Apply the given transformation.
-    fn transform(&mut self, trans: &Similarity3<f64>);
+    fn transform(&mut self, trans: &Affine3<f64>);
+}
+
+/// Get the linear part of an affine transformation.
+#[inline]
+#[must_use]
+pub fn linear_part(trans: &Affine3<f64>) -> Matrix3<f64> {
+    trans
+        .matrix()
+        .fixed_slice::<nalgebra::U3, nalgebra::U3>(0, 0)
+        .into_owned()
+}
+
+/// Transform a surface normal.
+/// Normals are mapped by the inverse transpose of the linear part, so that they stay perpendicular to transformed surfaces.
+#[inline]
+#[must_use]
+pub fn transform_norm(trans: &Affine3<f64>, norm: &Unit<Vector3<f64>>) -> Unit<Vector3<f64>> {
+    let inv = linear_part(trans)
+        .try_inverse()
+        .expect("Unable to transform normals with a singular transform.");
+
+    Unit::new_normalize(inv.transpose() * norm.as_ref())
+}
+
+/// Determine the lengths of the given mutually perpendicular vectors after transformation.
+/// None is returned if the transformed vectors are no longer mutually perpendicular.
+#[inline]
+#[must_use]
+pub fn perp_scalings(trans: &Affine3<f64>, vecs: &[Vector3<f64>]) -> Option<Vec<f64>> {
+    let lin = linear_part(trans);
+    let mapped: Vec<_> = vecs.iter().map(|v| lin * v).collect();
+
+    for (i, a) in mapped.iter().enumerate() {
+        for b in &mapped[(i + 1)..] {
+            if a.dot(b).abs() > CONFORMAL_TOL * a.norm() * b.norm() {
+                return None;
+            }
+        }
+    }
+
+    Some(mapped.iter().map(Vector3::norm).collect())
+}
+
+/// Determine the uniform scaling factor of a transform.
+/// None is returned if the transform stretches or shears.
+#[inline]
+#[must_use]
+pub fn uniform_scaling(trans: &Affine3<f64>) -> Option<f64> {
+    let scales = perp_scalings(trans, &[Vector3::x(), Vector3::y(), Vector3::z()])?;
+
+    if scales
+        .iter()
+        .all(|s| (s - scales[0]).abs() <= CONFORMAL_TOL * scales[0])
+    {
+        return Some(scales[0]);
+    }
+
+    None
 }
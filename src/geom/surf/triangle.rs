@@ -2,11 +2,11 @@
 
 use crate::{
     access,
-    geom::{Aabb, Collide, Emit, Ray, Trace, Transform},
+    geom::{linear_part, Aabb, Collide, Emit, Ray, Trace, Transform},
     list::Greek::{Alpha, Beta, Gamma},
 };
-use nalgebra::{Point3, Similarity3, Unit, Vector3};
-use rand::{rngs::ThreadRng, Rng};
+use nalgebra::{Affine3, Point3, Unit, Vector3};
+use rand::Rng;
 
 /// Triangle geometry.
 #[derive(Debug, Clone)]
@@ -320,21 +320,25 @@ impl Collide for Triangle {
     }
 }
 
+/// Reflecting transforms reverse the vertex winding, so that the plane normal keeps pointing the same way relative to the surface.
 impl Transform for Triangle {
     #[inline]
-    fn transform(&mut self, trans: &Similarity3<f64>) {
+    fn transform(&mut self, trans: &Affine3<f64>) {
         for v in &mut self.verts {
             *v = trans.transform_point(v);
         }
+        if linear_part(trans).determinant() < 0.0 {
+            self.verts.swap(Beta as usize, Gamma as usize);
+        }
 
-        self.plane_norm = Unit::new_normalize(trans.transform_vector(&self.plane_norm));
+        self.plane_norm = Self::init_plane_norm(&self.verts);
     }
 }
 
 impl Emit for Triangle {
     #[inline]
     #[must_use]
-    fn cast(&self, rng: &mut ThreadRng) -> Ray {
+    fn cast<R: Rng>(&self, rng: &mut R) -> Ray {
         let mut u = rng.gen::<f64>();
         let mut v = rng.gen::<f64>();
 
@@ -21,6 +21,8 @@ pub struct Frame {
     cam_pos: Point3<f64>,
     /// Target of the camera.
     tar_pos: Point3<f64>,
+    /// Time at which keyframed scene transforms are evaluated.
+    time: Option<f64>,
 }
 
 impl Frame {
@@ -31,4 +33,5 @@ impl Frame {
     clone!(fov, f64);
     access!(cam_pos, Point3<f64>);
     access!(tar_pos, Point3<f64>);
+    clone!(time, Option<f64>);
 }
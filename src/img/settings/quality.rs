@@ -16,6 +16,8 @@ pub struct Quality {
     dof_samples: Option<usize>,
     /// Shadow samples.
     shadow_samples: usize,
+    /// Optional random seed.
+    seed: Option<u64>,
 }
 
 impl Quality {
@@ -24,6 +26,7 @@ impl Quality {
     clone!(super_samples, Option<usize>);
     clone!(dof_samples, Option<usize>);
     clone!(shadow_samples, usize);
+    clone!(seed, Option<u64>);
 
     /// Calculate the number of samples expected per pixel.
     #[inline]
@@ -2,6 +2,7 @@
 
 use crate::{
     access,
+    err::Error,
     file::{Load, Transform as FileTransform},
     geom::{Mesh, Transform},
     sim::render::{Group, Scene as RenderScene},
@@ -20,18 +21,18 @@ pub struct Scene {
 impl Scene {
     access!(groups, Vec<(Group, Vec<(String, Option<FileTransform>)>)>);
 
-    /// Build a rendering scene.
+    /// Build a rendering scene, with any keyframed transforms evaluated at the given time.
+    /// Fails if any of the meshes can not be loaded.
     #[inline]
-    #[must_use]
-    pub fn build(&self, in_dir: &Path) -> RenderScene {
+    pub fn build(&self, in_dir: &Path, time: f64) -> Result<RenderScene, Error> {
         let mut surfs: BTreeMap<Group, Vec<_>> = BTreeMap::new();
         for (group, meshes) in &self.groups {
             for (name, transform) in meshes {
                 let path = in_dir.join(format!("{}.obj", name));
-                let mut mesh = Mesh::load(&path);
+                let mut mesh = Mesh::load(&path)?;
 
                 if let Some(transform) = transform {
-                    mesh.transform(&transform.build());
+                    mesh.transform(&transform.build(time));
                 }
 
                 if let Some(entry) = surfs.get_mut(group) {
@@ -42,6 +43,6 @@ impl Scene {
             }
         }
 
-        RenderScene::new(surfs)
+        Ok(RenderScene::new(surfs))
     }
 }
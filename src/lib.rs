@@ -37,6 +37,7 @@
 
 pub mod chem;
 pub mod data;
+pub mod err;
 pub mod file;
 pub mod fmt;
 pub mod geom;
@@ -1,12 +1,12 @@
 //! Distribution functions.
 
-use rand::{rngs::ThreadRng, Rng};
+use rand::Rng;
 use std::f64::consts::PI;
 
 /// Sample the Henyey-Greenstein phase function with a given asymmetry parameter.
 #[inline]
 #[must_use]
-pub fn henyey_greenstein(rng: &mut ThreadRng, asym: f64) -> f64 {
+pub fn henyey_greenstein<R: Rng>(rng: &mut R, asym: f64) -> f64 {
     debug_assert!(asym.abs() <= 1.0);
 
     if asym.abs() < 1.0e-6 {
@@ -22,7 +22,7 @@ pub fn henyey_greenstein(rng: &mut ThreadRng, asym: f64) -> f64 {
 /// Sample the normal distribution.
 #[inline]
 #[must_use]
-pub fn normal(rng: &mut ThreadRng) -> f64 {
+pub fn normal<R: Rng>(rng: &mut R) -> f64 {
     let a = (-2.0 * rng.gen_range(0.0_f64, 1.0).ln()).sqrt();
     let theta = rng.gen_range(0.0, 2.0 * PI);
 
@@ -34,6 +34,24 @@ pub fn normal(rng: &mut ThreadRng) -> f64 {
 /// Sample a gaussian distribution.
 #[inline]
 #[must_use]
-pub fn gaussian(rng: &mut ThreadRng, mu: f64, sigma: f64) -> f64 {
+pub fn gaussian<R: Rng>(rng: &mut R, mu: f64, sigma: f64) -> f64 {
     normal(rng).mul_add(sigma, mu)
 }
+
+/// Sample the dimensionless Planck distribution, x^3 / (e^x - 1), where x = hc / (lambda kT).
+#[inline]
+#[must_use]
+pub fn planck<R: Rng>(rng: &mut R) -> f64 {
+    let target = rng.gen_range(0.0_f64, 1.0) * PI.powi(4) / 90.0;
+
+    let mut l = 1.0_f64;
+    let mut sum = 1.0;
+    while sum < target {
+        l += 1.0;
+        sum += l.powi(-4);
+    }
+
+    let prod: f64 = (0..4).map(|_| 1.0 - rng.gen_range(0.0_f64, 1.0)).product();
+
+    -prod.ln() / l
+}
@@ -2,5 +2,7 @@
 
 pub mod distribution;
 pub mod probability;
+pub mod stream;
+pub mod tabulated;
 
-pub use self::probability::*;
+pub use self::{probability::*, stream::*, tabulated::*};
@@ -3,7 +3,7 @@
 use crate::math::distribution;
 use attr::json;
 use ndarray::Array1;
-use rand::{rngs::ThreadRng, Rng};
+use rand::Rng;
 
 /// Probability distribution formulae.
 #[json]
@@ -100,7 +100,7 @@ impl Probability {
     /// Generate a random number from the described distribution.
     #[inline]
     #[must_use]
-    pub fn gen(&self, rng: &mut ThreadRng) -> f64 {
+    pub fn gen<R: Rng>(&self, rng: &mut R) -> f64 {
         match self {
             Self::Point { c } => *c,
             Self::Points { cs } => *cs.get(rng.gen_range(0, cs.len())).expect("Invalid index."),
@@ -0,0 +1,18 @@
+//! Reproducible random-number stream functions.
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+/// Counter-based random number generator.
+/// Each seed provides 2^64 independent streams.
+pub type Stream = ChaCha8Rng;
+
+/// Construct the random number stream for a given block of work within a seeded run.
+#[inline]
+#[must_use]
+pub fn stream(seed: u64, block: u64) -> Stream {
+    let mut rng = Stream::seed_from_u64(seed);
+    rng.set_stream(block);
+
+    rng
+}
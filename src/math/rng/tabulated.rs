@@ -0,0 +1,146 @@
+//! Tabulated distribution implementation.
+
+use crate::{
+    access,
+    err::Error,
+    file::{from_csv, Load},
+    math::interp,
+};
+use ndarray::{s, Array1};
+use rand::Rng;
+use std::path::Path;
+
+/// Tabulated distribution.
+/// The probability density is interpolated linearly between the tabulated points,
+/// and sampled by exact inversion of the resulting piecewise quadratic cumulative distribution.
+#[derive(Debug, Clone)]
+pub struct Tabulated {
+    /// Tabulated values.
+    xs: Array1<f64>,
+    /// Normalised probability density at each value.
+    ps: Array1<f64>,
+    /// Cumulative probability at each value.
+    cdf: Array1<f64>,
+}
+
+impl Tabulated {
+    access!(xs, Array1<f64>);
+    access!(ps, Array1<f64>);
+    access!(cdf, Array1<f64>);
+
+    /// Construct a new instance from values and their relative probability densities.
+    #[inline]
+    #[must_use]
+    pub fn new(xs: Array1<f64>, ps: &Array1<f64>) -> Self {
+        debug_assert!(xs.len() > 1);
+        debug_assert!(xs.len() == ps.len());
+        debug_assert!(xs.windows(2).into_iter().all(|w| w[0] < w[1]));
+        debug_assert!(ps.iter().all(|p| *p >= 0.0));
+
+        let mut cdf = Array1::zeros(xs.len());
+        for i in 1..xs.len() {
+            cdf[i] = cdf[i - 1] + (0.5 * (ps[i - 1] + ps[i]) * (xs[i] - xs[i - 1]));
+        }
+
+        let total = cdf[xs.len() - 1];
+        debug_assert!(total > 0.0);
+        cdf /= total;
+        let ps = ps / total;
+
+        Self { xs, ps, cdf }
+    }
+
+    /// Evaluate the normalised probability density at a value.
+    /// Zero outside of the tabulated range.
+    #[inline]
+    #[must_use]
+    pub fn pdf(&self, x: f64) -> f64 {
+        if x < self.xs[0] || x > self.xs[self.xs.len() - 1] {
+            return 0.0;
+        }
+
+        interp::linear(&self.xs, &self.ps, x)
+    }
+
+    /// Generate a random number from the tabulated distribution.
+    #[inline]
+    #[must_use]
+    pub fn gen<R: Rng>(&self, rng: &mut R) -> f64 {
+        let r = rng.gen_range(0.0_f64, 1.0);
+
+        let index = match self
+            .cdf
+            .as_slice()
+            .expect("Invalid cumulative distribution.")
+            .binary_search_by(|c| c.partial_cmp(&r).expect("Invalid cumulative probability."))
+        {
+            Ok(index) | Err(index) => index,
+        }
+        .max(1)
+        .min(self.xs.len() - 1);
+
+        let (c0, c1) = (self.cdf[index - 1], self.cdf[index]);
+        let (x0, x1) = (self.xs[index - 1], self.xs[index]);
+        let (p0, p1) = (self.ps[index - 1], self.ps[index]);
+
+        if c1 <= c0 {
+            return x0;
+        }
+
+        // Solve p0 t + grad t^2 / 2 = r - c0 for the distance t into the bin, in the form which remains stable as grad -> 0.
+        let grad = (p1 - p0) / (x1 - x0);
+        let u = (r - c0).max(0.0);
+        let denom = p0 + ((p0 * p0) + (2.0 * grad * u)).max(0.0).sqrt();
+        let t = if denom > 0.0 { (2.0 * u) / denom } else { 0.0 };
+
+        (x0 + t).min(x1)
+    }
+}
+
+impl Load for Tabulated {
+    /// Load from a two-column csv file of values and relative probability densities.
+    #[inline]
+    fn load(path: &Path) -> Result<Self, Error> {
+        let table = from_csv(path)?;
+        if table.ncols() != 2 {
+            return Err(Error::parse(
+                path,
+                None,
+                "Expected two columns in tabulated file.",
+            ));
+        }
+
+        let xs = table.slice(s![.., 0]).to_owned();
+        let ps = table.slice(s![.., 1]).to_owned();
+        if xs.len() < 2 {
+            return Err(Error::parse(
+                path,
+                None,
+                "Expected at least two rows in tabulated file.",
+            ));
+        }
+        if !xs.windows(2).into_iter().all(|w| w[0] < w[1]) {
+            return Err(Error::parse(
+                path,
+                None,
+                "Tabulated values must be strictly increasing.",
+            ));
+        }
+        if !ps.iter().all(|p| *p >= 0.0) {
+            return Err(Error::parse(
+                path,
+                None,
+                "Tabulated densities must be non-negative.",
+            ));
+        }
+        if !ps.iter().any(|p| *p > 0.0) {
+            return Err(Error::parse(
+                path,
+                None,
+                "Tabulated densities must not all be zero.",
+            ));
+        }
+
+        Ok(Self::new(xs, &ps))
+    }
+}
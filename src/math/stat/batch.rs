@@ -0,0 +1,18 @@
+//! Batch statistics functions.
+
+use std::f64::INFINITY;
+
+/// Determine the relative error of a total from the sum, and sum of squares, of its batch contributions.
+/// Returns infinity when the total is zero or too few batches have been accumulated.
+#[inline]
+#[must_use]
+pub fn rel_err(sum: f64, sum_sq: f64, batches: u64) -> f64 {
+    if batches < 2 || sum <= 0.0 {
+        return INFINITY;
+    }
+
+    let n = batches as f64;
+    let var = (n / (n - 1.0)) * ((sum_sq / (sum * sum)) - (1.0 / n));
+
+    var.max(0.0).sqrt()
+}
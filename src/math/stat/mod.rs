@@ -1,3 +1,4 @@
 //! Statistics sub-module.
 
+pub mod batch;
 pub mod list;
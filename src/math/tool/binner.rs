@@ -1,9 +1,11 @@
 //! Binner implementation.
 
 use crate::{clone, math::Range};
+use ndarray::Array1;
+use serde::{Deserialize, Serialize};
 
 /// One-dimensional binning structure.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Binner {
     /// Range.
     range: Range,
@@ -31,14 +33,25 @@ impl Binner {
         self.range.width() / self.bins as f64
     }
 
+    /// Calculate the centre value of each bin.
+    #[inline]
+    #[must_use]
+    pub fn centres(&self) -> Array1<f64> {
+        let width = self.bin_width();
+        Array1::from_shape_fn(self.bins as usize, |i| {
+            (i as f64 + 0.5).mul_add(width, self.range.min())
+        })
+    }
+
     /// Determine the corresponding bin.
+    /// The range is inclusive, so a value on the upper edge falls into the last bin.
     #[inline]
     #[must_use]
     pub fn bin(&self, x: f64) -> usize {
         debug_assert!(self.range.contains(x));
 
         let frac = (x - self.range.min()) / self.range.width();
-        (frac * self.bins as f64).floor() as usize
+        ((frac * self.bins as f64).floor() as usize).min(self.bins as usize - 1)
     }
 
     /// Determine the corresponding bin if the value is within the range.
@@ -0,0 +1,35 @@
+//! Interpolation functions.
+
+use ndarray::Array1;
+
+/// Linearly interpolate tabulated values at a point.
+/// Values outside of the tabulated range are clamped to the end values.
+#[inline]
+#[must_use]
+pub fn linear(xs: &Array1<f64>, ys: &Array1<f64>, x: f64) -> f64 {
+    debug_assert!(xs.len() > 1);
+    debug_assert!(xs.len() == ys.len());
+
+    let n = xs.len();
+    if x <= xs[0] {
+        return ys[0];
+    }
+    if x >= xs[n - 1] {
+        return ys[n - 1];
+    }
+
+    let index = match xs
+        .as_slice()
+        .expect("Invalid tabulated values.")
+        .binary_search_by(|v| v.partial_cmp(&x).expect("Invalid tabulated value."))
+    {
+        Ok(index) | Err(index) => index,
+    }
+    .max(1)
+    .min(n - 1);
+
+    let (x0, x1) = (xs[index - 1], xs[index]);
+    let (y0, y1) = (ys[index - 1], ys[index]);
+
+    y0 + ((x - x0) / (x1 - x0) * (y1 - y0))
+}
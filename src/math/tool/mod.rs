@@ -2,6 +2,7 @@
 
 pub mod binner;
 pub mod indexer;
+pub mod interp;
 pub mod range;
 
 pub use self::{binner::*, range::*};
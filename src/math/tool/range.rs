@@ -1,10 +1,11 @@
 //! Range implementation.
 
 use crate::clone;
+use serde::{Deserialize, Serialize};
 use std::f64::{INFINITY, NEG_INFINITY};
 
 /// One-dimensional inclusive Range.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Range {
     /// Minimum bound.
     min: f64,
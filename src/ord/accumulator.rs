@@ -0,0 +1,65 @@
+//! Ordered accumulator implementation.
+
+use std::{
+    ops::AddAssign,
+    sync::{Condvar, Mutex},
+};
+
+/// Accumulates blocks of work, which may complete out of order, strictly in block order.
+/// This keeps floating-point summation independent of the thread scheduling.
+/// A thread completing a block early waits for its predecessors rather than storing a copy,
+/// so no more than one block per thread is ever held in memory.
+#[derive(Debug)]
+pub struct Accumulator<T> {
+    /// Accumulated total, and the start index of the next block to be accumulated.
+    state: Mutex<(T, u64)>,
+    /// Signalled each time a block is accumulated.
+    ready: Condvar,
+}
+
+impl<T> Accumulator<T>
+where
+    for<'a> T: AddAssign<&'a T>,
+{
+    /// Construct a new instance starting from the given initial total.
+    #[inline]
+    #[must_use]
+    pub fn new(total: T, next: u64) -> Self {
+        Self {
+            state: Mutex::new((total, next)),
+            ready: Condvar::new(),
+        }
+    }
+
+    /// Add a completed block covering the range [start, end), blocking until all earlier blocks have been added.
+    /// The given closure is called with the updated total while the accumulator is still locked.
+    /// Every block before start must eventually be pushed, or this will never return.
+    #[inline]
+    pub fn push<F, O>(&self, start: u64, end: u64, block: &T, then: F) -> O
+    where
+        F: FnOnce(&T) -> O,
+    {
+        debug_assert!(end > start);
+
+        let mut state = self.state.lock().expect("Could not lock accumulator.");
+        while state.1 != start {
+            debug_assert!(state.1 < start);
+            state = self.ready.wait(state).expect("Could not lock accumulator.");
+        }
+
+        state.0 += block;
+        state.1 = end;
+        self.ready.notify_all();
+
+        then(&state.0)
+    }
+
+    /// Retrieve the total and the index up to which it has been accumulated.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> (T, u64) {
+        self.state
+            .into_inner()
+            .expect("Could not retrieve accumulator.")
+    }
+}
@@ -35,6 +35,7 @@ macro_rules! key {
     };
 }
 
+key!(DetKey);
 key!(InterKey);
 key!(LightKey);
 key!(MatKey);
@@ -1,7 +1,8 @@
 //! Ordering module.
 
+pub mod accumulator;
 pub mod key;
 pub mod set;
 pub mod sort;
 
-pub use self::{key::*, set::*};
+pub use self::{accumulator::*, key::*, set::*};
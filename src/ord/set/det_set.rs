@@ -0,0 +1,26 @@
+//! Detector set.
+
+use crate::{
+    file::Detector as DetectorForm,
+    ord::{DetKey, Set},
+    world::Instrument,
+};
+use std::collections::BTreeMap;
+
+/// Alias for the detector set.
+pub type DetSet = Set<DetKey, Instrument>;
+
+impl DetSet {
+    /// Form a detector set from a set of detector forms.
+    #[inline]
+    #[must_use]
+    pub fn build(proto_dets: &Set<DetKey, DetectorForm>) -> Self {
+        let mut map = BTreeMap::new();
+
+        for (key, form) in proto_dets.map() {
+            map.insert(key.clone(), form.build());
+        }
+
+        Self::new(map)
+    }
+}
@@ -1,10 +1,11 @@
 //! Intersection set.
 
 use crate::{
-    geom::{Aabb, Ray, Trace},
+    geom::{Aabb, Ray, Scan, Trace},
     ord::{InterKey, MatKey, Set, SurfKey, SurfSet},
     world::Interface,
 };
+use std::collections::BTreeMap;
 
 /// Alias for the interface set.
 pub type InterSet = Set<InterKey, Interface>;
@@ -42,6 +43,17 @@ impl InterSet {
         mats
     }
 
+    /// Get the keys of the interfaces which use the named material on either side.
+    #[inline]
+    #[must_use]
+    pub fn mat_users(&self, mat: &str) -> Vec<&InterKey> {
+        self.map
+            .iter()
+            .filter(|(_, inter)| inter.in_mat().str() == mat || inter.out_mat().str() == mat)
+            .map(|(key, _)| key)
+            .collect()
+    }
+
     /// Determine which material, if any, would be observed with a given ray.
     #[inline]
     #[must_use]
@@ -84,4 +96,45 @@ impl InterSet {
 
         None
     }
+
+    /// Construct a scanline structure for observing the materials within a boundary.
+    #[inline]
+    #[must_use]
+    pub fn scan<'a>(
+        &'a self,
+        surfs: &'a SurfSet,
+        bound: Aabb,
+        res: [usize; 2],
+    ) -> Scan<'a, &'a Interface> {
+        let patches: Vec<_> = self
+            .map
+            .values()
+            .flat_map(|inter| {
+                surfs
+                    .get(inter.surf())
+                    .patches()
+                    .into_iter()
+                    .map(move |patch| (inter, patch))
+            })
+            .collect();
+
+        Scan::new(bound, res, &patches)
+    }
+
+    /// Determine the material most commonly found outside of the surfaces.
+    /// Used for points which can not be resolved by a scan.
+    #[inline]
+    #[must_use]
+    pub fn ambient_mat(&self) -> &MatKey {
+        let mut counts: BTreeMap<&MatKey, usize> = BTreeMap::new();
+        for inter in self.map.values() {
+            *counts.entry(inter.out_mat()).or_insert(0) += 1;
+        }
+
+        counts
+            .into_iter()
+            .max_by_key(|(_, count)| *count)
+            .expect("Unable to determine ambient material of an empty set.")
+            .0
+    }
 }
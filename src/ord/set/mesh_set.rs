@@ -1,9 +1,68 @@
 //! Mesh set.
 
 use crate::{
-    geom::Mesh,
+    err::Error,
+    file::Load,
+    geom::{Mesh, PolyMesh},
     ord::{MeshKey, Set},
 };
+use std::{
+    collections::BTreeMap,
+    io::{self, ErrorKind},
+    path::Path,
+};
+
+/// Supported mesh file extensions, in order of preference.
+pub const MESH_EXTS: [&str; 3] = ["obj", "stl", "ply"];
+
+/// Separator between a mesh file name and the name of a group within it.
+pub const GROUP_SEPARATOR: char = ':';
 
 /// Alias for the mesh set.
 pub type MeshSet = Set<MeshKey, Mesh>;
+
+impl MeshSet {
+    /// Load a set of meshes, using the first supported file extension found for each key.
+    /// A key of the form "file:group" selects a single object or group from within the file.
+    /// Fails if a mesh file can not be found or loaded, or does not contain the requested group.
+    #[inline]
+    pub fn load_meshes(dir: &Path, keys: &[MeshKey]) -> Result<Self, Error> {
+        let mut files: BTreeMap<&str, PolyMesh> = BTreeMap::new();
+        let mut map = BTreeMap::new();
+
+        for key in keys {
+            let mut parts = key.str().splitn(2, GROUP_SEPARATOR);
+            let name = parts.next().expect("Missing mesh file name.");
+            let group = parts.next();
+
+            let wrap = |err| Error::Load {
+                key: key.to_string(),
+                source: Box::new(err),
+            };
+
+            if !files.contains_key(name) {
+                let path = MESH_EXTS
+                    .iter()
+                    .map(|ext| dir.join(format!("{}.{}", name, ext)))
+                    .find(|path| path.exists())
+                    .ok_or_else(|| {
+                        wrap(Error::io(
+                            &dir.join(name),
+                            io::Error::new(
+                                ErrorKind::NotFound,
+                                format!("no mesh file with extension {}", MESH_EXTS.join(", ")),
+                            ),
+                        ))
+                    })?;
+                println!("\tLoading: {}", path.display());
+
+                let _ = files.insert(name, PolyMesh::load(&path).map_err(wrap)?);
+            }
+            let poly = &files[name];
+
+            map.insert(key.clone(), Mesh::new(poly.tris(group).map_err(wrap)?));
+        }
+
+        Ok(Self::new(map))
+    }
+}
@@ -1,5 +1,6 @@
 //! Set implementation.
 
+pub mod det_set;
 pub mod inter_set;
 pub mod light_set;
 pub mod mat_set;
@@ -11,12 +12,13 @@ pub mod state_set;
 pub mod surf_set;
 
 pub use self::{
-    inter_set::*, light_set::*, mat_set::*, mesh_set::*, react_set::*, region_set::*, spec_set::*,
-    state_set::*, surf_set::*,
+    det_set::*, inter_set::*, light_set::*, mat_set::*, mesh_set::*, react_set::*, region_set::*,
+    spec_set::*, state_set::*, surf_set::*,
 };
 
 use crate::{
     access,
+    err::Error,
     file::{as_json, from_json, Load, Save},
 };
 use serde::{Deserialize, Serialize};
@@ -39,51 +41,77 @@ impl<K: Display + Ord, T> Set<K, T> {
         Self { map }
     }
 
+    /// Construct the error reported when a key does not exist within the set.
+    #[inline]
+    #[must_use]
+    fn missing(key: &K) -> Error {
+        let set = std::any::type_name::<K>();
+
+        Error::MissingKey {
+            key: key.to_string(),
+            set: set.rsplit("::").next().unwrap_or(set).to_string(),
+        }
+    }
+
+    /// Access a value within the map.
+    /// Fails if the key does not exist within the set.
+    #[inline]
+    pub fn try_get(&self, key: &K) -> Result<&T, Error> {
+        self.map.get(key).ok_or_else(|| Self::missing(key))
+    }
+
     /// Access a value within the map.
     #[inline]
     #[must_use]
     pub fn get(&self, key: &K) -> &T {
+        self.try_get(key).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Determine the index corresponding to a key within the set.
+    /// Fails if the key does not exist within the set.
+    #[inline]
+    pub fn try_index_of_key(&self, key: &K) -> Result<usize, Error> {
         self.map
-            .get(key)
-            .unwrap_or_else(|| panic!("Key {} does not exist within the set.", key))
+            .keys()
+            .position(|k| k == key)
+            .ok_or_else(|| Self::missing(key))
     }
 
     /// Determine the index corresponding to a key within the set.
     #[inline]
     #[must_use]
     pub fn index_of_key(&self, key: &K) -> usize {
-        for (i, k) in self.map.keys().enumerate() {
-            if k == key {
-                return i;
-            }
-        }
-
-        panic!("Failed to find name within set.");
+        self.try_index_of_key(key)
+            .unwrap_or_else(|err| panic!("{}", err))
     }
 }
 
 impl<K: Display + Clone + Ord, T: Load> Set<K, T> {
     /// Load a set of files.
+    /// Fails if any of the files can not be loaded, reporting the key of the first failure.
     #[inline]
-    #[must_use]
-    pub fn load(dir: &Path, keys: &[K], ext: &str) -> Self {
+    pub fn load(dir: &Path, keys: &[K], ext: &str) -> Result<Self, Error> {
         let mut map = BTreeMap::new();
 
         for key in keys {
             let path = dir.join(format!("{}.{}", key, ext));
             println!("\tLoading: {}", path.display());
 
-            map.insert((*key).clone(), T::load(&path));
+            let item = T::load(&path).map_err(|err| Error::Load {
+                key: key.to_string(),
+                source: Box::new(err),
+            })?;
+            map.insert((*key).clone(), item);
         }
 
-        Self::new(map)
+        Ok(Self::new(map))
     }
 }
 
 impl<K: Ord + Serialize, T: Serialize> Save for Set<K, T> {
     #[inline]
-    fn save(&self, path: &Path) {
-        as_json(self, path);
+    fn save(&self, path: &Path) -> Result<(), Error> {
+        as_json(self, path)
     }
 }
 
@@ -93,8 +121,7 @@ where
     for<'de> T: Deserialize<'de>,
 {
     #[inline]
-    #[must_use]
-    fn load(path: &Path) -> Self {
+    fn load(path: &Path) -> Result<Self, Error> {
         from_json(path)
     }
 }
@@ -1,10 +1,11 @@
 //! Region set.
 
 use crate::{
-    geom::{Aabb, Ray, Trace},
+    geom::{Aabb, Ray, Scan, Trace},
     ord::{RegionKey, Set, StateKey, SurfKey, SurfSet},
     world::Region,
 };
+use std::collections::BTreeMap;
 
 /// Alias for the region set.
 pub type RegionSet = Set<RegionKey, Region>;
@@ -84,4 +85,45 @@ impl RegionSet {
 
         None
     }
+
+    /// Construct a scanline structure for observing the states within a boundary.
+    #[inline]
+    #[must_use]
+    pub fn scan<'a>(
+        &'a self,
+        surfs: &'a SurfSet,
+        bound: Aabb,
+        res: [usize; 2],
+    ) -> Scan<'a, &'a Region> {
+        let patches: Vec<_> = self
+            .map
+            .values()
+            .flat_map(|region| {
+                surfs
+                    .get(region.surf())
+                    .patches()
+                    .into_iter()
+                    .map(move |patch| (region, patch))
+            })
+            .collect();
+
+        Scan::new(bound, res, &patches)
+    }
+
+    /// Determine the state most commonly found outside of the surfaces.
+    /// Used for points which can not be resolved by a scan.
+    #[inline]
+    #[must_use]
+    pub fn ambient_state(&self) -> &StateKey {
+        let mut counts: BTreeMap<&StateKey, usize> = BTreeMap::new();
+        for region in self.map.values() {
+            *counts.entry(region.out_state()).or_insert(0) += 1;
+        }
+
+        counts
+            .into_iter()
+            .max_by_key(|(_, count)| *count)
+            .expect("Unable to determine ambient state of an empty set.")
+            .0
+    }
 }
@@ -1,26 +1,32 @@
 //! Surface set.
 
 use crate::{
+    err::Error,
     file::Surface as SurfaceForm,
-    geom::Mesh,
+    geom::Surface,
     ord::{MeshSet, Set, SurfKey},
 };
 use std::collections::BTreeMap;
 
 /// Alias for the surface set.
-pub type SurfSet = Set<SurfKey, Mesh>;
+pub type SurfSet = Set<SurfKey, Surface>;
 
 impl SurfSet {
-    /// Form a surface set from a mesh set.
+    /// Form a surface set from a mesh set and any primitives.
+    /// Keyframed transforms are evaluated at the given time.
+    /// Fails if any of the surfaces can not be built.
     #[inline]
-    #[must_use]
-    pub fn build(proto_surfs: &Set<SurfKey, SurfaceForm>, mesh_set: &MeshSet) -> Self {
+    pub fn build(
+        proto_surfs: &Set<SurfKey, SurfaceForm>,
+        mesh_set: &MeshSet,
+        time: f64,
+    ) -> Result<Self, Error> {
         let mut map = BTreeMap::new();
 
         for (key, form) in proto_surfs.map() {
-            map.insert(key.clone(), form.build(mesh_set));
+            map.insert(key.clone(), form.build(key, mesh_set, time)?);
         }
 
-        Self::new(map)
+        Ok(Self::new(map))
     }
 }
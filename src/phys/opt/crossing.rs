@@ -1,7 +1,7 @@
 //! Crossing implementation.
 
 use crate::{access, clone};
-use nalgebra::{Unit, Vector3};
+use nalgebra::{Matrix4, Unit, Vector3, Vector4};
 
 /// Crossing structure implementation.
 /// Optical interface Crossing information structure.
@@ -12,12 +12,24 @@ pub struct Crossing {
     ref_dir: Unit<Vector3<f64>>,
     /// Transmission (refraction) direction.
     trans_dir: Option<Unit<Vector3<f64>>>,
+    /// Normal of the plane of incidence, if the incidence is not normal.
+    perp: Option<Unit<Vector3<f64>>>,
+    /// Amplitude reflection coefficients (s, p).
+    ref_amps: (f64, f64),
+    /// Amplitude transmission coefficients (s, p).
+    trans_amps: Option<(f64, f64)>,
+    /// Phase retardance, of p relative to s, upon total internal reflection.
+    retard: f64,
 }
 
 impl Crossing {
     clone!(ref_prob, f64);
     access!(ref_dir, Unit<Vector3<f64>>);
     access!(trans_dir, Option<Unit<Vector3<f64>>>);
+    access!(perp, Option<Unit<Vector3<f64>>>);
+    clone!(ref_amps, (f64, f64));
+    clone!(trans_amps, Option<(f64, f64)>);
+    clone!(retard, f64);
 
     /// Construct a new instance.
     #[inline]
@@ -46,23 +58,36 @@ impl Crossing {
             Some((n_next / n_curr).asin())
         };
 
-        let (ref_prob, trans_dir) =
-            if crit_ang.is_some() && (ci.acos() >= crit_ang.expect("Calculation failure.")) {
-                (1.0, None)
-            } else {
-                let s2t = n.powi(2) * (1.0 - ci.powi(2));
-                let ct = (1.0 - s2t).sqrt();
-
-                (
-                    Self::init_ref_prob(n_curr, n_next, ci, ct),
-                    Some(Self::init_trans_dir(inc, &norm, n, ci, ct)),
-                )
+        let perp = inc.cross(&norm);
+        let perp = if perp.norm() > 1.0e-9 {
+            Some(Unit::new_normalize(perp))
+        } else {
+            None
+        };
+
+        if crit_ang.is_some() && (ci.acos() >= crit_ang.expect("Calculation failure.")) {
+            return Self {
+                ref_prob: 1.0,
+                ref_dir: Self::init_ref_dir(inc, &norm, ci),
+                trans_dir: None,
+                perp,
+                ref_amps: (1.0, 1.0),
+                trans_amps: None,
+                retard: Self::init_retard(n_curr, n_next, ci),
             };
+        }
+
+        let s2t = n.powi(2) * (1.0 - ci.powi(2));
+        let ct = (1.0 - s2t).sqrt();
 
         Self {
-            ref_prob,
+            ref_prob: Self::init_ref_prob(n_curr, n_next, ci, ct),
             ref_dir: Self::init_ref_dir(inc, &norm, ci),
-            trans_dir,
+            trans_dir: Some(Self::init_trans_dir(inc, &norm, n, ci, ct)),
+            perp,
+            ref_amps: Self::init_ref_amps(n_curr, n_next, ci, ct),
+            trans_amps: Some(Self::init_trans_amps(n_curr, n_next, ci, ct)),
+            retard: 0.0,
         }
     }
 
@@ -83,6 +108,36 @@ impl Crossing {
         (rn + rt) / 2.0
     }
 
+    /// Calculate the amplitude reflection coefficients (s, p).
+    #[must_use]
+    fn init_ref_amps(n1: f64, n2: f64, ci: f64, ct: f64) -> (f64, f64) {
+        let rs = ((n1 * ci) - (n2 * ct)) / ((n1 * ci) + (n2 * ct));
+        let rp = ((n2 * ci) - (n1 * ct)) / ((n2 * ci) + (n1 * ct));
+
+        (rs, rp)
+    }
+
+    /// Calculate the amplitude transmission coefficients (s, p).
+    #[must_use]
+    fn init_trans_amps(n1: f64, n2: f64, ci: f64, ct: f64) -> (f64, f64) {
+        let ts = (2.0 * n1 * ci) / ((n1 * ci) + (n2 * ct));
+        let tp = (2.0 * n1 * ci) / ((n2 * ci) + (n1 * ct));
+
+        (ts, tp)
+    }
+
+    /// Calculate the phase retardance, of p relative to s, upon total internal reflection.
+    #[must_use]
+    fn init_retard(n1: f64, n2: f64, ci: f64) -> f64 {
+        let n = n2 / n1;
+        let root = ((1.0 - ci.powi(2)) - n.powi(2)).max(0.0).sqrt();
+
+        let delta_s = 2.0 * (root / ci).atan();
+        let delta_p = 2.0 * (root / (n.powi(2) * ci)).atan();
+
+        delta_p - delta_s
+    }
+
     /// Calculate the reflection direction.
     #[must_use]
     fn init_ref_dir(
@@ -111,4 +166,53 @@ impl Crossing {
     pub fn trans_prob(&self) -> f64 {
         1.0 - self.ref_prob
     }
+
+    /// Calculate the reflection probability of a Stokes vector referenced to the plane of incidence.
+    #[inline]
+    #[must_use]
+    pub fn pol_ref_prob(&self, stokes: &Vector4<f64>) -> f64 {
+        if self.trans_dir.is_none() {
+            return 1.0;
+        }
+
+        let (rs, rp) = self.ref_amps;
+        let (rs2, rp2) = (rs * rs, rp * rp);
+
+        0.5 * (rp2 + rs2) + (0.5 * (rp2 - rs2) * stokes.y / stokes.x)
+    }
+
+    /// Determine the Mueller matrix of reflection, referenced to the plane of incidence.
+    #[inline]
+    #[must_use]
+    pub fn ref_mueller(&self) -> Matrix4<f64> {
+        if self.trans_dir.is_none() {
+            let (s, c) = self.retard.sin_cos();
+            return Matrix4::new(
+                1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, c, s, 0.0, 0.0, -s, c,
+            );
+        }
+
+        let (rs, rp) = self.ref_amps;
+        fresnel_mueller(rs, rp)
+    }
+
+    /// Determine the Mueller matrix of transmission, referenced to the plane of incidence.
+    #[inline]
+    #[must_use]
+    pub fn trans_mueller(&self) -> Option<Matrix4<f64>> {
+        self.trans_amps.map(|(ts, tp)| fresnel_mueller(ts, tp))
+    }
+}
+
+/// Form the Mueller matrix of given s and p amplitude coefficients.
+#[inline]
+#[must_use]
+fn fresnel_mueller(s: f64, p: f64) -> Matrix4<f64> {
+    let a = 0.5 * (p * p + s * s);
+    let b = 0.5 * (p * p - s * s);
+    let c = p * s;
+
+    Matrix4::new(
+        a, b, 0.0, 0.0, b, a, 0.0, 0.0, 0.0, 0.0, c, 0.0, 0.0, 0.0, 0.0, c,
+    )
 }
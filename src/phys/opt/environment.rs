@@ -1,6 +1,6 @@
 //! Environment implementation.
 
-use crate::clone;
+use crate::{access, clone, phys::Phase};
 
 /// Local optical properties structure.
 #[derive(Debug, Clone)]
@@ -13,8 +13,8 @@ pub struct Environment {
     abs_coeff: f64,
     /// Shift coefficient. [m^-1]
     shift_coeff: f64,
-    /// Asymmetry parameter.
-    asym: f64,
+    /// Phase function.
+    phase: Phase,
 }
 
 impl Environment {
@@ -22,7 +22,7 @@ impl Environment {
     clone!(scat_coeff, f64);
     clone!(abs_coeff, f64);
     clone!(shift_coeff, f64);
-    clone!(asym, f64);
+    access!(phase, Phase);
 
     /// Construct a new instance.
     #[inline]
@@ -32,20 +32,19 @@ impl Environment {
         scat_coeff: f64,
         abs_coeff: f64,
         shift_coeff: f64,
-        asym: f64,
+        phase: Phase,
     ) -> Self {
         debug_assert!(ref_index >= 1.0);
         debug_assert!(scat_coeff > 0.0);
         debug_assert!(abs_coeff >= 0.0);
         debug_assert!(shift_coeff >= 0.0);
-        debug_assert!(asym.abs() <= 1.0);
 
         Self {
             ref_index,
             scat_coeff,
             abs_coeff,
             shift_coeff,
-            asym,
+            phase,
         }
     }
 
@@ -69,4 +68,11 @@ impl Environment {
     pub fn shift_prob(&self) -> f64 {
         self.shift_coeff / self.inter_coeff()
     }
+
+    /// Calculate the shifting probability of an interaction which is not an absorption.
+    #[inline]
+    #[must_use]
+    pub fn inelastic_prob(&self) -> f64 {
+        self.shift_coeff / (self.scat_coeff + self.shift_coeff)
+    }
 }
@@ -0,0 +1,62 @@
+//! Inelastic process implementation.
+
+use crate::{
+    access, clone,
+    phys::{Photon, Spectrum},
+};
+use attr::json;
+use rand::Rng;
+
+/// Inelastic (fluorescence or Raman) process structure.
+/// Shifted photons are re-emitted isotropically.
+#[json]
+pub struct Inelastic {
+    /// Quantum yield.
+    quantum_yield: f64,
+    /// Emission spectrum of the shifted light.
+    /// A single target wavelength, such as a Raman line, is given by a laser spectrum.
+    emission: Spectrum,
+    /// Optional excited state lifetime. [s]
+    lifetime: Option<f64>,
+}
+
+impl Inelastic {
+    clone!(quantum_yield, f64);
+    access!(emission, Spectrum);
+    clone!(lifetime, Option<f64>);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(quantum_yield: f64, emission: Spectrum, lifetime: Option<f64>) -> Self {
+        debug_assert!(quantum_yield > 0.0);
+        debug_assert!(quantum_yield <= 1.0);
+        debug_assert!(lifetime.map_or(true, |t| t > 0.0));
+
+        Self {
+            quantum_yield,
+            emission,
+            lifetime,
+        }
+    }
+
+    /// Sample the target wavelength of a shifted photon.
+    #[inline]
+    #[must_use]
+    pub fn target<R: Rng>(&self, rng: &mut R) -> f64 {
+        self.emission.sample(rng)
+    }
+
+    /// Re-emit a photon isotropically at a sampled target wavelength.
+    /// If a lifetime is given, the photon is delayed by an exponentially distributed emission time.
+    /// Any polarisation state is lost.
+    #[inline]
+    pub fn emit<R: Rng>(&self, rng: &mut R, phot: &mut Photon) {
+        *phot.weight_mut() *= self.quantum_yield;
+        if let Some(lifetime) = self.lifetime {
+            *phot.time_mut() -= lifetime * rng.gen_range(0.0_f64, 1.0).ln();
+        }
+        let wavelength = self.target(rng);
+        phot.reemit(rng, wavelength);
+    }
+}
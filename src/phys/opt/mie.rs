@@ -0,0 +1,86 @@
+//! Mie scattering table implementation.
+
+use crate::{
+    access,
+    err::Error,
+    file::{from_csv, Load},
+    math::{interp, Tabulated},
+};
+use nalgebra::Matrix4;
+use ndarray::{s, Array1};
+use std::path::Path;
+
+/// Mie scattering table structure implementation.
+/// Scattering matrix elements tabulated against the cosine of the scattering angle.
+#[derive(Debug, Clone)]
+pub struct Mie {
+    /// Distribution of the scattering angle cosine, proportional to S11.
+    dist: Tabulated,
+    /// Ratio S12 / S11.
+    m12: Array1<f64>,
+    /// Ratio S33 / S11.
+    m33: Array1<f64>,
+    /// Ratio S34 / S11.
+    m34: Array1<f64>,
+}
+
+impl Mie {
+    access!(dist, Tabulated);
+
+    /// Construct a new instance from the scattering angle cosines and scattering matrix elements.
+    #[inline]
+    #[must_use]
+    pub fn new(
+        cos: Array1<f64>,
+        s11: &Array1<f64>,
+        s12: &Array1<f64>,
+        s33: &Array1<f64>,
+        s34: &Array1<f64>,
+    ) -> Self {
+        debug_assert!(s11.iter().all(|s| *s > 0.0));
+
+        Self {
+            m12: s12 / s11,
+            m33: s33 / s11,
+            m34: s34 / s11,
+            dist: Tabulated::new(cos, s11),
+        }
+    }
+
+    /// Determine the normalised Mueller matrix for the cosine of the scattering angle.
+    #[inline]
+    #[must_use]
+    pub fn mueller(&self, cos_ang: f64) -> Matrix4<f64> {
+        let xs = self.dist.xs();
+        let m12 = interp::linear(xs, &self.m12, cos_ang);
+        let m33 = interp::linear(xs, &self.m33, cos_ang);
+        let m34 = interp::linear(xs, &self.m34, cos_ang);
+
+        Matrix4::new(
+            1.0, m12, 0.0, 0.0, m12, 1.0, 0.0, 0.0, 0.0, 0.0, m33, m34, 0.0, 0.0, -m34, m33,
+        )
+    }
+}
+
+impl Load for Mie {
+    /// Load from a five-column csv file of increasing scattering angle cosines and the S11, S12, S33 and S34 elements.
+    #[inline]
+    fn load(path: &Path) -> Result<Self, Error> {
+        let table = from_csv(path)?;
+        if table.ncols() != 5 {
+            return Err(Error::parse(
+                path,
+                None,
+                "Expected five columns in Mie file.",
+            ));
+        }
+
+        Ok(Self::new(
+            table.slice(s![.., 0]).to_owned(),
+            &table.slice(s![.., 1]).to_owned(),
+            &table.slice(s![.., 2]).to_owned(),
+            &table.slice(s![.., 3]).to_owned(),
+            &table.slice(s![.., 4]).to_owned(),
+        ))
+    }
+}
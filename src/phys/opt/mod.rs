@@ -2,8 +2,13 @@
 
 pub mod crossing;
 pub mod environment;
+pub mod inelastic;
 pub mod laws;
+pub mod mie;
 pub mod optics;
+pub mod phase;
 pub mod spectrum;
 
-pub use self::{crossing::*, environment::*, optics::*, spectrum::*};
+pub use self::{
+    crossing::*, environment::*, inelastic::*, mie::*, optics::*, phase::*, spectrum::*,
+};
@@ -1,6 +1,10 @@
 //! Optics implementation.
 
-use crate::{access, math::Formula, phys::Environment};
+use crate::{
+    access,
+    math::Formula,
+    phys::{Environment, Inelastic, PhaseFunction},
+};
 use attr::json;
 
 /// Optical properties structure.
@@ -14,8 +18,10 @@ pub struct Optics {
     abs_coeff: Formula,
     /// Shift coefficient. [m^-1]
     shift_coeff: Formula,
-    /// Asymmetry parameter.
-    asym: Formula,
+    /// Phase function.
+    phase: PhaseFunction,
+    /// Optional inelastic process, occurring at the shift coefficient rate.
+    inelastic: Option<Inelastic>,
 }
 
 impl Optics {
@@ -23,7 +29,8 @@ impl Optics {
     access!(scat_coeff, Formula);
     access!(abs_coeff, Formula);
     access!(shift_coeff, Formula);
-    access!(asym, Formula);
+    access!(phase, PhaseFunction);
+    access!(inelastic, Option<Inelastic>);
 
     /// Construct a new instance.
     #[inline]
@@ -33,14 +40,16 @@ impl Optics {
         scat_coeff: Formula,
         abs_coeff: Formula,
         shift_coeff: Formula,
-        asym: Formula,
+        phase: PhaseFunction,
+        inelastic: Option<Inelastic>,
     ) -> Self {
         Self {
             ref_index,
             scat_coeff,
             abs_coeff,
             shift_coeff,
-            asym,
+            phase,
+            inelastic,
         }
     }
 
@@ -53,7 +62,7 @@ impl Optics {
             self.scat_coeff.y(w),
             self.abs_coeff.y(w),
             self.shift_coeff.y(w),
-            self.asym.y(w),
+            self.phase.phase(w),
         )
     }
 }
@@ -0,0 +1,170 @@
+//! Phase function implementation.
+
+use crate::{
+    file::Redirect,
+    math::{distribution, Formula, Tabulated},
+    phys::Mie,
+};
+use attr::json;
+use nalgebra::Matrix4;
+use rand::Rng;
+use std::sync::Arc;
+
+/// Phase function enumeration implementation.
+/// Wavelength dependent parameters are resolved into a `Phase` for a given wavelength.
+#[json]
+pub enum PhaseFunction {
+    /// Henyey-Greenstein with a given asymmetry parameter.
+    HenyeyGreenstein(Formula),
+    /// Weighted sum of a forward and backward Henyey-Greenstein function.
+    TwoTermHenyeyGreenstein {
+        /// Forward asymmetry parameter.
+        forward: Formula,
+        /// Backward asymmetry parameter.
+        backward: Formula,
+        /// Fraction of the forward term.
+        frac: Formula,
+    },
+    /// Rayleigh scattering.
+    Rayleigh,
+    /// Isotropic scattering.
+    Isotropic,
+    /// Tabulated (Mie) phase function, loaded from a two-column csv file of increasing scattering angle cosines and relative phase function values.
+    Tabulated(Redirect<Arc<Tabulated>>),
+    /// Mie scattering, loaded from a five-column csv file of increasing scattering angle cosines and the S11, S12, S33 and S34 scattering matrix elements.
+    Mie(Redirect<Arc<Mie>>),
+}
+
+impl PhaseFunction {
+    /// Determine the phase function for a given wavelength.
+    #[inline]
+    #[must_use]
+    pub fn phase(&self, w: f64) -> Phase {
+        match self {
+            Self::HenyeyGreenstein(asym) => Phase::HenyeyGreenstein(asym.y(w)),
+            Self::TwoTermHenyeyGreenstein {
+                forward,
+                backward,
+                frac,
+            } => Phase::TwoTermHenyeyGreenstein {
+                forward: forward.y(w),
+                backward: backward.y(w),
+                frac: frac.y(w),
+            },
+            Self::Rayleigh => Phase::Rayleigh,
+            Self::Isotropic => Phase::Isotropic,
+            Self::Tabulated(table) => Phase::Tabulated(Arc::clone(table.data())),
+            Self::Mie(table) => Phase::Mie(Arc::clone(table.data())),
+        }
+    }
+}
+
+/// Local phase function enumeration implementation.
+#[derive(Debug, Clone)]
+pub enum Phase {
+    /// Henyey-Greenstein with a given asymmetry parameter.
+    HenyeyGreenstein(f64),
+    /// Weighted sum of a forward and backward Henyey-Greenstein function.
+    TwoTermHenyeyGreenstein {
+        /// Forward asymmetry parameter.
+        forward: f64,
+        /// Backward asymmetry parameter.
+        backward: f64,
+        /// Fraction of the forward term.
+        frac: f64,
+    },
+    /// Rayleigh scattering.
+    Rayleigh,
+    /// Isotropic scattering.
+    Isotropic,
+    /// Tabulated distribution of scattering angle cosines.
+    Tabulated(Arc<Tabulated>),
+    /// Tabulated Mie scattering matrix.
+    Mie(Arc<Mie>),
+}
+
+impl Phase {
+    /// Sample a scattering angle [rad].
+    #[inline]
+    #[must_use]
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> f64 {
+        match self {
+            Self::HenyeyGreenstein(asym) => distribution::henyey_greenstein(rng, *asym),
+            Self::TwoTermHenyeyGreenstein {
+                forward,
+                backward,
+                frac,
+            } => {
+                if rng.gen_range(0.0, 1.0) <= *frac {
+                    distribution::henyey_greenstein(rng, *forward)
+                } else {
+                    distribution::henyey_greenstein(rng, *backward)
+                }
+            }
+            Self::Rayleigh => {
+                let q = rng.gen_range(0.0_f64, 1.0).mul_add(4.0, -2.0);
+                let u = (q + q.mul_add(q, 1.0).sqrt()).cbrt();
+                (u - (1.0 / u)).max(-1.0).min(1.0).acos()
+            }
+            Self::Isotropic => rng.gen_range(-1.0_f64, 1.0).acos(),
+            Self::Tabulated(table) => table.gen(rng).max(-1.0).min(1.0).acos(),
+            Self::Mie(table) => table.dist().gen(rng).max(-1.0).min(1.0).acos(),
+        }
+    }
+
+    /// Evaluate the probability density of the cosine of the scattering angle.
+    #[inline]
+    #[must_use]
+    pub fn eval(&self, cos_ang: f64) -> f64 {
+        debug_assert!(cos_ang.abs() <= 1.0);
+
+        match self {
+            Self::HenyeyGreenstein(asym) => henyey_greenstein(*asym, cos_ang),
+            Self::TwoTermHenyeyGreenstein {
+                forward,
+                backward,
+                frac,
+            } => frac.mul_add(
+                henyey_greenstein(*forward, cos_ang),
+                (1.0 - frac) * henyey_greenstein(*backward, cos_ang),
+            ),
+            Self::Rayleigh => 0.375 * cos_ang.mul_add(cos_ang, 1.0),
+            Self::Isotropic => 0.5,
+            Self::Tabulated(table) => table.pdf(cos_ang),
+            Self::Mie(table) => table.dist().pdf(cos_ang),
+        }
+    }
+
+    /// Determine the Mueller matrix, normalised so that the first element is unity, for the cosine of the scattering angle.
+    /// The Stokes vector is referenced to the scattering plane.
+    /// Phase functions without polarisation information leave the Stokes vector unchanged.
+    #[inline]
+    #[must_use]
+    pub fn mueller(&self, cos_ang: f64) -> Matrix4<f64> {
+        match self {
+            Self::Rayleigh => {
+                let c2 = cos_ang * cos_ang;
+                let m12 = (c2 - 1.0) / (c2 + 1.0);
+                let m33 = (2.0 * cos_ang) / (c2 + 1.0);
+
+                Matrix4::new(
+                    1.0, m12, 0.0, 0.0, m12, 1.0, 0.0, 0.0, 0.0, 0.0, m33, 0.0, 0.0, 0.0, 0.0, m33,
+                )
+            }
+            Self::Mie(table) => table.mueller(cos_ang),
+            Self::HenyeyGreenstein(..)
+            | Self::TwoTermHenyeyGreenstein { .. }
+            | Self::Isotropic
+            | Self::Tabulated(..) => Matrix4::identity(),
+        }
+    }
+}
+
+/// Evaluate the Henyey-Greenstein probability density of the cosine of the scattering angle.
+#[inline]
+#[must_use]
+fn henyey_greenstein(asym: f64, cos_ang: f64) -> f64 {
+    let g2 = asym * asym;
+
+    0.5 * (1.0 - g2) / (1.0 + g2 - (2.0 * asym * cos_ang)).powf(1.5)
+}
@@ -1,13 +1,36 @@
 //! Spectrum implementation.
 
+use crate::{
+    file::Redirect,
+    math::{distribution, Tabulated},
+};
 use attr::json;
-use rand::rngs::ThreadRng;
+use physical_constants::{BOLTZMANN_CONSTANT, PLANCK_CONSTANT, SPEED_OF_LIGHT_IN_VACUUM};
+use rand::Rng;
 
 /// Spectrum enumeration implementation.
 #[json]
 pub enum Spectrum {
     /// Single wavelength.
     Laser(f64),
+    /// Uniform range of wavelengths.
+    Uniform {
+        /// Minimum wavelength.
+        min: f64,
+        /// Maximum wavelength.
+        max: f64,
+    },
+    /// Gaussian distribution of wavelengths.
+    Gaussian {
+        /// Average wavelength.
+        mu: f64,
+        /// Standard deviation.
+        sigma: f64,
+    },
+    /// Tabulated spectrum, loaded from a two-column csv file of wavelengths and relative intensities.
+    Tabulated(Redirect<Tabulated>),
+    /// Blackbody at a given temperature [K].
+    Blackbody(f64),
 }
 
 impl Spectrum {
@@ -20,12 +43,53 @@ impl Spectrum {
         Self::Laser { 0: wavelength }
     }
 
+    /// Construct a new uniform spectrum.
+    #[inline]
+    #[must_use]
+    pub fn new_uniform(min: f64, max: f64) -> Self {
+        debug_assert!(min > 0.0);
+        debug_assert!(min < max);
+
+        Self::Uniform { min, max }
+    }
+
+    /// Construct a new gaussian spectrum.
+    #[inline]
+    #[must_use]
+    pub fn new_gaussian(mu: f64, sigma: f64) -> Self {
+        debug_assert!(mu > 0.0);
+        debug_assert!(sigma > 0.0);
+
+        Self::Gaussian { mu, sigma }
+    }
+
+    /// Construct a new blackbody spectrum.
+    #[inline]
+    #[must_use]
+    pub fn new_blackbody(temp: f64) -> Self {
+        debug_assert!(temp > 0.0);
+
+        Self::Blackbody { 0: temp }
+    }
+
     /// Sample the spectrum for a wavelength.
     #[inline]
     #[must_use]
-    pub fn sample(&self, _rng: &mut ThreadRng) -> f64 {
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> f64 {
         match self {
             Self::Laser(w) => *w,
+            Self::Uniform { min, max } => rng.gen_range(*min, *max),
+            Self::Gaussian { mu, sigma } => loop {
+                let w = distribution::gaussian(rng, *mu, *sigma);
+                if w > 0.0 {
+                    break w;
+                }
+            },
+            Self::Tabulated(table) => table.data().gen(rng),
+            Self::Blackbody(temp) => {
+                (PLANCK_CONSTANT * SPEED_OF_LIGHT_IN_VACUUM)
+                    / (distribution::planck(rng) * BOLTZMANN_CONSTANT * temp)
+            }
         }
     }
 }
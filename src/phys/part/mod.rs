@@ -1,5 +1,6 @@
 //! Particle sub-module.
 
 pub mod photon;
+pub mod polarisation;
 
-pub use self::photon::*;
+pub use self::{photon::*, polarisation::*};
@@ -1,6 +1,14 @@
 //! Photon implementation.
 
-use crate::{access, clone, geom::Ray};
+use crate::{
+    access, clone,
+    geom::Ray,
+    phys::{Phase, Polarisation},
+};
+use nalgebra::Vector4;
+use physical_constants::SPEED_OF_LIGHT_IN_VACUUM;
+use rand::Rng;
+use std::f64::consts::PI;
 
 /// Photon structure.
 #[derive(Debug, Clone)]
@@ -13,6 +21,10 @@ pub struct Photon {
     power: f64,
     /// Ray of travel.
     ray: Ray,
+    /// Optional polarisation state.
+    pol: Option<Polarisation>,
+    /// Time of flight since emission [s].
+    time: f64,
 }
 
 impl Photon {
@@ -20,6 +32,8 @@ impl Photon {
     clone!(wavelength, wavelength_mut, f64);
     clone!(power, f64);
     access!(ray, ray_mut, Ray);
+    access!(pol, pol_mut, Option<Polarisation>);
+    clone!(time, time_mut, f64);
 
     /// Construct a new instance.
     #[inline]
@@ -33,6 +47,60 @@ impl Photon {
             wavelength,
             power,
             ray,
+            pol: None,
+            time: 0.0,
+        }
+    }
+
+    /// Construct a new polarised instance.
+    #[inline]
+    #[must_use]
+    pub fn new_polarised(wavelength: f64, power: f64, ray: Ray, pol: Polarisation) -> Self {
+        let mut phot = Self::new(wavelength, power, ray);
+        phot.pol = Some(pol);
+        phot
+    }
+
+    /// Travel a given distance through a medium of the given refractive index.
+    /// The time of flight is advanced by the optical path length over the vacuum speed of light.
+    #[inline]
+    pub fn travel(&mut self, dist: f64, ref_index: f64) {
+        debug_assert!(ref_index > 0.0);
+
+        self.ray.travel(dist);
+        self.time += dist * ref_index / SPEED_OF_LIGHT_IN_VACUUM;
+    }
+
+    /// Scatter with a given pitch and subsequent roll.
+    /// Any polarisation state is updated, and the weight corrected, using the phase function's Mueller matrix.
+    #[inline]
+    pub fn scatter(&mut self, pitch: f64, roll: f64, phase: &Phase) {
+        let old_dir = *self.ray.dir();
+        self.ray.rotate(pitch, roll);
+
+        if let Some(pol) = &mut self.pol {
+            self.weight *= pol.scatter(&old_dir, self.ray.dir(), &phase.mueller(pitch.cos()));
+        }
+    }
+
+    /// Re-emit isotropically at a new wavelength.
+    /// Any polarisation state is lost.
+    #[inline]
+    pub fn reemit<R: Rng>(&mut self, rng: &mut R, wavelength: f64) {
+        debug_assert!(wavelength > 0.0);
+
+        self.wavelength = wavelength;
+        self.ray.rotate(
+            rng.gen_range(-1.0_f64, 1.0).acos(),
+            rng.gen_range(0.0, 2.0 * PI),
+        );
+
+        if let Some(pol) = &mut self.pol {
+            *pol = Polarisation::new(
+                Vector4::new(1.0, 0.0, 0.0, 0.0),
+                self.ray.dir(),
+                pol.ref_dir(),
+            );
         }
     }
 }
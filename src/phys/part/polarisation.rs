@@ -0,0 +1,174 @@
+//! Polarisation implementation.
+
+use crate::{access, phys::Crossing};
+use nalgebra::{Matrix4, Unit, Vector3, Vector4};
+use rand::Rng;
+
+/// Polarisation structure.
+/// Stokes vector (I, Q, U, V), normalised to unit intensity, referenced to a direction perpendicular to travel.
+#[derive(Debug, Clone)]
+pub struct Polarisation {
+    /// Normalised Stokes vector.
+    stokes: Vector4<f64>,
+    /// Reference direction, along which positive Q is polarised.
+    ref_dir: Unit<Vector3<f64>>,
+}
+
+impl Polarisation {
+    access!(stokes, Vector4<f64>);
+    access!(ref_dir, Unit<Vector3<f64>>);
+
+    /// Construct a new instance.
+    /// The reference direction is projected perpendicular to the direction of travel.
+    #[inline]
+    #[must_use]
+    pub fn new(stokes: Vector4<f64>, dir: &Unit<Vector3<f64>>, ref_dir: &Vector3<f64>) -> Self {
+        debug_assert!(stokes.x > 0.0);
+        debug_assert!((stokes.y.powi(2) + stokes.z.powi(2) + stokes.w.powi(2)).sqrt() <= stokes.x);
+
+        Self {
+            stokes: stokes / stokes.x,
+            ref_dir: perpendicular(dir, ref_dir),
+        }
+    }
+
+    /// Rotate the reference frame about the direction of travel to a new reference direction.
+    #[inline]
+    pub fn rotate_frame(&mut self, dir: &Unit<Vector3<f64>>, ref_dir: &Unit<Vector3<f64>>) {
+        let sin = dir.dot(&self.ref_dir.cross(ref_dir));
+        let cos = self.ref_dir.dot(ref_dir);
+        let ang = 2.0 * sin.atan2(cos);
+        let (s2, c2) = ang.sin_cos();
+
+        let q = self.stokes.y;
+        let u = self.stokes.z;
+        self.stokes.y = q.mul_add(c2, u * s2);
+        self.stokes.z = (-q).mul_add(s2, u * c2);
+        self.ref_dir = *ref_dir;
+    }
+
+    /// Apply a Mueller matrix, in the current reference frame, and renormalise.
+    /// Returns the fractional change in intensity.
+    #[inline]
+    #[must_use]
+    pub fn apply(&mut self, mueller: &Matrix4<f64>) -> f64 {
+        let stokes = mueller * self.stokes;
+        debug_assert!(stokes.x > 0.0);
+
+        self.stokes = stokes / stokes.x;
+        stokes.x
+    }
+
+    /// Scatter from an initial to a final direction of travel, using a normalised Mueller matrix referenced to the scattering plane.
+    /// Returns the weight multiplier required when the angles were sampled from the unpolarised phase function.
+    #[inline]
+    #[must_use]
+    pub fn scatter(
+        &mut self,
+        old_dir: &Unit<Vector3<f64>>,
+        new_dir: &Unit<Vector3<f64>>,
+        mueller: &Matrix4<f64>,
+    ) -> f64 {
+        let perp = old_dir.cross(new_dir);
+        if perp.norm() < 1.0e-9 {
+            return self.apply(mueller);
+        }
+        let perp = Unit::new_normalize(perp);
+
+        self.rotate_frame(old_dir, &Unit::new_normalize(perp.cross(old_dir)));
+        let factor = self.apply(mueller);
+        self.ref_dir = Unit::new_normalize(perp.cross(new_dir));
+
+        factor
+    }
+
+    /// Decide whether the photon reflects at an interface crossing, updating the polarisation state.
+    /// Returns true if reflected.
+    #[inline]
+    #[must_use]
+    pub fn cross<R: Rng>(
+        &mut self,
+        rng: &mut R,
+        inc: &Unit<Vector3<f64>>,
+        crossing: &Crossing,
+    ) -> bool {
+        if let Some(perp) = crossing.perp() {
+            self.rotate_frame(inc, &Unit::new_normalize(perp.cross(inc)));
+        }
+
+        if rng.gen_range(0.0, 1.0) <= crossing.pol_ref_prob(&self.stokes) {
+            let _ = self.apply(&crossing.ref_mueller());
+            if let Some(perp) = crossing.perp() {
+                self.ref_dir = Unit::new_normalize(perp.cross(crossing.ref_dir()));
+            }
+            return true;
+        }
+
+        let trans_dir = crossing
+            .trans_dir()
+            .expect("Failed to determine transmission direction.");
+        let _ = self.apply(
+            &crossing
+                .trans_mueller()
+                .expect("Failed to determine transmission Mueller matrix."),
+        );
+        if let Some(perp) = crossing.perp() {
+            self.ref_dir = Unit::new_normalize(perp.cross(&trans_dir));
+        }
+
+        false
+    }
+
+    /// Transmit through an interface crossing, updating the polarisation state.
+    /// The state is referenced to the plane of incidence, about the refracted direction.
+    /// Returns the probability of transmission.
+    #[inline]
+    #[must_use]
+    pub fn transmit(&mut self, inc: &Unit<Vector3<f64>>, crossing: &Crossing) -> f64 {
+        if let Some(perp) = crossing.perp() {
+            self.rotate_frame(inc, &Unit::new_normalize(perp.cross(inc)));
+        }
+
+        let trans_mueller = match crossing.trans_mueller() {
+            Some(trans_mueller) => trans_mueller,
+            None => return 0.0,
+        };
+        let prob = 1.0 - crossing.pol_ref_prob(&self.stokes);
+        let _ = self.apply(&trans_mueller);
+        if let (Some(perp), Some(trans_dir)) = (crossing.perp(), crossing.trans_dir()) {
+            self.ref_dir = Unit::new_normalize(perp.cross(trans_dir));
+        }
+
+        prob
+    }
+
+    /// Determine the fraction of intensity transmitted through an ideal linear polariser with the given axis.
+    #[inline]
+    #[must_use]
+    pub fn linear_frac(&self, dir: &Unit<Vector3<f64>>, axis: &Vector3<f64>) -> f64 {
+        let axis = perpendicular(dir, axis);
+        let sin = dir.dot(&self.ref_dir.cross(&axis));
+        let cos = self.ref_dir.dot(&axis);
+        let (s2, c2) = (2.0 * sin.atan2(cos)).sin_cos();
+
+        0.5 * (1.0 + self.stokes.y.mul_add(c2, self.stokes.z * s2))
+    }
+}
+
+/// Project a direction perpendicular to the direction of travel.
+/// Falls back to a global axis if the direction is parallel to travel.
+#[inline]
+#[must_use]
+fn perpendicular(dir: &Unit<Vector3<f64>>, v: &Vector3<f64>) -> Unit<Vector3<f64>> {
+    let p = v - (dir.as_ref() * dir.dot(v));
+    if p.norm() > 1.0e-9 {
+        return Unit::new_normalize(p);
+    }
+
+    let axis = if dir.x.abs() < 0.9 {
+        Vector3::x()
+    } else {
+        Vector3::y()
+    };
+    Unit::new_normalize(axis - (dir.as_ref() * dir.dot(&axis)))
+}
@@ -0,0 +1,240 @@
+//! Boundary implementation.
+
+use crate::{
+    access,
+    err::Error,
+    geom::Aabb,
+    phys::{Crossing, Photon},
+    sim::boundary::Condition::{self, Escape, Fresnel, Lambertian, Mirror, Periodic},
+};
+use attr::json;
+use nalgebra::{Point3, Unit, Vector3};
+use rand::Rng;
+use std::f64::consts::PI;
+
+/// Names of the domain faces, in index order.
+pub const FACE_NAMES: [&str; 6] = ["x_min", "x_max", "y_min", "y_max", "z_min", "z_max"];
+
+/// Outcome of a photon reaching the domain boundary.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Fate {
+    /// Photon remains within the domain.
+    Inside,
+    /// Photon has left the domain through the face with the given index.
+    Exit(usize),
+    /// Photon has been absorbed by the face with the given index.
+    Absorbed(usize),
+}
+
+/// Boundary structure implementation.
+/// Holds the condition applied at each face of the simulation domain.
+/// Periodic conditions should be given to both faces of an axis.
+#[json]
+#[derive(Clone)]
+pub struct Boundary {
+    /// Conditions at the minimum and maximum x faces.
+    x: [Condition; 2],
+    /// Conditions at the minimum and maximum y faces.
+    y: [Condition; 2],
+    /// Conditions at the minimum and maximum z faces.
+    z: [Condition; 2],
+}
+
+impl Boundary {
+    access!(x, [Condition; 2]);
+    access!(y, [Condition; 2]);
+    access!(z, [Condition; 2]);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(x: [Condition; 2], y: [Condition; 2], z: [Condition; 2]) -> Self {
+        let boundary = Self { x, y, z };
+        debug_assert!(boundary.validate().is_ok());
+
+        boundary
+    }
+
+    /// Construct an instance where photons escape through every face.
+    #[inline]
+    #[must_use]
+    pub fn escape() -> Self {
+        Self::new([Escape; 2], [Escape; 2], [Escape; 2])
+    }
+
+    /// Construct an instance which is periodic in x and y, and escaping in z.
+    /// Suitable for semi-infinite slabs.
+    #[inline]
+    #[must_use]
+    pub fn periodic_xy() -> Self {
+        Self::new([Periodic; 2], [Periodic; 2], [Escape; 2])
+    }
+
+    /// Check that periodic conditions are given to both faces of each axis.
+    #[inline]
+    pub fn validate(&self) -> Result<(), Error> {
+        for (name, [min, max]) in ["x", "y", "z"].iter().zip(&[self.x, self.y, self.z]) {
+            if (*min == Periodic) != (*max == Periodic) {
+                return Err(Error::invalid(
+                    "boundary",
+                    &format!("periodic conditions must be given to both {} faces", name),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the condition of the face with the given index.
+    #[inline]
+    #[must_use]
+    pub fn face(&self, index: usize) -> Condition {
+        [self.x, self.y, self.z][index / 2][index % 2]
+    }
+
+    /// Apply the boundary conditions to a photon which has travelled outside of the bound.
+    /// The refractive index of the medium inside is used by Fresnel faces.
+    /// Photons which remain within the domain are placed back inside of the bound.
+    #[inline]
+    #[must_use]
+    pub fn apply<R: Rng>(
+        &self,
+        rng: &mut R,
+        bound: &Aabb,
+        phot: &mut Photon,
+        ref_index: f64,
+    ) -> Fate {
+        let (mins, maxs) = bound.mins_maxs();
+
+        for axis in 0..3 {
+            let pos = phot.ray().pos()[axis];
+            let (side, wall) = if pos < mins[axis] {
+                (0, mins[axis])
+            } else if pos > maxs[axis] {
+                (1, maxs[axis])
+            } else {
+                continue;
+            };
+            let face = (2 * axis) + side;
+
+            let mut inward = Vector3::zeros();
+            inward[axis] = if side == 0 { 1.0 } else { -1.0 };
+            let inward = Unit::new_unchecked(inward);
+
+            match self.face(face) {
+                Escape => return Fate::Exit(face),
+                Periodic => {
+                    let width = maxs[axis] - mins[axis];
+                    let p = &mut phot.ray_mut().pos_mut()[axis];
+                    while *p < mins[axis] {
+                        *p += width;
+                    }
+                    while *p > maxs[axis] {
+                        *p -= width;
+                    }
+                }
+                Mirror => {
+                    let mut dir = phot.ray().dir().into_inner();
+                    dir[axis] = -dir[axis];
+                    *phot.ray_mut().dir_mut() = Unit::new_normalize(dir);
+                    reflect_pos(phot, axis, wall);
+                }
+                Lambertian(albedo) => {
+                    *phot.weight_mut() *= albedo;
+                    if phot.weight() <= 0.0 {
+                        return Fate::Absorbed(face);
+                    }
+
+                    *phot.ray_mut().dir_mut() = lambertian_dir(rng, axis, &inward);
+                    *phot.pol_mut() = None;
+                    reflect_pos(phot, axis, wall);
+                }
+                Fresnel(n_out) => {
+                    let crossing = Crossing::new(phot.ray().dir(), &inward, ref_index, n_out);
+
+                    let inc = *phot.ray().dir();
+                    let reflect = if let Some(pol) = phot.pol_mut() {
+                        pol.cross(rng, &inc, &crossing)
+                    } else {
+                        rng.gen_range(0.0, 1.0) <= crossing.ref_prob()
+                    };
+
+                    if !reflect {
+                        *phot.ray_mut().dir_mut() = crossing
+                            .trans_dir()
+                            .expect("Failed to determine transmission direction.");
+                        return Fate::Exit(face);
+                    }
+
+                    *phot.ray_mut().dir_mut() = *crossing.ref_dir();
+                    reflect_pos(phot, axis, wall);
+                }
+            }
+        }
+
+        Fate::Inside
+    }
+
+    /// Determine the faces through which a virtual photon, which has travelled outside of the bound, leaves the domain.
+    /// Returns the inward normal and outside refractive index of each Fresnel face crossed, in axis order,
+    /// or None if the photon can not leave through the face reached.
+    #[inline]
+    #[must_use]
+    pub fn exit_faces(
+        &self,
+        bound: &Aabb,
+        pos: &Point3<f64>,
+    ) -> Option<Vec<(Unit<Vector3<f64>>, f64)>> {
+        let (mins, maxs) = bound.mins_maxs();
+
+        let mut faces = Vec::new();
+        for axis in 0..3 {
+            let side = if pos[axis] < mins[axis] {
+                0
+            } else if pos[axis] > maxs[axis] {
+                1
+            } else {
+                continue;
+            };
+
+            match self.face((2 * axis) + side) {
+                Escape => {}
+                Fresnel(n_out) => {
+                    let mut inward = Vector3::zeros();
+                    inward[axis] = if side == 0 { 1.0 } else { -1.0 };
+                    faces.push((Unit::new_unchecked(inward), n_out));
+                }
+                Periodic | Mirror | Lambertian(_) => return None,
+            }
+        }
+
+        Some(faces)
+    }
+}
+
+/// Reflect a photon's position about a face.
+#[inline]
+fn reflect_pos(phot: &mut Photon, axis: usize, wall: f64) {
+    let p = &mut phot.ray_mut().pos_mut()[axis];
+    *p = (2.0 * wall) - *p;
+}
+
+/// Sample a cosine-weighted direction about the inward normal of a face.
+#[inline]
+#[must_use]
+fn lambertian_dir<R: Rng>(
+    rng: &mut R,
+    axis: usize,
+    inward: &Unit<Vector3<f64>>,
+) -> Unit<Vector3<f64>> {
+    let cos_theta = rng.gen_range(0.0_f64, 1.0).sqrt();
+    let sin_theta = (1.0 - cos_theta.powi(2)).sqrt();
+    let phi = rng.gen_range(0.0, 2.0 * PI);
+
+    let mut dir = Vector3::zeros();
+    dir[axis] = inward[axis] * cos_theta;
+    dir[(axis + 1) % 3] = sin_theta * phi.cos();
+    dir[(axis + 2) % 3] = sin_theta * phi.sin();
+
+    Unit::new_normalize(dir)
+}
@@ -0,0 +1,21 @@
+//! Boundary condition enumeration.
+
+use attr::json;
+
+/// Treatment of photons reaching a face of the simulation domain.
+#[json]
+#[derive(Clone, Copy, PartialEq)]
+pub enum Condition {
+    /// Photons leave the domain.
+    Escape,
+    /// Photons re-enter through the opposite face.
+    Periodic,
+    /// Photons are specularly reflected.
+    Mirror,
+    /// Photons are diffusely reflected, and their weight is scaled by the given albedo.
+    Lambertian(f64),
+    /// Photons are reflected or transmitted according to the Fresnel equations,
+    /// with the given refractive index beyond the face.
+    /// If the index matches that of the medium inside, all photons are transmitted.
+    Fresnel(f64),
+}
@@ -0,0 +1,100 @@
+//! Exit-Map structure.
+
+use crate::{access, err::Error, file::Save, geom::Aabb, sim::boundary::FACE_NAMES};
+use nalgebra::Point3;
+use ndarray::Array2;
+use serde::{Deserialize, Serialize};
+use std::{
+    ops::{AddAssign, MulAssign},
+    path::Path,
+};
+
+/// Exit-Map structure implementation.
+/// Accumulates the energy leaving the domain through each face, binned over the face at the grid resolution.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExitMap {
+    /// Exiting energy, in face index order.
+    faces: Vec<Array2<f64>>,
+}
+
+impl ExitMap {
+    access!(faces, Vec<Array2<f64>>);
+
+    /// Construct a new instance for a grid of the given resolution.
+    #[inline]
+    #[must_use]
+    pub fn new(res: [usize; 3]) -> Self {
+        Self {
+            faces: (0..FACE_NAMES.len())
+                .map(|face| {
+                    let [a, b] = plane_axes(face / 2);
+                    Array2::zeros([res[a], res[b]])
+                })
+                .collect(),
+        }
+    }
+
+    /// Record energy leaving through a face at the given position.
+    /// Positions are clamped onto the face.
+    #[inline]
+    pub fn record(&mut self, bound: &Aabb, face: usize, pos: &Point3<f64>, energy: f64) {
+        let (mins, maxs) = bound.mins_maxs();
+        let map = &mut self.faces[face];
+        let shape = [map.nrows(), map.ncols()];
+
+        let mut index = [0; 2];
+        for ((i, axis), n) in index.iter_mut().zip(&plane_axes(face / 2)).zip(&shape) {
+            let x = (pos[*axis] - mins[*axis]) / (maxs[*axis] - mins[*axis]);
+            *i = ((x * *n as f64).max(0.0) as usize).min(n - 1);
+        }
+
+        map[index] += energy;
+    }
+
+    /// Calculate the total energy leaving through each face.
+    #[inline]
+    #[must_use]
+    pub fn totals(&self) -> Vec<f64> {
+        self.faces.iter().map(Array2::sum).collect()
+    }
+}
+
+/// Get the axes spanning the faces perpendicular to the given axis.
+#[inline]
+#[must_use]
+fn plane_axes(axis: usize) -> [usize; 2] {
+    match axis {
+        0 => [1, 2],
+        1 => [0, 2],
+        _ => [0, 1],
+    }
+}
+
+impl AddAssign<&Self> for ExitMap {
+    #[inline]
+    fn add_assign(&mut self, rhs: &Self) {
+        for (map, rhs) in self.faces.iter_mut().zip(&rhs.faces) {
+            *map += rhs;
+        }
+    }
+}
+
+impl MulAssign<f64> for ExitMap {
+    #[inline]
+    fn mul_assign(&mut self, rhs: f64) {
+        for map in &mut self.faces {
+            *map *= rhs;
+        }
+    }
+}
+
+impl Save for ExitMap {
+    #[inline]
+    fn save(&self, path: &Path) -> Result<(), Error> {
+        for (name, map) in FACE_NAMES.iter().zip(&self.faces) {
+            map.save(&path.join(format!("exit_{}.nc", name)))?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,7 @@
+//! Photon boundary condition sub-module.
+
+pub mod boundary;
+pub mod condition;
+pub mod exit_map;
+
+pub use self::{boundary::*, condition::*, exit_map::*};
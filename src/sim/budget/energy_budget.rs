@@ -0,0 +1,176 @@
+//! Energy-Budget structure.
+
+use crate::{
+    access, clone,
+    err::Error,
+    file::{as_json, Save},
+    sim::boundary::FACE_NAMES,
+};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::{
+    ops::{AddAssign, MulAssign},
+    path::Path,
+};
+
+/// Relative residual above which the budget is considered not to balance.
+pub const BUDGET_TOL: f64 = 1.0e-6;
+
+/// Energy-Budget structure implementation.
+/// Ledger of where the launched photon energy goes during a run.
+/// Detected and shifted energies are tallies of energy already counted elsewhere, and do not enter the balance.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EnergyBudget {
+    /// Energy launched by the light source [J].
+    launched: f64,
+    /// Energy absorbed within the grid [J].
+    absorbed: f64,
+    /// Energy absorbed by reflecting faces of the boundary [J].
+    boundary_absorbed: f64,
+    /// Energy leaving through each face of the boundary, in face index order [J].
+    escaped: [f64; 6],
+    /// Energy of escaping photons collected by the detectors [J].
+    detected: f64,
+    /// Energy of photons which have been inelastically shifted [J].
+    shifted: f64,
+    /// Energy gained by photons surviving roulette, or through splitting [J].
+    roulette_gained: f64,
+    /// Energy lost by photons killed by roulette, or through splitting [J].
+    roulette_lost: f64,
+    /// Energy of photons culled for exceeding the loop limit [J].
+    culled: f64,
+    /// Net energy introduced by biased sampling [J].
+    biased: f64,
+    /// Net energy introduced by the polarised weight corrections of scattering and Fresnel crossings [J].
+    polarised: f64,
+}
+
+impl EnergyBudget {
+    clone!(launched, launched_mut, f64);
+    clone!(absorbed, absorbed_mut, f64);
+    clone!(boundary_absorbed, boundary_absorbed_mut, f64);
+    access!(escaped, escaped_mut, [f64; 6]);
+    clone!(detected, detected_mut, f64);
+    clone!(shifted, shifted_mut, f64);
+    clone!(roulette_gained, roulette_gained_mut, f64);
+    clone!(roulette_lost, roulette_lost_mut, f64);
+    clone!(culled, culled_mut, f64);
+    clone!(biased, biased_mut, f64);
+    clone!(polarised, polarised_mut, f64);
+
+    /// Construct a new empty instance.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Calculate the total energy leaving through the boundary.
+    #[inline]
+    #[must_use]
+    pub fn total_escaped(&self) -> f64 {
+        self.escaped.iter().sum()
+    }
+
+    /// Calculate the energy unaccounted for.
+    #[inline]
+    #[must_use]
+    pub fn residual(&self) -> f64 {
+        (self.launched + self.roulette_gained + self.biased + self.polarised)
+            - (self.absorbed
+                + self.boundary_absorbed
+                + self.total_escaped()
+                + self.roulette_lost
+                + self.culled)
+    }
+
+    /// Calculate the energy unaccounted for, relative to the energy launched.
+    #[inline]
+    #[must_use]
+    pub fn rel_residual(&self) -> f64 {
+        if self.launched > 0.0 {
+            self.residual() / self.launched
+        } else {
+            self.residual()
+        }
+    }
+
+    /// Determine if the budget balances to within the tolerance.
+    #[inline]
+    #[must_use]
+    pub fn balances(&self) -> bool {
+        self.rel_residual().abs() <= BUDGET_TOL
+    }
+
+    /// Log the budget, warning if it does not balance.
+    #[inline]
+    pub fn report(&self) {
+        info!("{: <31}: {:e}", "launched", self.launched);
+        info!("{: <31}: {:e}", "absorbed", self.absorbed);
+        info!("{: <31}: {:e}", "boundary absorbed", self.boundary_absorbed);
+        for (name, escaped) in FACE_NAMES.iter().zip(&self.escaped) {
+            info!("{: <31}: {:e}", format!("escaped {}", name), escaped);
+        }
+        info!("{: <31}: {:e}", "detected", self.detected);
+        info!("{: <31}: {:e}", "shifted", self.shifted);
+        info!("{: <31}: {:e}", "roulette gained", self.roulette_gained);
+        info!("{: <31}: {:e}", "roulette lost", self.roulette_lost);
+        info!("{: <31}: {:e}", "culled", self.culled);
+        info!("{: <31}: {:e}", "biased", self.biased);
+        info!("{: <31}: {:e}", "polarised", self.polarised);
+        info!("{: <31}: {:e}", "residual", self.residual());
+
+        if !self.balances() {
+            warn!(
+                "Energy budget does not balance: relative residual of {:e} exceeds {:e}.",
+                self.rel_residual(),
+                BUDGET_TOL
+            );
+        }
+    }
+}
+
+impl AddAssign<&Self> for EnergyBudget {
+    #[inline]
+    fn add_assign(&mut self, rhs: &Self) {
+        self.launched += rhs.launched;
+        self.absorbed += rhs.absorbed;
+        self.boundary_absorbed += rhs.boundary_absorbed;
+        for (escaped, rhs) in self.escaped.iter_mut().zip(&rhs.escaped) {
+            *escaped += rhs;
+        }
+        self.detected += rhs.detected;
+        self.shifted += rhs.shifted;
+        self.roulette_gained += rhs.roulette_gained;
+        self.roulette_lost += rhs.roulette_lost;
+        self.culled += rhs.culled;
+        self.biased += rhs.biased;
+        self.polarised += rhs.polarised;
+    }
+}
+
+impl MulAssign<f64> for EnergyBudget {
+    #[inline]
+    fn mul_assign(&mut self, rhs: f64) {
+        self.launched *= rhs;
+        self.absorbed *= rhs;
+        self.boundary_absorbed *= rhs;
+        for escaped in &mut self.escaped {
+            *escaped *= rhs;
+        }
+        self.detected *= rhs;
+        self.shifted *= rhs;
+        self.roulette_gained *= rhs;
+        self.roulette_lost *= rhs;
+        self.culled *= rhs;
+        self.biased *= rhs;
+        self.polarised *= rhs;
+    }
+}
+
+impl Save for EnergyBudget {
+    #[inline]
+    fn save(&self, path: &Path) -> Result<(), Error> {
+        as_json(self, path)
+    }
+}
@@ -0,0 +1,5 @@
+//! Energy accounting sub-module.
+
+pub mod energy_budget;
+
+pub use self::energy_budget::*;
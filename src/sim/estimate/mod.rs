@@ -0,0 +1,6 @@
+//! Next-event estimation sub-module.
+
+pub mod next_event;
+pub mod traverse;
+
+pub use self::{next_event::*, traverse::*};
@@ -0,0 +1,198 @@
+//! Next-event estimation functions.
+
+use crate::{
+    ord::{DetKey, MatSet},
+    phys::{Crossing, Environment, Phase, Photon},
+    sim::estimate::Traverse,
+    world::{Detector, Instrument},
+};
+use nalgebra::{Unit, Vector3};
+use rand::Rng;
+use std::{collections::BTreeMap, f64::consts::PI};
+
+/// Surface crossed by a virtual photon, as its normal, and the refractive indices before and after the crossing.
+pub type Refraction = (Unit<Vector3<f64>>, f64, f64);
+
+/// Trace a virtual photon in a straight line, without scattering, until it has travelled the given distance or leaves the domain.
+/// The photon is attenuated by the extinction of each material passed through.
+/// Each interface and boundary face crossed is recorded, so that the refraction at it can be accounted for by the caller.
+/// Returns the fraction of the photon's weight which is not attenuated, the photon where the trace stopped, and the surfaces crossed in order,
+/// or None if it can not arrive.
+#[inline]
+#[must_use]
+pub fn transmit<G: Traverse>(
+    grid: &G,
+    mats: &MatSet,
+    mut phot: Photon,
+    mut env: Environment,
+    max_dist: f64,
+) -> Option<(f64, Photon, Vec<Refraction>)> {
+    let bump_dist = grid.bump_dist();
+    let mut trans = 1.0;
+    let mut remaining = max_dist;
+    let mut crossings = Vec::new();
+
+    while grid.bound().contains(phot.ray().pos()) {
+        let (cell_dist, inter) = grid.step(phot.ray());
+
+        match inter {
+            Some((dist, inside, norm, inter)) if dist < cell_dist && dist < remaining => {
+                let next_mat = if inside {
+                    inter.out_mat()
+                } else {
+                    inter.in_mat()
+                };
+                let next_env = mats.get(next_mat).optics().env(phot.wavelength());
+
+                trans *= (-env.inter_coeff() * (dist + bump_dist)).exp();
+                phot.travel(dist + bump_dist, env.ref_index());
+                remaining -= dist + bump_dist;
+
+                crossings.push((norm, env.ref_index(), next_env.ref_index()));
+                env = next_env;
+            }
+            _ => {
+                let step = (cell_dist + bump_dist).min(remaining);
+                trans *= (-env.inter_coeff() * step).exp();
+                phot.travel(step, env.ref_index());
+                remaining -= step;
+            }
+        }
+
+        if trans <= 0.0 {
+            return None;
+        }
+        if remaining <= 0.0 {
+            return Some((trans, phot, crossings));
+        }
+    }
+
+    let mut n_curr = env.ref_index();
+    for (inward, n_out) in grid.boundary().exit_faces(grid.bound(), phot.ray().pos())? {
+        crossings.push((inward, n_curr, n_out));
+        n_curr = n_out;
+    }
+
+    Some((trans, phot, crossings))
+}
+
+/// Determine the directions in which a photon must meet each of a sequence of surfaces, so that it leaves the last travelling in the given direction.
+/// Also returns the ratio of the solid angle about the first of these directions to the solid angle it is refracted into about the given direction.
+/// Returns None if no direction is refracted into the given direction.
+#[inline]
+#[must_use]
+pub fn unrefract(
+    dir: &Unit<Vector3<f64>>,
+    crossings: &[Refraction],
+) -> Option<(Vec<Unit<Vector3<f64>>>, f64)> {
+    let mut out = *dir;
+    let mut spread = 1.0;
+    let mut incs = Vec::with_capacity(crossings.len());
+
+    for (norm, n_curr, n_next) in crossings.iter().rev() {
+        let back = Crossing::new(&-out, norm, *n_next, *n_curr)
+            .trans_dir()
+            .clone()?;
+        let inc = -back;
+
+        let cos_inc = inc.dot(norm).abs();
+        if cos_inc <= 0.0 {
+            return None;
+        }
+        spread *= (n_next.powi(2) * out.dot(norm).abs()) / (n_curr.powi(2) * cos_inc);
+
+        incs.push(inc);
+        out = inc;
+    }
+    incs.reverse();
+
+    Some((incs, spread))
+}
+
+/// Make a next-event estimate of a detector's signal from a scattering event.
+/// A point on the detector's face is sampled, and a virtual photon is scattered towards it.
+/// The photon is scattered into the direction which, after refraction at each surface on the way, arrives travelling towards the point.
+/// Its weight is the phase function density in that direction, over the solid angle subtended by the face,
+/// scaled by the change in solid angle upon refraction, and by the transmission probability at each surface.
+/// The photon is attenuated along the straight path to the point, which is accurate while the event lies close to the surfaces crossed, relative to its distance from the detector.
+/// The trace stops just short of the point, so detectors within the domain are not attenuated past their face.
+/// If no phase function is given, the event is isotropic.
+/// Returns the power offered to the detector, if it was detected.
+#[inline]
+pub fn next_event<G: Traverse, D: Detector, R: Rng>(
+    rng: &mut R,
+    grid: &G,
+    mats: &MatSet,
+    phot: &Photon,
+    env: &Environment,
+    phase: Option<&Phase>,
+    det: &mut D,
+) -> Option<f64> {
+    let (point, norm, area) = det.sample_face(rng);
+    let offset = point - phot.ray().pos();
+    let dist = offset.norm();
+    let dir = Unit::new_normalize(offset);
+
+    let cos_face = -dir.dot(&norm);
+    if cos_face <= 0.0 {
+        return None;
+    }
+    let solid_angle = area * cos_face / dist.powi(2);
+
+    let mut virt = phot.clone();
+    *virt.ray_mut().dir_mut() = dir;
+    let (mut trans, mut virt, crossings) = transmit(
+        grid,
+        mats,
+        virt,
+        env.clone(),
+        (dist - grid.bump_dist()).max(0.0),
+    )?;
+    let (incs, spread) = unrefract(&dir, &crossings)?;
+    let emit_dir = incs.first().unwrap_or(&dir);
+
+    let density = match phase {
+        Some(phase) => {
+            let cos_ang = phot.ray().dir().dot(emit_dir).max(-1.0).min(1.0);
+            if let Some(pol) = virt.pol_mut() {
+                *virt.weight_mut() *=
+                    pol.scatter(phot.ray().dir(), emit_dir, &phase.mueller(cos_ang));
+            }
+            phase.eval(cos_ang) / (2.0 * PI)
+        }
+        None => 1.0 / (4.0 * PI),
+    };
+
+    for (inc, (norm, n_curr, n_next)) in incs.iter().zip(&crossings) {
+        let crossing = Crossing::new(inc, norm, *n_curr, *n_next);
+        trans *= match virt.pol_mut() {
+            Some(pol) => pol.transmit(inc, &crossing),
+            None => crossing.trans_prob(),
+        };
+    }
+
+    *virt.weight_mut() *= density * solid_angle * spread * trans;
+    if virt.weight() <= 0.0 || !det.detect(&virt) {
+        return None;
+    }
+
+    Some(virt.weight() * virt.power())
+}
+
+/// Make a next-event estimate of each detector's signal from a scattering event.
+/// If no phase function is given, the event is isotropic.
+/// Returns the total power detected.
+#[inline]
+pub fn estimate<G: Traverse, R: Rng>(
+    rng: &mut R,
+    grid: &G,
+    mats: &MatSet,
+    phot: &Photon,
+    env: &Environment,
+    phase: Option<&Phase>,
+    dets: &mut BTreeMap<DetKey, Instrument>,
+) -> f64 {
+    dets.values_mut()
+        .filter_map(|det| next_event(rng, grid, mats, phot, env, phase, det))
+        .sum()
+}
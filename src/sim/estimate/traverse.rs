@@ -0,0 +1,47 @@
+//! Traverse trait.
+
+use crate::{
+    geom::{Aabb, Ray},
+    sim::boundary::Boundary,
+    world::Interface,
+};
+use nalgebra::{Unit, Vector3};
+
+/// Types implementing this trait are domains which virtual photons may be traced through.
+pub trait Traverse {
+    /// Reference the bounding box of the domain.
+    fn bound(&self) -> &Aabb;
+
+    /// Reference the conditions applied at the faces of the domain.
+    fn boundary(&self) -> &Boundary;
+
+    /// Determine the distance used to bump photons through boundaries.
+    fn bump_dist(&self) -> f64;
+
+    /// Determine the distance to the edge of the cell containing the ray's position,
+    /// and the nearest interface crossing within it, if the crossing is made from inside the interface, and the normal at the intersection point.
+    fn step(&self, ray: &Ray) -> (f64, Option<(f64, bool, Unit<Vector3<f64>>, &Interface)>);
+
+    /// Determine the distance along a ray to the first interface crossing, or to the edge of the domain.
+    /// This is the distance over which the ray passes through a single material.
+    #[inline]
+    fn reach(&self, ray: &Ray) -> f64 {
+        let bump_dist = self.bump_dist();
+        let mut ray = ray.clone();
+        let mut total = 0.0;
+
+        while self.bound().contains(ray.pos()) {
+            let (cell_dist, inter) = self.step(&ray);
+            if let Some((dist, ..)) = inter {
+                if dist < cell_dist {
+                    return total + dist;
+                }
+            }
+
+            total += cell_dist + bump_dist;
+            ray.travel(cell_dist + bump_dist);
+        }
+
+        total
+    }
+}
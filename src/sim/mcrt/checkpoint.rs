@@ -0,0 +1,100 @@
+//! Checkpoint structure.
+
+use crate::{
+    access, clone,
+    err::Error,
+    sim::{mcrt::LightMap, variance::Variance},
+};
+use attr::json;
+
+/// Checkpoint structure implementation.
+/// Stores the state of a partially complete simulation so it may be resumed.
+/// The random state is fully determined by the seed and completed count, as each block of photons draws from its own stream.
+#[json]
+pub struct Checkpoint {
+    /// Random seed of the run.
+    seed: u64,
+    /// Total number of photons to simulate.
+    num_phot: u64,
+    /// Number of photons completed.
+    complete: u64,
+    /// Light-map accumulated from the completed photons.
+    light_map: LightMap,
+    /// Variance reduction settings of the run.
+    variance: Variance,
+}
+
+impl Checkpoint {
+    clone!(seed, u64);
+    clone!(num_phot, u64);
+    clone!(complete, u64);
+    access!(light_map, LightMap);
+    access!(variance, Variance);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(
+        seed: u64,
+        num_phot: u64,
+        complete: u64,
+        light_map: LightMap,
+        variance: Variance,
+    ) -> Self {
+        debug_assert!(num_phot > 0);
+        debug_assert!(complete <= num_phot);
+
+        Self {
+            seed,
+            num_phot,
+            complete,
+            light_map,
+            variance,
+        }
+    }
+
+    /// Check that the checkpoint was written by a run with the given settings, so that it may be resumed.
+    /// The light-map is compared against a fresh one constructed from the current parameters.
+    #[inline]
+    pub fn validate(
+        &self,
+        seed: Option<u64>,
+        num_phot: u64,
+        light_map: &LightMap,
+        variance: &Variance,
+    ) -> Result<(), Error> {
+        if seed.map_or(false, |seed| seed != self.seed) {
+            return Err(Error::invalid(
+                "checkpoint",
+                "random seed does not match the parameters",
+            ));
+        }
+        if num_phot != self.num_phot {
+            return Err(Error::invalid(
+                "checkpoint",
+                "photon count does not match the parameters",
+            ));
+        }
+        if !self.light_map.matches(light_map) {
+            return Err(Error::invalid(
+                "checkpoint",
+                "grid resolution, binners or detectors do not match the parameters",
+            ));
+        }
+        if variance != &self.variance {
+            return Err(Error::invalid(
+                "checkpoint",
+                "variance reduction settings do not match the parameters",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Retrieve the accumulated light-map.
+    #[inline]
+    #[must_use]
+    pub fn into_light_map(self) -> LightMap {
+        self.light_map
+    }
+}
@@ -2,12 +2,18 @@
 
 use crate::{
     access,
-    geom::{Aabb, Ray},
+    geom::{Aabb, Ray, Trace},
     list::Cartesian::{X, Y, Z},
     math::{indexer, list},
-    ord::{sort, InterSet, MatKey, MatSet, Set, SurfSet},
-    sim::mcrt::Cell,
+    ord::{sort, MatKey, MatSet, Set},
+    sim::{
+        boundary::Boundary,
+        estimate::Traverse,
+        mcrt::{Cell, Refine},
+        vox::Voxelisation,
+    },
     util::ParProgressBar,
+    world::Interface,
 };
 use nalgebra::{Point3, Unit, Vector3};
 use ndarray::Array3;
@@ -18,9 +24,6 @@ use std::{
     sync::{Arc, Mutex},
 };
 
-/// Material detection rays must be aimed at a triangle with at least this deviation from the triangle's plane.
-const HIT_ANGLE_THRESHOLD: f64 = 1.0e-3;
-
 /// Scaling to bump photons through boundaries within the grid.
 const BUMP_SCALE: f64 = 1.0e-3;
 
@@ -28,26 +31,31 @@ const BUMP_SCALE: f64 = 1.0e-3;
 pub struct Grid<'a> {
     /// Boundary.
     bound: Aabb,
+    /// Conditions applied to photons reaching the boundary.
+    boundary: Boundary,
     /// Cells.
     cells: Array3<Cell<'a>>,
 }
 
 impl<'a> Grid<'a> {
     access!(bound, Aabb);
+    access!(boundary, Boundary);
     access!(cells, Array3<Cell<'a>>);
 
-    /// Construct a new instance.
+    /// Construct a new instance from a voxelisation.
+    /// Cells are optionally refined about interfaces.
     #[inline]
     #[must_use]
-    pub fn new(res: [usize; 3], bound: Aabb, inters: &'a InterSet, surfs: &'a SurfSet) -> Self {
+    pub fn new(
+        vox: &Voxelisation<'a>,
+        refine: Option<&Refine>,
+        boundary: Boundary,
+        mats: &MatSet,
+    ) -> Self {
+        let res = vox.res();
         let total_cells = res.get(X as usize).expect("Missing resolution index.")
-            * res.get(1).expect("Missing resolution index.")
-            * res.get(2).expect("Missing resolution index.");
-
-        let mut cell_size = bound.widths();
-        for (w, n) in cell_size.iter_mut().zip(&res) {
-            *w /= *n as f64;
-        }
+            * res.get(Y as usize).expect("Missing resolution index.")
+            * res.get(Z as usize).expect("Missing resolution index.");
 
         let pb = ParProgressBar::new("Building", total_cells as u64);
         let pb = Arc::new(Mutex::new(pb));
@@ -59,11 +67,9 @@ impl<'a> Grid<'a> {
                 Self::init_cell_blocks(
                     &Arc::clone(&pb),
                     ((total_cells / num_cpus::get()) / 100).max(1) as u64,
-                    &res,
-                    &bound,
-                    inters,
-                    surfs,
-                    &cell_size,
+                    vox,
+                    refine,
+                    mats,
                 )
             })
             .collect();
@@ -74,7 +80,8 @@ impl<'a> Grid<'a> {
         let cells = sort::stitch(cell_blocks);
 
         Self {
-            bound,
+            bound: vox.bound().clone(),
+            boundary,
             cells: Array3::from_shape_vec(res, cells).expect("Unable to construct cell array."),
         }
     }
@@ -85,32 +92,13 @@ impl<'a> Grid<'a> {
     fn init_cell_blocks(
         pb: &Arc<Mutex<ParProgressBar>>,
         block_size: u64,
-        res: &[usize; 3],
-        bound: &Aabb,
-        inters: &'a InterSet,
-        surfs: &'a SurfSet,
-        cell_size: &Vector3<f64>,
+        vox: &Voxelisation<'a>,
+        refine: Option<&Refine>,
+        mats: &MatSet,
     ) -> Vec<(usize, Vec<Cell<'a>>)> {
+        let res = vox.res();
         let mut cell_blocks = Vec::new();
 
-        let gen_mat_ray = |p: &Point3<f64>| -> Ray {
-            for inter in inters.map().values() {
-                let surf = surfs.get(inter.surf());
-                for tri in surf.tris() {
-                    let tc = tri.tri().centre();
-
-                    if bound.contains(&tc) {
-                        let dir = Unit::new_normalize(tc - p);
-                        if dir.dot(tri.tri().plane_norm()).abs() >= HIT_ANGLE_THRESHOLD {
-                            return Ray::new(*p, dir);
-                        }
-                    }
-                }
-            }
-
-            panic!("Unable to determine suitable material tracing ray.");
-        };
-
         while let Some((start, end)) = {
             let mut pb = pb.lock().expect("Could not lock progress bar.");
             let b = pb.block(block_size);
@@ -119,23 +107,18 @@ impl<'a> Grid<'a> {
         } {
             let mut cells: Vec<_> = Vec::with_capacity((end - start) as usize);
             for n in start..end {
-                let index = indexer::three_dim(n as usize, *res);
-
-                let x = cell_size.x * index[0] as f64;
-                let y = cell_size.y * index[1] as f64;
-                let z = cell_size.z * index[2] as f64;
-
-                let mins = bound.mins() + Vector3::new(x, y, z);
-                let maxs = mins + cell_size;
-                let cell_bound = Aabb::new(mins, maxs);
+                let index = indexer::three_dim(n as usize, res);
 
-                let p = cell_bound.centre();
-
-                let mat = inters
-                    .observe_mat(surfs, bound, &gen_mat_ray(&p))
-                    .expect("Unable to observe material.");
+                let mut cell = Cell::new(
+                    vox.cell_bound(index),
+                    vox.mats()[index],
+                    vox.inter_tris()[index].clone(),
+                );
+                if let Some(refine) = refine {
+                    cell.refine(refine, mats, 0, &|p| vox.observe_mat(p));
+                }
 
-                cells.push(Cell::new(Aabb::new(mins, maxs), mat, inters, surfs));
+                cells.push(cell);
             }
             cell_blocks.push((start as usize, cells));
         }
@@ -164,14 +147,14 @@ impl<'a> Grid<'a> {
     }
 
     /// Create a set of material maps.
+    /// Refined cells are resampled to the volume fraction of each material.
     #[inline]
     #[must_use]
     pub fn mat_maps(&self, mats: &MatSet) -> Set<MatKey, Array3<f64>> {
         let mut set = BTreeMap::new();
 
-        let keys = self.mat_keys();
         for key in mats.map().keys() {
-            set.insert(key.clone(), keys.map(|k| if k == &key { 1.0 } else { 0.0 }));
+            set.insert(key.clone(), self.cells.map(|cell| cell.mat_frac(key)));
         }
 
         Set::new(set)
@@ -184,6 +167,44 @@ impl<'a> Grid<'a> {
         self.cells().map(|c| c.inter_tris().len() as f64)
     }
 
+    /// Determine the number of intersecting triangles in each cell.
+    #[inline]
+    #[must_use]
+    pub fn tris(&self) -> Array3<f64> {
+        self.cells().map(|c| c.num_tris() as f64)
+    }
+
+    /// Determine the number of acceleration tree leaves in each cell.
+    /// Cells searched linearly hold zero.
+    #[inline]
+    #[must_use]
+    pub fn accel(&self) -> Array3<f64> {
+        self.cells().map(|c| {
+            c.tree()
+                .as_ref()
+                .map_or(0.0, |tree| tree.num_leaves() as f64)
+        })
+    }
+
+    /// Determine the number of cells using an acceleration tree, and the maximum depth of any tree.
+    #[inline]
+    #[must_use]
+    pub fn accel_stats(&self) -> (usize, u32) {
+        self.cells()
+            .iter()
+            .filter_map(|c| c.tree().as_ref())
+            .fold((0, 0), |(num, depth), tree| {
+                (num + 1, depth.max(tree.depth()))
+            })
+    }
+
+    /// Determine the depth of refinement in each cell.
+    #[inline]
+    #[must_use]
+    pub fn depths(&self) -> Array3<f64> {
+        self.cells().map(|c| f64::from(c.depth()))
+    }
+
     /// Determine a suitable bump distance for the grid.
     #[inline]
     #[must_use]
@@ -195,7 +216,56 @@ impl<'a> Grid<'a> {
             .zip(self.cells.shape())
             .map(|(dx, r)| *dx / *r as f64 * BUMP_SCALE)
             .collect();
+        let depth = self.cells.iter().map(Cell::depth).max().unwrap_or(0);
+
+        list::min(&mins) / f64::from(2_u32.pow(depth))
+    }
+
+    /// Reference the leaf cell containing a position.
+    #[inline]
+    #[must_use]
+    pub fn leaf(&self, pos: &Point3<f64>) -> &Cell<'a> {
+        debug_assert!(self.bound.contains(pos));
+
+        let mins = self.bound.mins();
+        let widths = self.bound.widths();
+        let shape = self.cells.shape();
+        let index = |axis: usize| {
+            let frac = (pos[axis] - mins[axis]) / widths[axis];
+            ((frac * shape[axis] as f64) as usize).min(shape[axis] - 1)
+        };
+
+        self.cells
+            .get((index(X as usize), index(Y as usize), index(Z as usize)))
+            .expect("Invalid grid index.")
+            .leaf(pos)
+    }
+}
+
+impl Traverse for Grid<'_> {
+    #[inline]
+    fn bound(&self) -> &Aabb {
+        &self.bound
+    }
+
+    #[inline]
+    fn boundary(&self) -> &Boundary {
+        &self.boundary
+    }
+
+    #[inline]
+    fn bump_dist(&self) -> f64 {
+        Grid::bump_dist(self)
+    }
+
+    #[inline]
+    fn step(&self, ray: &Ray) -> (f64, Option<(f64, bool, Unit<Vector3<f64>>, &Interface)>) {
+        let cell = self.leaf(ray.pos());
+        let cell_dist = cell
+            .bound()
+            .dist(ray)
+            .expect("Could not determine cell distance.");
 
-        list::min(&mins)
+        (cell_dist, cell.inter_dist_inside_norm_inter(ray))
     }
 }
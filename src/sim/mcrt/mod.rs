@@ -2,55 +2,105 @@
 
 pub mod cell;
 pub mod cell_rec;
+pub mod checkpoint;
 pub mod grid;
 pub mod hit;
 pub mod light_map;
 pub mod photon_loop;
 pub mod record;
+pub mod refine;
+pub mod target;
 
-pub use self::{cell::*, cell_rec::*, grid::*, hit::*, light_map::*, record::*};
+pub use self::{
+    cell::*, cell_rec::*, checkpoint::*, grid::*, hit::*, light_map::*, record::*, refine::*,
+    target::*,
+};
 
 use crate::{
-    ord::{MatSet, SurfSet},
+    ord::{Accumulator, DetSet, MatSet, SurfSet},
+    sim::variance::Variance,
     util::ParProgressBar,
     world::Light,
 };
+use log::info;
 use num_cpus;
 use rayon::prelude::*;
-use std::sync::{Arc, Mutex};
+use std::{
+    path::Path,
+    sync::{atomic::AtomicBool, Arc, Mutex},
+};
+
+/// Number of blocks the photons are divided into.
+/// This is independent of the thread count so that each block's random stream is reproducible.
+const NUM_BLOCKS: u64 = 1_000;
 
 /// Run a MCRT simulation.
 #[inline]
 #[must_use]
-pub fn run(num_phot: u64, light: &Light, grid: &Grid, surfs: &SurfSet, mats: &MatSet) -> LightMap {
-    debug_assert!(num_phot > 0);
+pub fn run(
+    state: Checkpoint,
+    checkpoint_path: &Path,
+    target: Option<&Target>,
+    variance: &Variance,
+    light: &Light,
+    grid: &Grid,
+    surfs: &SurfSet,
+    mats: &MatSet,
+    dets: &DetSet,
+) -> LightMap {
+    let num_phot = state.num_phot();
+    let seed = state.seed();
+    let complete = state.complete();
 
-    let pb = ParProgressBar::new("Photon Loop", num_phot);
+    let pb = ParProgressBar::resume("Photon Loop", complete, num_phot);
     let pb = Arc::new(Mutex::new(pb));
+    let binner = state.light_map().binner().clone();
+    let time_binner = state.light_map().time_binner().clone();
+    let acc = Accumulator::new(state.into_light_map(), complete);
+    let acc = Arc::new(acc);
+    let saved = Mutex::new(complete);
+    let stop = AtomicBool::new(false);
     let thread_ids: Vec<usize> = (0..num_cpus::get()).collect();
 
-    let mut light_maps: Vec<_> = thread_ids
-        .par_iter()
-        .map(|_| {
-            photon_loop::run_thread(
-                &Arc::clone(&pb),
-                ((num_phot / num_cpus::get() as u64) / 100).max(1) as u64,
-                num_phot,
-                light,
-                grid,
-                surfs,
-                mats,
-            )
-        })
-        .collect();
+    thread_ids.par_iter().for_each(|_| {
+        photon_loop::run_thread(
+            &Arc::clone(&pb),
+            &Arc::clone(&acc),
+            checkpoint_path,
+            &saved,
+            target,
+            variance,
+            &stop,
+            binner.as_ref(),
+            time_binner.as_ref(),
+            (num_phot / NUM_BLOCKS).max(1),
+            num_phot,
+            seed,
+            light,
+            grid,
+            surfs,
+            mats,
+            dets,
+        )
+    });
     pb.lock()
         .expect("Could not lock progress bar.")
         .finish_with_message("Complete.");
 
-    let mut light_map = light_maps.pop().expect("Did not receive any light maps.");
-    for lm in light_maps {
-        light_map += &lm;
+    let (mut light_map, done) = Arc::try_unwrap(acc)
+        .expect("Could not retrieve accumulator.")
+        .into_inner();
+
+    if done < num_phot {
+        info!(
+            "Target error reached after {} of {} photons.",
+            done, num_phot
+        );
+        light_map *= num_phot as f64 / done as f64;
     }
 
+    info!("Energy budget:");
+    light_map.budget().report();
+
     light_map
 }
@@ -1,216 +1,418 @@
 //! Core MCRT photon loop function.
 
 use crate::{
+    file::Save,
     geom::Trace,
-    math::distribution,
-    ord::{MatSet, SurfSet},
-    phys::{Crossing, Environment, Photon},
-    sim::mcrt::{CellRec, Grid, Hit, LightMap},
+    math::{stream, Binner},
+    ord::{Accumulator, DetSet, MatSet, SurfSet},
+    phys::{Crossing, Environment, Optics, Photon},
+    sim::{
+        boundary::Fate,
+        budget::EnergyBudget,
+        estimate::estimate,
+        mcrt::{CellRec, Checkpoint, Grid, Hit, LightMap, Target},
+        variance::{Flight, Variance},
+    },
     util::ParProgressBar,
     world::Light,
 };
 use log::warn;
-use nalgebra::Point3;
 use physical_constants::SPEED_OF_LIGHT_IN_VACUUM;
-use rand::{rngs::ThreadRng, thread_rng, Rng};
+use rand::Rng;
 use std::{
     f64::{consts::PI, MIN_POSITIVE},
-    sync::{Arc, Mutex},
+    fs::rename,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
 };
 
 /// Maximum number of loops a photon will make before being culled prematurely.
 const MAX_LOOPS: u64 = 1_000_000;
 
-/// Weight below which to perform roulette each photon loop.
-const ROULETTE: f64 = 0.1;
+/// Number of blocks between writing checkpoints and checking the convergence target.
+const CHECKPOINT_BLOCKS: u64 = 10;
 
 /// Run a single threaded instance of the photon loop.
 #[allow(clippy::too_many_lines)]
 #[inline]
-#[must_use]
 pub fn run_thread(
     pb: &Arc<Mutex<ParProgressBar>>,
+    acc: &Arc<Accumulator<LightMap>>,
+    checkpoint_path: &Path,
+    saved: &Mutex<u64>,
+    target: Option<&Target>,
+    variance: &Variance,
+    stop: &AtomicBool,
+    binner: Option<&Binner>,
+    time_binner: Option<&Binner>,
     block_size: u64,
     num_phot: u64,
+    seed: u64,
     light: &Light,
     grid: &Grid,
     surfs: &SurfSet,
     mats: &MatSet,
-) -> LightMap {
+    dets: &DetSet,
+) {
     let bump_dist = grid.bump_dist();
-
-    let mut lm = LightMap::new(grid);
-    let mut rng = thread_rng();
+    let mut lm = LightMap::new(grid, binner.cloned(), time_binner.cloned(), dets);
 
     while let Some((start, end)) = {
         let mut pb = pb.lock().expect("Could not lock progress bar.");
-        let b = pb.block(block_size);
+        let b = if stop.load(Ordering::SeqCst) {
+            None
+        } else {
+            pb.block(block_size)
+        };
         std::mem::drop(pb);
         b
     } {
+        lm.reset();
+        let mut budget = EnergyBudget::new();
+        let mut rng = stream(seed, start);
+
+        let mut bank: Vec<(Photon, &Optics, bool, Flight)> = Vec::new();
+        let mut estimates = lm.dets().clone();
+
         for _ in start..end {
-            let mut phot = light.emit(&mut rng, num_phot, surfs);
+            let phot = light.emit(&mut rng, num_phot, surfs);
+            *budget.launched_mut() += phot.weight() * phot.power();
 
             debug_assert!(grid.bound().contains(phot.ray().pos()));
 
-            let mut shifted = false;
-
             let mut cr = CellRec::new(phot.ray().pos(), grid, &mut lm);
-            *cr.rec_mut().emis_mut() += phot.weight();
-
-            let mut env = mats.get(cr.cell().mat()).optics().env(phot.wavelength());
-
-            let mut num_loops = 0;
-            loop {
-                debug_assert!(phot.weight() > 0.0);
+            cr.record(&phot, |rec| *rec.emis_mut() += phot.weight());
+            let optics = mats.get(cr.cell().mat()).optics();
+            bank.push((phot, optics, false, variance.launch()));
+
+            while let Some((mut phot, mut optics, mut shifted, mut flight)) = bank.pop() {
+                let mut cr = CellRec::new(phot.ray().pos(), grid, &mut lm);
+                let mut env = optics.env(phot.wavelength());
+                let mut importance = variance.importance(phot.ray().pos());
+
+                let mut num_loops = 0;
+                loop {
+                    debug_assert!(phot.weight() > 0.0);
+
+                    num_loops += 1;
+                    if num_loops >= MAX_LOOPS {
+                        warn!(
+                            "Photon prematurely killed as number of loops exceeded {}",
+                            MAX_LOOPS
+                        );
+                        *budget.culled_mut() += phot.weight() * phot.power();
+                        break;
+                    }
 
-                num_loops += 1;
-                if num_loops >= MAX_LOOPS {
-                    warn!(
-                        "Photon prematurely killed as number of loops exceeded {}",
-                        MAX_LOOPS
+                    let copies = variance.play(
+                        &mut rng,
+                        &mut phot,
+                        &mut importance,
+                        grid.bound(),
+                        &mut budget,
                     );
-                }
-
-                if phot.weight() < ROULETTE {
-                    if rng.gen_range(0.0_f64, 1.0) <= ROULETTE {
-                        *phot.weight_mut() /= ROULETTE;
-                    } else {
+                    if copies == 0 {
                         break;
                     }
-                }
+                    for _ in 1..copies {
+                        bank.push((phot.clone(), optics, shifted, flight));
+                    }
 
-                let scat_dist = -(rng.gen_range(0.0_f64, 1.0)).ln() / env.inter_coeff();
-
-                let cell_dist = cr
-                    .cell()
-                    .bound()
-                    .dist(phot.ray())
-                    .expect("Could not determine cell distance.");
-                let inter_dist = cr.cell().inter_dist(phot.ray());
-
-                match Hit::new(scat_dist, cell_dist, inter_dist, bump_dist) {
-                    Hit::Scattering(dist) => {
-                        *cr.rec_mut().dist_trav_mut() += dist;
-                        phot.ray_mut().travel(dist);
-                        *cr.rec_mut().abs_mut() +=
-                            phot.weight() * phot.power() * env.abs_coeff() * dist;
-                        *cr.rec_mut().shifts_mut() += (phot.weight()
-                            * phot.power()
-                            * env.ref_index()
-                            * env.scat_coeff()
-                            * dist)
-                            / SPEED_OF_LIGHT_IN_VACUUM;
-
-                        *cr.rec_mut().scats_mut() += phot.weight();
-                        phot.ray_mut().rotate(
-                            distribution::henyey_greenstein(&mut rng, env.asym()),
-                            rng.gen_range(0.0, 2.0 * PI),
-                        );
+                    let cell_dist = cr
+                        .cell()
+                        .bound()
+                        .dist(phot.ray())
+                        .expect("Could not determine cell distance.");
+                    let inter_dist = cr.cell().inter_dist(phot.ray());
+                    let (scat_dist, free) = variance.scat_dist(
+                        &mut rng,
+                        grid,
+                        &mut phot,
+                        &mut flight,
+                        env.inter_coeff(),
+                    );
+                    if let Some(free) = free {
+                        bank.push((free, optics, shifted, Flight::Uncollided));
+                    }
 
-                        // *cr.rec_mut().abs_mut() += env.albedo() * phot.weight();
-                        *cr.rec_mut().abs_mut() +=
-                            phot.weight() * phot.power() * env.abs_coeff() * dist;
-                        *phot.weight_mut() *= env.albedo();
+                    match Hit::new(scat_dist, cell_dist, inter_dist, bump_dist) {
+                        Hit::Scattering(dist) => {
+                            flight = Flight::Free;
+                            cr.record(&phot, |rec| *rec.dist_trav_mut() += phot.weight() * dist);
+                            phot.travel(dist, env.ref_index());
+                            cr.record(&phot, |rec| {
+                                *rec.abs_mut() +=
+                                    phot.weight() * phot.power() * env.abs_coeff() * dist
+                            });
+                            cr.record(&phot, |rec| {
+                                *rec.shifts_mut() += (phot.weight()
+                                    * phot.power()
+                                    * env.ref_index()
+                                    * env.scat_coeff()
+                                    * dist)
+                                    / SPEED_OF_LIGHT_IN_VACUUM
+                            });
+
+                            cr.record(&phot, |rec| *rec.scats_mut() += phot.weight());
+
+                            // *cr.rec_mut().abs_mut() += env.albedo() * phot.weight();
+                            cr.record(&phot, |rec| {
+                                *rec.abs_mut() +=
+                                    phot.weight() * phot.power() * env.abs_coeff() * dist
+                            });
+                            *budget.absorbed_mut() +=
+                                phot.weight() * phot.power() * (1.0 - env.albedo());
+                            *phot.weight_mut() *= env.albedo();
+
+                            match optics.inelastic() {
+                                Some(inelastic)
+                                    if !shifted
+                                        && rng.gen_range(0.0, 1.0) <= env.inelastic_prob() =>
+                                {
+                                    cr.record(&phot, |rec| *rec.shifts_mut() += phot.weight());
+                                    let weight = phot.weight();
+                                    inelastic.emit(&mut rng, &mut phot);
+                                    *budget.absorbed_mut() +=
+                                        (weight - phot.weight()) * phot.power();
+                                    *budget.shifted_mut() += phot.weight() * phot.power();
+                                    env = optics.env(phot.wavelength());
+                                    shifted = true;
+
+                                    if variance.next_event() {
+                                        *budget.detected_mut() += estimate(
+                                            &mut rng,
+                                            grid,
+                                            mats,
+                                            &phot,
+                                            &env,
+                                            None,
+                                            &mut estimates,
+                                        );
+                                    }
+                                }
+                                _ => {
+                                    if variance.next_event() {
+                                        *budget.detected_mut() += estimate(
+                                            &mut rng,
+                                            grid,
+                                            mats,
+                                            &phot,
+                                            &env,
+                                            Some(env.phase()),
+                                            &mut estimates,
+                                        );
+                                    }
+
+                                    let weight = phot.weight();
+                                    phot.scatter(
+                                        env.phase().sample(&mut rng),
+                                        rng.gen_range(0.0, 2.0 * PI),
+                                        env.phase(),
+                                    );
+                                    *budget.polarised_mut() +=
+                                        (phot.weight() - weight) * phot.power();
+                                }
+                            }
+                        }
+                        Hit::Cell(dist) => {
+                            let dist = dist + bump_dist;
+                            cr.record(&phot, |rec| *rec.dist_trav_mut() += phot.weight() * dist);
+                            phot.travel(dist, env.ref_index());
+                            cr.record(&phot, |rec| {
+                                *rec.abs_mut() +=
+                                    phot.weight() * phot.power() * env.abs_coeff() * dist
+                            });
+                            cr.record(&phot, |rec| {
+                                *rec.shifts_mut() += (phot.weight()
+                                    * phot.power()
+                                    * env.ref_index()
+                                    * env.scat_coeff()
+                                    * dist)
+                                    / SPEED_OF_LIGHT_IN_VACUUM
+                            });
+
+                            if !grid.bound().contains(phot.ray().pos()) {
+                                flight = Flight::Free;
+                                if !cross_boundary(
+                                    grid,
+                                    &mut rng,
+                                    &mut phot,
+                                    &env,
+                                    &mut lm,
+                                    &mut budget,
+                                    !variance.next_event(),
+                                ) {
+                                    break;
+                                }
+                            }
 
-                        if !shifted && rng.gen_range(0.0, 1.0) <= env.shift_prob() {
-                            *cr.rec_mut().shifts_mut() += phot.weight();
-                            shifted = true;
+                            cr = CellRec::new(phot.ray().pos(), grid, &mut lm);
                         }
-                    }
-                    Hit::Cell(dist) => {
-                        let dist = dist + bump_dist;
-                        *cr.rec_mut().dist_trav_mut() += dist;
-                        phot.ray_mut().travel(dist);
-                        *cr.rec_mut().abs_mut() +=
-                            phot.weight() * phot.power() * env.abs_coeff() * dist;
-                        *cr.rec_mut().shifts_mut() += (phot.weight()
-                            * phot.power()
-                            * env.ref_index()
-                            * env.scat_coeff()
-                            * dist)
-                            / SPEED_OF_LIGHT_IN_VACUUM;
-
-                        if !grid.bound().contains(phot.ray().pos())
-                            && !periodic_xy(&mut phot, grid.bound().mins(), grid.bound().maxs())
-                        {
-                            break;
+                        Hit::Interface(dist) => {
+                            flight = Flight::Free;
+                            hit_interface(
+                                mats,
+                                bump_dist,
+                                &mut rng,
+                                &mut phot,
+                                &mut cr,
+                                &mut optics,
+                                &mut env,
+                                dist,
+                            );
+
+                            if !cr.cell().bound().contains(phot.ray().pos()) {
+                                // TODO: This should be able to be removed.
+                                if !grid.bound().contains(phot.ray().pos())
+                                    && !cross_boundary(
+                                        grid,
+                                        &mut rng,
+                                        &mut phot,
+                                        &env,
+                                        &mut lm,
+                                        &mut budget,
+                                        !variance.next_event(),
+                                    )
+                                {
+                                    break;
+                                }
+
+                                // warn!("Interface crossing caused cell crossing!");
+                                cr = CellRec::new(phot.ray().pos(), grid, &mut lm);
+                            }
                         }
+                        Hit::InterfaceCell(dist) => {
+                            flight = Flight::Free;
+                            hit_interface(
+                                mats,
+                                bump_dist,
+                                &mut rng,
+                                &mut phot,
+                                &mut cr,
+                                &mut optics,
+                                &mut env,
+                                dist,
+                            );
 
-                        cr = CellRec::new(phot.ray().pos(), grid, &mut lm);
-                    }
-                    Hit::Interface(dist) => {
-                        hit_interface(
-                            mats, bump_dist, &mut rng, &mut phot, &mut cr, &mut env, dist,
-                        );
-
-                        if !cr.cell().bound().contains(phot.ray().pos()) {
-                            // TODO: This should be able to be removed.
                             if !grid.bound().contains(phot.ray().pos())
-                                && !periodic_xy(&mut phot, grid.bound().mins(), grid.bound().maxs())
+                                && !cross_boundary(
+                                    grid,
+                                    &mut rng,
+                                    &mut phot,
+                                    &env,
+                                    &mut lm,
+                                    &mut budget,
+                                    !variance.next_event(),
+                                )
                             {
                                 break;
                             }
 
-                            // warn!("Interface crossing caused cell crossing!");
                             cr = CellRec::new(phot.ray().pos(), grid, &mut lm);
                         }
                     }
-                    Hit::InterfaceCell(dist) => {
-                        hit_interface(
-                            mats, bump_dist, &mut rng, &mut phot, &mut cr, &mut env, dist,
-                        );
+                }
+            }
+        }
 
-                        if !grid.bound().contains(phot.ray().pos())
-                            && !periodic_xy(&mut phot, grid.bound().mins(), grid.bound().maxs())
-                        {
-                            break;
-                        }
+        for (det, estimate) in lm.dets_mut().values_mut().zip(estimates.values()) {
+            *det += estimate;
+        }
+        *lm.budget_mut() += &budget;
+        lm.close_batch();
+
+        let interval = block_size * CHECKPOINT_BLOCKS;
+        let due =
+            (end / interval > start / interval) || (end == num_phot) || stop.load(Ordering::SeqCst);
+        let checkpoint = acc.push(start, end, &lm, |total| {
+            if due {
+                Some(Checkpoint::new(
+                    seed,
+                    num_phot,
+                    end,
+                    total.clone(),
+                    variance.clone(),
+                ))
+            } else {
+                None
+            }
+        });
 
-                        cr = CellRec::new(phot.ray().pos(), grid, &mut lm);
-                    }
+        if let Some(checkpoint) = checkpoint {
+            if let Some(target) = target {
+                if target.converged(grid, checkpoint.light_map()) {
+                    stop.store(true, Ordering::SeqCst);
                 }
             }
+
+            write_checkpoint(&checkpoint, checkpoint_path, saved);
         }
     }
-
-    lm
 }
 
-/// Create a periodic-xy boundary condition for the photons.
-fn periodic_xy(phot: &mut Photon, mins: &Point3<f64>, maxs: &Point3<f64>) -> bool {
-    let p = phot.ray_mut().pos_mut();
-    let w = maxs - mins;
-
-    if p.z < mins.z || p.z > maxs.z {
-        return false;
+/// Write a checkpoint to file, unless a more complete one has already been written.
+/// The checkpoint is written to a temporary file first so that an interrupted write never replaces a valid checkpoint.
+#[inline]
+fn write_checkpoint(checkpoint: &Checkpoint, path: &Path, saved: &Mutex<u64>) {
+    let mut saved = saved.lock().expect("Could not lock checkpoint writer.");
+    if checkpoint.complete() <= *saved {
+        return;
     }
 
-    while p.x < mins.x {
-        p.x += w.x;
+    let tmp_path = path.with_extension("tmp");
+    if let Err(err) = checkpoint.save(&tmp_path) {
+        warn!("Unable to write checkpoint: {}", err);
+        return;
     }
-    while p.x > maxs.x {
-        p.x -= w.x;
+    if let Err(err) = rename(&tmp_path, path) {
+        warn!("Unable to move checkpoint file: {}", err);
+        return;
     }
 
-    while p.y < mins.y {
-        p.y += w.y;
-    }
-    while p.y > maxs.y {
-        p.y -= w.y;
-    }
+    *saved = checkpoint.complete();
+}
 
-    true
+/// Apply the grid boundary conditions to a photon which has left the grid.
+/// Escaping photons are offered to the detectors if requested.
+/// Returns true if the photon remains within the domain.
+#[inline]
+fn cross_boundary<R: Rng>(
+    grid: &Grid,
+    rng: &mut R,
+    phot: &mut Photon,
+    env: &Environment,
+    lm: &mut LightMap,
+    budget: &mut EnergyBudget,
+    detect: bool,
+) -> bool {
+    let weight = phot.weight();
+    let fate = grid
+        .boundary()
+        .apply(rng, grid.bound(), phot, env.ref_index());
+    *budget.boundary_absorbed_mut() += (weight - phot.weight()) * phot.power();
+
+    match fate {
+        Fate::Inside => true,
+        Fate::Exit(face) => {
+            lm.escape(grid.bound(), face, phot, detect);
+            false
+        }
+        Fate::Absorbed(_) => false,
+    }
 }
 
 /// Perform an interface hit event.
 #[inline]
-fn hit_interface(
-    mats: &MatSet,
+fn hit_interface<'m, R: Rng>(
+    mats: &'m MatSet,
     bump_dist: f64,
-    rng: &mut ThreadRng,
+    rng: &mut R,
     phot: &mut Photon,
     cr: &mut CellRec,
+    optics: &mut &'m Optics,
     env: &mut Environment,
     dist: f64,
 ) {
@@ -224,36 +426,57 @@ fn hit_interface(
     } else {
         inter.in_mat()
     };
-    let next_env = mats.get(next_mat).optics().env(phot.wavelength());
+    let next_optics = mats.get(next_mat).optics();
+    let next_env = next_optics.env(phot.wavelength());
 
     let n_curr = env.ref_index();
     let n_next = next_env.ref_index();
 
     let crossing = Crossing::new(phot.ray().dir(), &norm, n_curr, n_next);
 
-    if rng.gen_range(0.0, 1.0) <= crossing.ref_prob() {
+    let inc = *phot.ray().dir();
+    let reflect = if let Some(pol) = phot.pol_mut() {
+        pol.cross(rng, &inc, &crossing)
+    } else {
+        rng.gen_range(0.0, 1.0) <= crossing.ref_prob()
+    };
+
+    if reflect {
         let effective_dist = (dist - bump_dist).max(MIN_POSITIVE);
-        *cr.rec_mut().dist_trav_mut() += effective_dist;
-        phot.ray_mut().travel(effective_dist);
-        *cr.rec_mut().abs_mut() += phot.weight() * phot.power() * env.abs_coeff() * dist;
-        *cr.rec_mut().shifts_mut() +=
-            (phot.weight() * phot.power() * env.ref_index() * env.scat_coeff() * dist)
-                / SPEED_OF_LIGHT_IN_VACUUM;
+        cr.record(&phot, |rec| {
+            *rec.dist_trav_mut() += phot.weight() * effective_dist
+        });
+        phot.travel(effective_dist, env.ref_index());
+        cr.record(&phot, |rec| {
+            *rec.abs_mut() += phot.weight() * phot.power() * env.abs_coeff() * dist
+        });
+        cr.record(&phot, |rec| {
+            *rec.shifts_mut() +=
+                (phot.weight() * phot.power() * env.ref_index() * env.scat_coeff() * dist)
+                    / SPEED_OF_LIGHT_IN_VACUUM
+        });
 
         *phot.ray_mut().dir_mut() = *crossing.ref_dir();
     } else {
         let effective_dist = dist + bump_dist;
-        *cr.rec_mut().dist_trav_mut() += effective_dist;
-        phot.ray_mut().travel(effective_dist);
-        *cr.rec_mut().abs_mut() += phot.weight() * phot.power() * env.abs_coeff() * dist;
-        *cr.rec_mut().shifts_mut() +=
-            (phot.weight() * phot.power() * env.ref_index() * env.scat_coeff() * dist)
-                / SPEED_OF_LIGHT_IN_VACUUM;
+        cr.record(&phot, |rec| {
+            *rec.dist_trav_mut() += phot.weight() * effective_dist
+        });
+        phot.travel(effective_dist, env.ref_index());
+        cr.record(&phot, |rec| {
+            *rec.abs_mut() += phot.weight() * phot.power() * env.abs_coeff() * dist
+        });
+        cr.record(&phot, |rec| {
+            *rec.shifts_mut() +=
+                (phot.weight() * phot.power() * env.ref_index() * env.scat_coeff() * dist)
+                    / SPEED_OF_LIGHT_IN_VACUUM
+        });
 
         *phot.ray_mut().dir_mut() = crossing
             .trans_dir()
             .expect("Failed to determine transmission direction.");
 
+        *optics = next_optics;
         *env = next_env;
     }
 }
@@ -1,11 +1,12 @@
 //! Light-Map record structure.
 
 use crate::clone;
-use std::ops::AddAssign;
+use serde::{Deserialize, Serialize};
+use std::ops::{AddAssign, MulAssign};
 
 /// Record structure implementation.
 /// Stores data about a single cell during an MCRT simulation.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Record {
     /// Total weight of photon emissions.
     emis: f64,
@@ -25,6 +26,19 @@ impl Record {
     clone!(abs, abs_mut, f64);
     clone!(shifts, shifts_mut, f64);
     clone!(dist_trav, dist_trav_mut, f64);
+
+    /// Create a record of the squared values.
+    #[inline]
+    #[must_use]
+    pub fn sqr(&self) -> Self {
+        Self {
+            emis: self.emis * self.emis,
+            scats: self.scats * self.scats,
+            abs: self.abs * self.abs,
+            shifts: self.shifts * self.shifts,
+            dist_trav: self.dist_trav * self.dist_trav,
+        }
+    }
 }
 
 impl Default for Record {
@@ -51,3 +65,14 @@ impl AddAssign<Self> for Record {
         self.dist_trav += rhs.dist_trav;
     }
 }
+
+impl MulAssign<f64> for Record {
+    #[inline]
+    fn mul_assign(&mut self, rhs: f64) {
+        self.emis *= rhs;
+        self.scats *= rhs;
+        self.abs *= rhs;
+        self.shifts *= rhs;
+        self.dist_trav *= rhs;
+    }
+}
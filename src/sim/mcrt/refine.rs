@@ -0,0 +1,49 @@
+//! Grid refinement structure.
+
+use crate::{clone, ord::MatSet, sim::mcrt::Cell};
+use attr::json;
+
+/// Grid refinement structure implementation.
+/// Cells cut by interfaces are subdivided to resolve thin layers, while records are still collected on the regular grid.
+#[json]
+pub struct Refine {
+    /// Maximum subdivision depth.
+    max_depth: u32,
+    /// Optional wavelength [m] and absorption coefficient [m^-1].
+    /// If given, only interfaces bordering a material at least this absorbing are refined.
+    absorber: Option<(f64, f64)>,
+}
+
+impl Refine {
+    clone!(max_depth, u32);
+    clone!(absorber, Option<(f64, f64)>);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(max_depth: u32, absorber: Option<(f64, f64)>) -> Self {
+        debug_assert!(absorber.map_or(true, |(w, abs)| w > 0.0 && abs >= 0.0));
+
+        Self {
+            max_depth,
+            absorber,
+        }
+    }
+
+    /// Determine if a cell should be subdivided.
+    #[inline]
+    #[must_use]
+    pub fn refines(&self, cell: &Cell, depth: u32, mats: &MatSet) -> bool {
+        if depth >= self.max_depth || cell.inter_tris().is_empty() {
+            return false;
+        }
+
+        self.absorber.map_or(true, |(w, abs)| {
+            cell.inter_tris().iter().any(|((_, inter), _)| {
+                [inter.in_mat(), inter.out_mat()]
+                    .iter()
+                    .any(|mat| mats.get(mat).optics().abs_coeff().y(w) >= abs)
+            })
+        })
+    }
+}
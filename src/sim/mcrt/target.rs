@@ -0,0 +1,56 @@
+//! Convergence target structure.
+
+use crate::{
+    access, clone,
+    geom::Aabb,
+    math::stat::batch,
+    sim::mcrt::{Grid, LightMap},
+};
+use attr::json;
+
+/// Convergence target structure implementation.
+/// A run may halt once every cell centred within the bound has reached the relative error on its distance travelled (fluence) estimate.
+#[json]
+pub struct Target {
+    /// Region of interest.
+    bound: Aabb,
+    /// Target relative error.
+    rel_err: f64,
+}
+
+impl Target {
+    access!(bound, Aabb);
+    clone!(rel_err, f64);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(bound: Aabb, rel_err: f64) -> Self {
+        debug_assert!(rel_err > 0.0);
+
+        Self { bound, rel_err }
+    }
+
+    /// Determine if the light-map has reached the target relative error within the region.
+    /// A region containing no cell centres is never considered converged.
+    #[inline]
+    #[must_use]
+    pub fn converged(&self, grid: &Grid, lm: &LightMap) -> bool {
+        let mut cells = grid
+            .cells()
+            .indexed_iter()
+            .filter(|(_, cell)| self.bound.contains(&cell.bound().centre()))
+            .peekable();
+        if cells.peek().is_none() {
+            return false;
+        }
+
+        cells.all(|(index, _)| {
+            batch::rel_err(
+                lm.recs()[index].dist_trav(),
+                lm.sqrs()[index].dist_trav(),
+                lm.batches(),
+            ) <= self.rel_err
+        })
+    }
+}
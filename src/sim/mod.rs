@@ -1,7 +1,12 @@
 //! Simulation module.
 
+pub mod boundary;
+pub mod budget;
 pub mod diff;
+pub mod estimate;
 pub mod kin;
 pub mod mcrt;
 pub mod raman;
 pub mod render;
+pub mod variance;
+pub mod vox;
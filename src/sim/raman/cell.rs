@@ -2,11 +2,21 @@
 
 use crate::{
     access,
-    geom::{Aabb, Collide, Ray, SmoothTriangle, Trace},
-    ord::{InterKey, InterSet, MatKey, SurfSet},
+    geom::{Aabb, Collide, Ray, Trace, TriTree},
+    ord::{InterKey, MatKey, MatSet},
+    sim::{raman::Refine, vox::InterTris},
     world::Interface,
 };
-use nalgebra::{Unit, Vector3};
+use nalgebra::{Point3, Unit, Vector3};
+
+/// Number of patches within a cell above which an acceleration tree is built.
+const ACCEL_THRESHOLD: usize = 32;
+
+/// Target number of patches within each acceleration tree leaf.
+const ACCEL_TAR_TRIS: usize = 8;
+
+/// Maximum depth of an acceleration tree.
+const ACCEL_MAX_DEPTH: u32 = 5;
 
 /// Cell holding local information.
 pub struct Cell<'a> {
@@ -14,44 +24,148 @@ pub struct Cell<'a> {
     bound: Aabb,
     /// Central material.
     mat: &'a MatKey,
-    /// Intersecting interface triangles.
-    inter_tris: Vec<((&'a InterKey, &'a Interface), Vec<&'a SmoothTriangle>)>,
+    /// Intersecting interface patches.
+    inter_tris: InterTris<'a>,
+    /// Optional acceleration tree, tagging patches with their interface index.
+    tree: Option<TriTree<'a, usize>>,
+    /// Refined child cells, empty if this is a leaf.
+    children: Vec<Cell<'a>>,
 }
 
 impl<'a> Cell<'a> {
     access!(bound, Aabb);
     access!(mat, MatKey);
-    access!(
-        inter_tris,
-        Vec<((&'a InterKey, &'a Interface), Vec<&'a SmoothTriangle>)>
-    );
+    access!(inter_tris, InterTris<'a>);
+    access!(tree, Option<TriTree<'a, usize>>);
+    access!(children, Vec<Cell<'a>>);
 
-    /// Construct a new instance.
+    /// Construct a new instance from a list of intersecting interface patches.
     #[inline]
     #[must_use]
-    pub fn new(bound: Aabb, mat: &'a MatKey, inters: &'a InterSet, surfs: &'a SurfSet) -> Self {
-        let mut inter_tris = Vec::new();
-
-        for (key, inter) in inters.map() {
-            let surf = surfs.get(inter.surf());
-            if bound.overlap(surf.aabb()) {
-                let mut intersections = Vec::new();
-
-                for tri in surf.tris().iter().filter(|tri| tri.overlap(&bound)) {
-                    intersections.push(tri);
-                }
-
-                if !intersections.is_empty() {
-                    inter_tris.push(((key, inter), intersections));
-                }
-            }
-        }
+    pub fn new(bound: Aabb, mat: &'a MatKey, inter_tris: InterTris<'a>) -> Self {
+        let tris: Vec<_> = inter_tris
+            .iter()
+            .enumerate()
+            .flat_map(|(index, (_, tris))| tris.iter().map(move |tri| (index, *tri)))
+            .collect();
+        let tree = if tris.len() > ACCEL_THRESHOLD {
+            Some(TriTree::new(
+                bound.clone(),
+                &tris,
+                ACCEL_TAR_TRIS,
+                ACCEL_MAX_DEPTH,
+            ))
+        } else {
+            None
+        };
 
         Self {
             bound,
             mat,
             inter_tris,
+            tree,
+            children: Vec::new(),
+        }
+    }
+
+    /// Recursively subdivide the cell into octants, observing the central material of each child.
+    #[inline]
+    pub fn refine<F>(&mut self, refine: &Refine, mats: &MatSet, depth: u32, observe_mat: &F)
+    where
+        F: Fn(&Point3<f64>) -> &'a MatKey,
+    {
+        if !refine.refines(self, depth, mats) {
+            return;
+        }
+
+        let hws = self.bound.half_widths();
+        let mins = self.bound.mins();
+        for n in 0..8 {
+            let offset = Vector3::new(
+                if n & 1 == 0 { 0.0 } else { hws.x },
+                if n & 2 == 0 { 0.0 } else { hws.y },
+                if n & 4 == 0 { 0.0 } else { hws.z },
+            );
+            let min = mins + offset;
+            let bound = Aabb::new(min, min + hws);
+
+            let inter_tris: Vec<_> = self
+                .inter_tris
+                .iter()
+                .map(|(inter, tris)| {
+                    (
+                        *inter,
+                        tris.iter()
+                            .filter(|tri| tri.overlap(&bound))
+                            .copied()
+                            .collect::<Vec<_>>(),
+                    )
+                })
+                .filter(|(_, tris)| !tris.is_empty())
+                .collect();
+
+            let mat = observe_mat(&bound.centre());
+            let mut child = Self::new(bound, mat, inter_tris);
+            child.refine(refine, mats, depth + 1, observe_mat);
+            self.children.push(child);
+        }
+
+        self.tree = None;
+    }
+
+    /// Reference the leaf cell containing the given position.
+    #[inline]
+    #[must_use]
+    pub fn leaf(&self, pos: &Point3<f64>) -> &Self {
+        debug_assert!(self.bound.contains(pos));
+
+        self.children
+            .iter()
+            .find(|child| child.bound.contains(pos))
+            .map_or(self, |child| child.leaf(pos))
+    }
+
+    /// Determine the depth of refinement below this cell.
+    #[inline]
+    #[must_use]
+    pub fn depth(&self) -> u32 {
+        self.children
+            .iter()
+            .map(|child| child.depth() + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Determine the volume fraction of the cell filled with the given material.
+    #[inline]
+    #[must_use]
+    pub fn mat_frac(&self, key: &MatKey) -> f64 {
+        if self.children.is_empty() {
+            return if self.mat == key { 1.0 } else { 0.0 };
         }
+
+        self.children
+            .iter()
+            .map(|child| child.mat_frac(key))
+            .sum::<f64>()
+            / self.children.len() as f64
+    }
+
+    /// Determine the total number of intersecting patches.
+    #[inline]
+    #[must_use]
+    pub fn num_tris(&self) -> usize {
+        self.inter_tris.iter().map(|(_, tris)| tris.len()).sum()
+    }
+
+    /// Reference the interface of a given index.
+    #[inline]
+    #[must_use]
+    fn inter(&self, index: usize) -> (&'a InterKey, &'a Interface) {
+        self.inter_tris
+            .get(index)
+            .expect("Invalid interface index.")
+            .0
     }
 
     /// Determine the distance to the next interface along a ray's line of sight.
@@ -60,6 +174,12 @@ impl<'a> Cell<'a> {
     pub fn inter_dist(&self, ray: &Ray) -> Option<f64> {
         debug_assert!(self.bound().contains(ray.pos()));
 
+        if let Some(tree) = &self.tree {
+            return tree
+                .nearest(ray, &|_, tri| tri.dist(ray).map(|dist| (dist, ())))
+                .map(|(dist, ())| dist);
+        }
+
         let mut nearest = None;
         for ((_key, _inter), tris) in &self.inter_tris {
             for tri in tris {
@@ -81,6 +201,15 @@ impl<'a> Cell<'a> {
         &self,
         ray: &Ray,
     ) -> Option<(f64, bool, Unit<Vector3<f64>>, &Interface)> {
+        if let Some(tree) = &self.tree {
+            return tree
+                .nearest(ray, &|index, tri| {
+                    tri.dist_inside_norm(ray)
+                        .map(|(dist, inside, norm)| (dist, (inside, norm, index)))
+                })
+                .map(|(dist, (inside, norm, index))| (dist, inside, norm, self.inter(index).1));
+        }
+
         let mut nearest: Option<(f64, bool, Unit<Vector3<f64>>, &Interface)> = None;
 
         for ((_key, inter), tris) in &self.inter_tris {
@@ -102,6 +231,12 @@ impl<'a> Cell<'a> {
     pub fn inter_dist_key(&self, ray: &Ray) -> Option<(f64, &InterKey)> {
         debug_assert!(self.bound().contains(ray.pos()));
 
+        if let Some(tree) = &self.tree {
+            return tree
+                .nearest(ray, &|index, tri| tri.dist(ray).map(|dist| (dist, index)))
+                .map(|(dist, index)| (dist, self.inter(index).0));
+        }
+
         let mut nearest: Option<(f64, _)> = None;
         for ((key, _inter), tris) in &self.inter_tris {
             for tri in tris {
@@ -3,9 +3,12 @@
 use crate::{
     access,
     list::Cartesian::{X, Y, Z},
+    math::Binner,
+    phys::Photon,
     sim::raman::{Cell, Grid, LightMap, Record},
 };
 use nalgebra::Point3;
+use ndarray::ArrayViewMut1;
 
 /// Store a reference to a cell and its corresponding light-map record.
 pub struct CellRec<'a> {
@@ -13,6 +16,10 @@ pub struct CellRec<'a> {
     cell: &'a Cell<'a>,
     /// Reference to the respective light-map record.
     rec: &'a mut Record,
+    /// Optional wavelength binner and the respective spectral records.
+    spec: Option<(&'a Binner, ArrayViewMut1<'a, Record>)>,
+    /// Optional time binner and the respective time-gated records.
+    gate: Option<(&'a Binner, ArrayViewMut1<'a, Record>)>,
 }
 
 impl<'a> CellRec<'a> {
@@ -37,14 +44,45 @@ impl<'a> CellRec<'a> {
             *id.get(Z as usize).expect("Missing index."),
         );
 
-        let cell = grid.cells().get(index).expect("Invalid grid index.");
-        let rec = light_map
-            .recs_mut()
-            .get_mut(index)
-            .expect("Invalid record index.");
+        let cell = grid
+            .cells()
+            .get(index)
+            .expect("Invalid grid index.")
+            .leaf(pos);
+        let (rec, spec, gate) = light_map.cell_recs_mut(index);
 
         debug_assert!(cell.bound().contains(pos));
 
-        Self { cell, rec }
+        Self {
+            cell,
+            rec,
+            spec,
+            gate,
+        }
+    }
+
+    /// Apply an update to the cell's record.
+    /// The spectral record of the photon's wavelength, and the time-gated record of its time of flight, are also updated if binned.
+    #[inline]
+    pub fn record<F: Fn(&mut Record)>(&mut self, phot: &Photon, update: F) {
+        update(self.rec);
+
+        if let Some((binner, recs)) = &mut self.spec {
+            if let Some(rec) = binner
+                .try_bin(phot.wavelength())
+                .and_then(|bin| recs.get_mut(bin))
+            {
+                update(rec);
+            }
+        }
+
+        if let Some((binner, recs)) = &mut self.gate {
+            if let Some(rec) = binner
+                .try_bin(phot.time())
+                .and_then(|bin| recs.get_mut(bin))
+            {
+                update(rec);
+            }
+        }
     }
 }
@@ -2,11 +2,26 @@
 
 use crate::{
     access, clone,
-    file::Save,
-    sim::raman::{Grid, Record},
+    err::Error,
+    file::{as_spectral, as_temporal, Save},
+    geom::Aabb,
+    math::{stat::batch, Binner},
+    ord::{DetKey, DetSet},
+    phys::Photon,
+    sim::{
+        boundary::ExitMap,
+        budget::EnergyBudget,
+        raman::{Grid, Record},
+    },
+    world::{Detector, Instrument},
+};
+use ndarray::{s, Array3, Array4, ArrayViewMut1, Zip};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    ops::{AddAssign, MulAssign},
+    path::Path,
 };
-use ndarray::Array3;
-use std::{ops::AddAssign, path::Path};
 
 macro_rules! data_dens {
     ($dens_func: ident, $prop: ident) => {
@@ -19,29 +34,119 @@ macro_rules! data_dens {
     };
 }
 
+macro_rules! data_err {
+    ($err_func: ident, $prop: ident) => {
+        /// Create a relative error data-cube of the lightmap's records.
+        #[inline]
+        #[must_use]
+        pub fn $err_func(&self) -> Array3<f64> {
+            let mut errs = Array3::zeros(self.recs.raw_dim());
+            Zip::from(&mut errs)
+                .and(&self.recs)
+                .and(&self.sqrs)
+                .apply(|err, rec, sqr| {
+                    *err = batch::rel_err(rec.$prop(), sqr.$prop(), self.batches)
+                });
+            errs
+        }
+    };
+}
+
+macro_rules! data_spec_dens {
+    ($dens_func: ident, $prop: ident) => {
+        /// Create a spectral density data-cube of the lightmap's records, per wavelength bin.
+        #[inline]
+        #[must_use]
+        pub fn $dens_func(&self) -> Array4<f64> {
+            self.spec_recs.map(|rec| rec.$prop() / self.cell_vol)
+        }
+    };
+}
+
+macro_rules! data_time_dens {
+    ($dens_func: ident, $prop: ident) => {
+        /// Create a time-gated density data-cube of the lightmap's records, per time bin.
+        #[inline]
+        #[must_use]
+        pub fn $dens_func(&self) -> Array4<f64> {
+            self.time_recs.map(|rec| rec.$prop() / self.cell_vol)
+        }
+    };
+}
+
 /// Light-Map structure implementation.
 /// Stores output data from an MCRT simulation.
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LightMap {
     /// Record array.
     recs: Array3<Record>,
+    /// Sum of squared batch record array.
+    sqrs: Array3<Record>,
+    /// Number of batches accumulated.
+    batches: u64,
     /// Cell volume [m^3].
     cell_vol: f64,
+    /// Optional wavelength binner.
+    binner: Option<Binner>,
+    /// Wavelength resolved record array.
+    spec_recs: Array4<Record>,
+    /// Optional time of flight binner.
+    time_binner: Option<Binner>,
+    /// Time-gated record array.
+    time_recs: Array4<Record>,
+    /// Detectors collecting escaping photons.
+    dets: BTreeMap<DetKey, Instrument>,
+    /// Energy leaving through each face of the grid.
+    exits: ExitMap,
+    /// Ledger of the launched energy.
+    budget: EnergyBudget,
 }
 
 impl LightMap {
     access!(recs, recs_mut, Array3<Record>);
+    access!(sqrs, Array3<Record>);
+    clone!(batches, u64);
     clone!(cell_vol, f64);
+    access!(binner, Option<Binner>);
+    access!(spec_recs, spec_recs_mut, Array4<Record>);
+    access!(time_binner, Option<Binner>);
+    access!(time_recs, time_recs_mut, Array4<Record>);
+    access!(dets, dets_mut, BTreeMap<DetKey, Instrument>);
+    access!(exits, ExitMap);
+    access!(budget, budget_mut, EnergyBudget);
 
     /// Construct a new instance.
     #[inline]
     #[must_use]
-    pub fn new(grid: &Grid) -> Self {
+    pub fn new(
+        grid: &Grid,
+        binner: Option<Binner>,
+        time_binner: Option<Binner>,
+        dets: &DetSet,
+    ) -> Self {
         let cell_vol = grid.bound().vol() / grid.cells().len() as f64;
+        let [nx, ny, nz] = grid.res();
+        let bins = binner.as_ref().map_or(0, |binner| binner.bins() as usize);
+        let gates = time_binner
+            .as_ref()
+            .map_or(0, |binner| binner.bins() as usize);
 
         Self {
             recs: Array3::default(grid.res()),
+            sqrs: Array3::default(grid.res()),
+            batches: 0,
             cell_vol,
+            binner,
+            spec_recs: Array4::default([nx, ny, nz, bins]),
+            time_binner,
+            time_recs: Array4::default([nx, ny, nz, gates]),
+            dets: dets
+                .map()
+                .iter()
+                .map(|(key, det)| (key.clone(), det.clone()))
+                .collect(),
+            exits: ExitMap::new(grid.res()),
+            budget: EnergyBudget::new(),
         }
     }
 
@@ -50,6 +155,113 @@ impl LightMap {
     data_dens!(absorptions_dens, absorptions);
     data_dens!(shift_dens, shifts);
     data_dens!(dist_travelled_dens, dist_travelled);
+
+    data_err!(emissions_err, emissions);
+    data_err!(scatters_err, scatters);
+    data_err!(absorptions_err, absorptions);
+    data_err!(shift_err, shifts);
+    data_err!(dist_travelled_err, dist_travelled);
+
+    data_spec_dens!(spec_absorptions_dens, absorptions);
+    data_spec_dens!(spec_scatters_dens, scatters);
+    data_spec_dens!(spec_dist_travelled_dens, dist_travelled);
+
+    data_time_dens!(time_absorptions_dens, absorptions);
+    data_time_dens!(time_scatters_dens, scatters);
+    data_time_dens!(time_dist_travelled_dens, dist_travelled);
+
+    /// Reference a cell's record, and its spectral and time-gated records if binned.
+    #[inline]
+    #[must_use]
+    pub fn cell_recs_mut(
+        &mut self,
+        index: (usize, usize, usize),
+    ) -> (
+        &mut Record,
+        Option<(&Binner, ArrayViewMut1<'_, Record>)>,
+        Option<(&Binner, ArrayViewMut1<'_, Record>)>,
+    ) {
+        let rec = self.recs.get_mut(index).expect("Invalid record index.");
+        let spec_recs = &mut self.spec_recs;
+        let spec = self.binner.as_ref().map(move |binner| {
+            (
+                binner,
+                spec_recs.slice_mut(s![index.0, index.1, index.2, ..]),
+            )
+        });
+        let time_recs = &mut self.time_recs;
+        let gate = self.time_binner.as_ref().map(move |binner| {
+            (
+                binner,
+                time_recs.slice_mut(s![index.0, index.1, index.2, ..]),
+            )
+        });
+
+        (rec, spec, gate)
+    }
+
+    /// Record a photon leaving through a face of the grid, and optionally offer it to each of the detectors.
+    #[inline]
+    pub fn escape(&mut self, bound: &Aabb, face: usize, phot: &Photon, detect: bool) {
+        let energy = phot.weight() * phot.power();
+        self.exits.record(bound, face, phot.ray().pos(), energy);
+        self.budget.escaped_mut()[face] += energy;
+
+        if !detect {
+            return;
+        }
+        for det in self.dets.values_mut() {
+            if det.detect(phot) {
+                *self.budget.detected_mut() += energy;
+            }
+        }
+    }
+
+    /// Clear all records so that the map can be reused for another block.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.recs.map_inplace(|rec| *rec = Record::default());
+        self.sqrs.map_inplace(|sqr| *sqr = Record::default());
+        self.batches = 0;
+        self.spec_recs.map_inplace(|rec| *rec = Record::default());
+        self.time_recs.map_inplace(|rec| *rec = Record::default());
+        for det in self.dets.values_mut() {
+            *det *= 0.0;
+        }
+        self.exits *= 0.0;
+        self.budget = EnergyBudget::new();
+    }
+
+    /// Check that this light-map was constructed with the same grid resolution, binners and detectors as another.
+    /// Accumulated data is not compared.
+    #[inline]
+    #[must_use]
+    pub fn matches(&self, other: &Self) -> bool {
+        self.recs.shape() == other.recs.shape()
+            && self.binner == other.binner
+            && self.time_binner == other.time_binner
+            && self.dets.keys().eq(other.dets.keys())
+            && self
+                .dets
+                .values()
+                .zip(other.dets.values())
+                .all(|(det, other)| {
+                    let mut det = det.clone();
+                    det *= 0.0;
+                    let mut other = other.clone();
+                    other *= 0.0;
+                    det == other
+                })
+    }
+
+    /// Close the current records as a single batch of the variance estimate.
+    #[inline]
+    pub fn close_batch(&mut self) {
+        debug_assert!(self.batches == 0);
+
+        self.sqrs = self.recs.map(Record::sqr);
+        self.batches = 1;
+    }
 }
 
 impl AddAssign<&Self> for LightMap {
@@ -58,18 +270,97 @@ impl AddAssign<&Self> for LightMap {
         debug_assert!((self.cell_vol - rhs.cell_vol).abs() < 1.0e-9);
 
         self.recs += &rhs.recs;
+        self.sqrs += &rhs.sqrs;
+        self.batches += rhs.batches;
+        self.spec_recs += &rhs.spec_recs;
+        self.time_recs += &rhs.time_recs;
+        for (det, rhs) in self.dets.values_mut().zip(rhs.dets.values()) {
+            *det += rhs;
+        }
+        self.exits += &rhs.exits;
+        self.budget += &rhs.budget;
+    }
+}
+
+impl MulAssign<f64> for LightMap {
+    #[inline]
+    fn mul_assign(&mut self, rhs: f64) {
+        self.recs.map_inplace(|rec| *rec *= rhs);
+        self.sqrs.map_inplace(|sqr| *sqr *= rhs * rhs);
+        self.spec_recs.map_inplace(|rec| *rec *= rhs);
+        self.time_recs.map_inplace(|rec| *rec *= rhs);
+        for det in self.dets.values_mut() {
+            *det *= rhs;
+        }
+        self.exits *= rhs;
+        self.budget *= rhs;
     }
 }
 
 impl Save for LightMap {
-    fn save(&self, path: &Path) {
+    fn save(&self, path: &Path) -> Result<(), Error> {
         self.emissions_dens()
-            .save(&path.join("lm_emissions_dens.nc"));
-        self.scatters_dens().save(&path.join("lm_scatters_dens.nc"));
+            .save(&path.join("lm_emissions_dens.nc"))?;
+        self.emissions_err()
+            .save(&path.join("lm_emissions_err.nc"))?;
+        self.scatters_dens()
+            .save(&path.join("lm_scatters_dens.nc"))?;
+        self.scatters_err().save(&path.join("lm_scatters_err.nc"))?;
         self.absorptions_dens()
-            .save(&path.join("lm_absorptions_dens.nc"));
-        self.shift_dens().save(&path.join("lm_shift_dens.nc"));
+            .save(&path.join("lm_absorptions_dens.nc"))?;
+        self.absorptions_err()
+            .save(&path.join("lm_absorptions_err.nc"))?;
+        self.shift_dens().save(&path.join("lm_shift_dens.nc"))?;
+        self.shift_err().save(&path.join("lm_shift_err.nc"))?;
         self.dist_travelled_dens()
-            .save(&path.join("lm_dist_travelled_dens.nc"));
+            .save(&path.join("lm_dist_travelled_dens.nc"))?;
+        self.dist_travelled_err()
+            .save(&path.join("lm_dist_travelled_err.nc"))?;
+
+        for (key, det) in &self.dets {
+            det.save(&path.join(format!("det_{}", key)))?;
+        }
+        self.exits.save(path)?;
+        self.budget.save(&path.join("energy_budget.json"))?;
+
+        if let Some(binner) = &self.binner {
+            let wavelengths = binner.centres();
+            as_spectral(
+                &self.spec_absorptions_dens(),
+                &wavelengths,
+                &path.join("lm_spec_absorptions_dens.nc"),
+            )?;
+            as_spectral(
+                &self.spec_scatters_dens(),
+                &wavelengths,
+                &path.join("lm_spec_scatters_dens.nc"),
+            )?;
+            as_spectral(
+                &self.spec_dist_travelled_dens(),
+                &wavelengths,
+                &path.join("lm_spec_dist_travelled_dens.nc"),
+            )?;
+        }
+
+        if let Some(binner) = &self.time_binner {
+            let times = binner.centres();
+            as_temporal(
+                &self.time_absorptions_dens(),
+                &times,
+                &path.join("lm_time_absorptions_dens.nc"),
+            )?;
+            as_temporal(
+                &self.time_scatters_dens(),
+                &times,
+                &path.join("lm_time_scatters_dens.nc"),
+            )?;
+            as_temporal(
+                &self.time_dist_travelled_dens(),
+                &times,
+                &path.join("lm_time_dist_travelled_dens.nc"),
+            )?;
+        }
+
+        Ok(())
     }
 }
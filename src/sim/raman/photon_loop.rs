@@ -1,218 +1,439 @@
 //! Core MCRT photon loop function.
 
 use crate::{
+    file::Save,
     geom::Trace,
-    list::Cartesian::{X, Y, Z},
-    math::distribution,
-    ord::{MatKey, MatSet, SurfSet},
-    phys::{Crossing, Environment, Photon},
-    sim::raman::{Cell, CellRec, Grid, Hit, LightMap},
+    math::{stream, Binner},
+    ord::{Accumulator, DetSet, MatSet, SurfSet},
+    phys::{Crossing, Environment, Inelastic, Optics, Photon},
+    sim::{
+        boundary::Fate,
+        budget::EnergyBudget,
+        estimate::estimate,
+        raman::{CellRec, Checkpoint, Grid, Hit, LightMap, Settings, Target},
+        variance::{Flight, Variance},
+    },
     util::ParProgressBar,
     world::Light,
 };
 use log::warn;
-use nalgebra::{Point3, Unit};
-use rand::{rngs::ThreadRng, thread_rng, Rng};
+use rand::Rng;
 use std::{
     f64::{consts::PI, MIN_POSITIVE},
-    sync::{Arc, Mutex},
+    fs::rename,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
 };
 
 /// Maximum number of loops a photon will make before being culled prematurely.
 const MAX_LOOPS: u64 = 1_000_000;
 
-/// Weight below which to perform roulette each photon loop.
-const ROULETTE: f64 = 0.1;
+/// Number of blocks between writing checkpoints and checking the convergence target.
+const CHECKPOINT_BLOCKS: u64 = 10;
 
 /// Run a single threaded instance of the photon loop.
 #[allow(clippy::too_many_lines)]
 #[inline]
-#[must_use]
 pub fn run_thread(
     pb: &Arc<Mutex<ParProgressBar>>,
+    acc: &Arc<Accumulator<LightMap>>,
+    checkpoint_path: &Path,
+    saved: &Mutex<u64>,
+    target: Option<&Target>,
+    variance: &Variance,
+    stop: &AtomicBool,
+    binner: Option<&Binner>,
+    time_binner: Option<&Binner>,
     block_size: u64,
     num_phot: u64,
+    seed: u64,
+    settings: &Settings,
+    shift_optics: &Optics,
+    shift_inelastic: &Inelastic,
     light: &Light,
     grid: &Grid,
     surfs: &SurfSet,
     mats: &MatSet,
-) -> LightMap {
+    dets: &DetSet,
+) {
     let bump_dist = grid.bump_dist();
+    let mut lm = LightMap::new(grid, binner.cloned(), time_binner.cloned(), dets);
 
-    let mut lm = LightMap::new(grid);
-    let mut rng = thread_rng();
-    let mut extra_phot: Option<Photon> = None;
     while let Some((start, end)) = {
         let mut pb = pb.lock().expect("Could not lock progress bar.");
-        let b = pb.block(block_size);
+        let b = if stop.load(Ordering::SeqCst) {
+            None
+        } else {
+            pb.block(block_size)
+        };
         std::mem::drop(pb);
         b
     } {
-        //for _ in start..end {
-        let mut total = end - start;
-        //println!("Start and end: {}, {}", start, end);
-        //println!("total: {}", total);
-        while total > 0 {
-            let mut shifted = false;
-            let mut mat = None;
-            let mut phot = if let Some(phot) = extra_phot {
-                total += 1;
-                extra_phot = None;
-                //println!("cont phot!");
-                mat = Some(MatKey::new("ptfe"));
-                phot
-            } else {
-                total -= 1;
-                //println!("From light source");
-                light.emit(&mut rng, num_phot, surfs)
-            };
+        lm.reset();
+        let mut budget = EnergyBudget::new();
+        let mut rng = stream(seed, start);
+        let mut bank: Vec<(Photon, &Optics, bool, Flight)> = Vec::new();
+        let mut estimates = lm.dets().clone();
+
+        for _ in start..end {
+            let phot = light.emit(&mut rng, num_phot, surfs);
+            *budget.launched_mut() += phot.weight() * phot.power();
 
             debug_assert!(grid.bound().contains(phot.ray().pos()));
 
             let mut cr = CellRec::new(phot.ray().pos(), grid, &mut lm);
-            *cr.rec_mut().emissions_mut() += phot.weight();
+            cr.record(&phot, |rec| *rec.emissions_mut() += phot.weight());
+            let optics = mats.get(cr.cell().mat()).optics();
+            bank.push((phot, optics, false, variance.launch()));
+
+            while let Some((mut phot, mut optics, mut shifted, mut flight)) = bank.pop() {
+                let mut cr = CellRec::new(phot.ray().pos(), grid, &mut lm);
+                let mut env = optics.env(phot.wavelength());
+                let mut importance = variance.importance(phot.ray().pos());
+
+                let mut num_loops = 0;
+                loop {
+                    debug_assert!(phot.weight() > 0.0);
+                    num_loops += 1;
+                    if num_loops >= MAX_LOOPS {
+                        warn!(
+                            "Photon prematurely killed as number of loops exceeded {}",
+                            MAX_LOOPS
+                        );
+                        *budget.culled_mut() += phot.weight() * phot.power();
+                        break;
+                    }
 
-            #[allow(unused_assignments)]
-            // This is here to suppress a warning. I think it's a bug though so check it. - Freddy
-            let mut env = if let Some(mat) = mat {
-                mats.get(&mat).optics().env(phot.wavelength())
-            } else {
-                mats.get(cr.cell().mat()).optics().env(phot.wavelength())
-            };
-
-            //println!("abs coeff: {}", env.abs_coeff());
-
-            let mut num_loops = 0;
-            loop {
-                debug_assert!(phot.weight() > 0.0);
-                num_loops += 1;
-                if num_loops >= MAX_LOOPS {
-                    warn!(
-                        "Photon prematurely killed as number of loops exceeded {}",
-                        MAX_LOOPS
+                    let copies = variance.play(
+                        &mut rng,
+                        &mut phot,
+                        &mut importance,
+                        grid.bound(),
+                        &mut budget,
                     );
-                }
-
-                if phot.weight() < ROULETTE {
-                    if rng.gen_range(0.0_f64, 1.0) <= ROULETTE {
-                        *phot.weight_mut() /= ROULETTE;
-                    } else {
+                    if copies == 0 {
                         break;
                     }
-                }
+                    for _ in 1..copies {
+                        bank.push((phot.clone(), optics, shifted, flight));
+                    }
 
-                let scat_dist = -(rng.gen_range(0.0_f64, 1.0)).ln() / env.inter_coeff();
-
-                let cell_dist = cr
-                    .cell()
-                    .bound()
-                    .dist(phot.ray())
-                    .expect("Could not determine cell distance.");
-                let inter_dist = cr.cell().inter_dist(phot.ray());
-
-                match Hit::new(scat_dist, cell_dist, inter_dist, bump_dist) {
-                    Hit::Scattering(dist) => {
-                        *cr.rec_mut().dist_travelled_mut() += dist;
-                        phot.ray_mut().travel(dist);
-                        //if shifted == true {
-                        //println!("Abs coeff: {}", env.abs_coeff());
-                        //};
-
-                        *cr.rec_mut().absorptions_mut() +=
-                            phot.weight() * phot.power() * env.abs_coeff() * dist;
-
-                        *cr.rec_mut().scatters_mut() += phot.weight();
-                        phot.ray_mut().rotate(
-                            distribution::henyey_greenstein(&mut rng, env.asym()),
-                            rng.gen_range(0.0, 2.0 * PI),
-                        );
+                    let cell_dist = cr
+                        .cell()
+                        .bound()
+                        .dist(phot.ray())
+                        .expect("Could not determine cell distance.");
+                    let inter_dist = cr.cell().inter_dist(phot.ray());
+                    let (scat_dist, free) = variance.scat_dist(
+                        &mut rng,
+                        grid,
+                        &mut phot,
+                        &mut flight,
+                        env.inter_coeff(),
+                    );
+                    if let Some(free) = free {
+                        bank.push((free, optics, shifted, Flight::Uncollided));
+                    }
+
+                    match Hit::new(scat_dist, cell_dist, inter_dist, bump_dist) {
+                        Hit::Scattering(dist) => {
+                            flight = Flight::Free;
+                            cr.record(&phot, |rec| {
+                                *rec.dist_travelled_mut() += phot.weight() * dist
+                            });
+                            phot.travel(dist, env.ref_index());
+
+                            cr.record(&phot, |rec| {
+                                *rec.absorptions_mut() +=
+                                    phot.weight() * phot.power() * env.abs_coeff() * dist
+                            });
+
+                            cr.record(&phot, |rec| *rec.scatters_mut() += phot.weight());
+
+                            cr.record(&phot, |rec| {
+                                *rec.absorptions_mut() +=
+                                    phot.weight() * phot.power() * env.abs_coeff() * dist
+                            });
+                            *budget.absorbed_mut() +=
+                                phot.weight() * phot.power() * (1.0 - env.albedo());
+                            *phot.weight_mut() *= env.albedo();
+                            let shiftable = !shifted && std::ptr::eq(optics, shift_optics);
+                            let enhanced_prob = (settings.bias() * env.inelastic_prob()).min(1.0);
+
+                            if shiftable && rng.gen_range(0.0, 1.0) <= enhanced_prob {
+                                let weight = phot.weight();
+                                let mut re_weight = phot.clone();
+                                *phot.weight_mut() *= env.inelastic_prob() / enhanced_prob;
+                                *re_weight.weight_mut() *= 1.0 - env.inelastic_prob();
+                                *budget.biased_mut() +=
+                                    (phot.weight() + re_weight.weight() - weight) * phot.power();
+                                if variance.next_event() {
+                                    *budget.detected_mut() += estimate(
+                                        &mut rng,
+                                        grid,
+                                        mats,
+                                        &re_weight,
+                                        &env,
+                                        Some(env.phase()),
+                                        &mut estimates,
+                                    );
+                                }
+
+                                let weight = re_weight.weight();
+                                re_weight.scatter(
+                                    env.phase().sample(&mut rng),
+                                    rng.gen_range(0.0, 2.0 * PI),
+                                    env.phase(),
+                                );
+                                *budget.polarised_mut() +=
+                                    (re_weight.weight() - weight) * re_weight.power();
+                                bank.push((re_weight, optics, false, Flight::Free));
+                                cr.record(&phot, |rec| *rec.shifts_mut() += phot.weight());
+                                cr.record(&phot, |rec| *rec.ram_laser_mut() += 1.0);
+                                let weight = phot.weight();
+                                shift_inelastic.emit(&mut rng, &mut phot);
+                                *budget.absorbed_mut() += (weight - phot.weight()) * phot.power();
+                                *budget.shifted_mut() += phot.weight() * phot.power();
+                                env = optics.env(phot.wavelength());
+                                shifted = true;
+
+                                if variance.next_event() {
+                                    let det = estimate(
+                                        &mut rng,
+                                        grid,
+                                        mats,
+                                        &phot,
+                                        &env,
+                                        None,
+                                        &mut estimates,
+                                    );
+                                    cr.record(&phot, |rec| *rec.det_raman_mut() += det);
+                                    *budget.detected_mut() += det;
+                                }
+                            } else {
+                                if shiftable {
+                                    *budget.biased_mut() -=
+                                        phot.weight() * phot.power() * env.inelastic_prob();
+                                    *phot.weight_mut() *= 1.0 - env.inelastic_prob();
+                                }
+
+                                if variance.next_event() {
+                                    let det = estimate(
+                                        &mut rng,
+                                        grid,
+                                        mats,
+                                        &phot,
+                                        &env,
+                                        Some(env.phase()),
+                                        &mut estimates,
+                                    );
+                                    if shifted {
+                                        cr.record(&phot, |rec| *rec.det_raman_mut() += det);
+                                    }
+                                    *budget.detected_mut() += det;
+                                }
+
+                                let weight = phot.weight();
+                                phot.scatter(
+                                    env.phase().sample(&mut rng),
+                                    rng.gen_range(0.0, 2.0 * PI),
+                                    env.phase(),
+                                );
+                                *budget.polarised_mut() += (phot.weight() - weight) * phot.power();
+                            }
+                        }
+                        Hit::Cell(dist) => {
+                            let dist = dist + bump_dist;
+                            cr.record(&phot, |rec| {
+                                *rec.dist_travelled_mut() += phot.weight() * dist
+                            });
+                            phot.travel(dist, env.ref_index());
+                            cr.record(&phot, |rec| {
+                                *rec.absorptions_mut() +=
+                                    phot.weight() * phot.power() * env.abs_coeff() * dist
+                            });
+
+                            if !grid.bound().contains(phot.ray().pos()) {
+                                flight = Flight::Free;
+                                if !cross_boundary(
+                                    grid,
+                                    &mut rng,
+                                    &mut phot,
+                                    &env,
+                                    &mut lm,
+                                    &mut budget,
+                                    !variance.next_event(),
+                                ) {
+                                    break;
+                                }
+                            }
 
-                        *cr.rec_mut().absorptions_mut() +=
-                            phot.weight() * phot.power() * env.abs_coeff() * dist;
-                        *phot.weight_mut() *= env.albedo();
-                        let enhanced_prob = 1000.0 * env.shift_prob();
-
-                        if !shifted && rng.gen_range(0.0, 1.0) <= enhanced_prob {
-                            let mut re_weight = phot.clone();
-                            *phot.weight_mut() *= env.shift_prob() / enhanced_prob;
-                            *re_weight.weight_mut() *= 1.0 - env.shift_prob();
-                            extra_phot = Some(re_weight);
-                            *cr.rec_mut().shifts_mut() += phot.weight();
-                            *cr.rec_mut().ram_laser_mut() += 1.0;
-                            *phot.wavelength_mut() = 884.0e-9;
-                            shifted = true;
-                            env = mats
-                                .get(&MatKey::new("ptfe"))
-                                .optics()
-                                .env(phot.wavelength());
-                            //println!("Ramanised!: {}", phot.ray().pos());
+                            cr = CellRec::new(phot.ray().pos(), grid, &mut lm);
                         }
-                        if shifted {
-                            *cr.rec_mut().det_raman_mut() += peel_off(
-                                phot.clone(),
-                                env.clone(),
-                                grid,
+                        Hit::Interface(dist) => {
+                            flight = Flight::Free;
+                            hit_interface(
                                 mats,
-                                &Point3::new(0.0129, 0.0, 0.0),
                                 bump_dist,
-                            )
-                            .unwrap_or(0.0);
-                        }
-                    }
-                    Hit::Cell(dist) => {
-                        let dist = dist + bump_dist;
-                        *cr.rec_mut().dist_travelled_mut() += dist;
-                        phot.ray_mut().travel(dist);
-                        *cr.rec_mut().absorptions_mut() +=
-                            phot.weight() * phot.power() * env.abs_coeff() * dist;
-
-                        if !grid.bound().contains(phot.ray().pos()) {
-                            break;
+                                &mut rng,
+                                &mut phot,
+                                &mut cr,
+                                &mut optics,
+                                &mut env,
+                                dist,
+                            );
+
+                            if !cr.cell().bound().contains(phot.ray().pos()) {
+                                // TODO: This should be able to be removed.
+                                if !grid.bound().contains(phot.ray().pos())
+                                    && !cross_boundary(
+                                        grid,
+                                        &mut rng,
+                                        &mut phot,
+                                        &env,
+                                        &mut lm,
+                                        &mut budget,
+                                        !variance.next_event(),
+                                    )
+                                {
+                                    break;
+                                }
+
+                                // warn!("Interface crossing caused cell crossing!");
+                                cr = CellRec::new(phot.ray().pos(), grid, &mut lm);
+                            }
                         }
-
-                        cr = CellRec::new(phot.ray().pos(), grid, &mut lm);
-                    }
-                    Hit::Interface(dist) => {
-                        hit_interface(
-                            mats, bump_dist, &mut rng, &mut phot, &mut cr, &mut env, dist,
-                        );
-
-                        if !cr.cell().bound().contains(phot.ray().pos()) {
-                            // TODO: This should be able to be removed.
-                            if !grid.bound().contains(phot.ray().pos()) {
+                        Hit::InterfaceCell(dist) => {
+                            flight = Flight::Free;
+                            hit_interface(
+                                mats,
+                                bump_dist,
+                                &mut rng,
+                                &mut phot,
+                                &mut cr,
+                                &mut optics,
+                                &mut env,
+                                dist,
+                            );
+
+                            if !grid.bound().contains(phot.ray().pos())
+                                && !cross_boundary(
+                                    grid,
+                                    &mut rng,
+                                    &mut phot,
+                                    &env,
+                                    &mut lm,
+                                    &mut budget,
+                                    !variance.next_event(),
+                                )
+                            {
                                 break;
                             }
-
-                            // warn!("Interface crossing caused cell crossing!");
                             cr = CellRec::new(phot.ray().pos(), grid, &mut lm);
                         }
                     }
-                    Hit::InterfaceCell(dist) => {
-                        hit_interface(
-                            mats, bump_dist, &mut rng, &mut phot, &mut cr, &mut env, dist,
-                        );
+                }
+            }
+        }
 
-                        if !grid.bound().contains(phot.ray().pos()) {
-                            break;
-                        }
-                        cr = CellRec::new(phot.ray().pos(), grid, &mut lm);
-                    }
+        for (det, estimate) in lm.dets_mut().values_mut().zip(estimates.values()) {
+            *det += estimate;
+        }
+        *lm.budget_mut() += &budget;
+        lm.close_batch();
+
+        let interval = block_size * CHECKPOINT_BLOCKS;
+        let due =
+            (end / interval > start / interval) || (end == num_phot) || stop.load(Ordering::SeqCst);
+        let checkpoint = acc.push(start, end, &lm, |total| {
+            if due {
+                Some(Checkpoint::new(
+                    seed,
+                    num_phot,
+                    end,
+                    total.clone(),
+                    variance.clone(),
+                ))
+            } else {
+                None
+            }
+        });
+
+        if let Some(checkpoint) = checkpoint {
+            if let Some(target) = target {
+                if target.converged(grid, checkpoint.light_map()) {
+                    stop.store(true, Ordering::SeqCst);
                 }
             }
+
+            write_checkpoint(&checkpoint, checkpoint_path, saved);
         }
     }
+}
+
+/// Write a checkpoint to file, unless a more complete one has already been written.
+/// The checkpoint is written to a temporary file first so that an interrupted write never replaces a valid checkpoint.
+#[inline]
+fn write_checkpoint(checkpoint: &Checkpoint, path: &Path, saved: &Mutex<u64>) {
+    let mut saved = saved.lock().expect("Could not lock checkpoint writer.");
+    if checkpoint.complete() <= *saved {
+        return;
+    }
 
-    lm
+    let tmp_path = path.with_extension("tmp");
+    if let Err(err) = checkpoint.save(&tmp_path) {
+        warn!("Unable to write checkpoint: {}", err);
+        return;
+    }
+    if let Err(err) = rename(&tmp_path, path) {
+        warn!("Unable to move checkpoint file: {}", err);
+        return;
+    }
+
+    *saved = checkpoint.complete();
+}
+
+/// Apply the grid boundary conditions to a photon which has left the grid.
+/// Escaping photons are offered to the detectors if requested.
+/// Returns true if the photon remains within the domain.
+#[inline]
+fn cross_boundary<R: Rng>(
+    grid: &Grid,
+    rng: &mut R,
+    phot: &mut Photon,
+    env: &Environment,
+    lm: &mut LightMap,
+    budget: &mut EnergyBudget,
+    detect: bool,
+) -> bool {
+    let weight = phot.weight();
+    let fate = grid
+        .boundary()
+        .apply(rng, grid.bound(), phot, env.ref_index());
+    *budget.boundary_absorbed_mut() += (weight - phot.weight()) * phot.power();
+
+    match fate {
+        Fate::Inside => true,
+        Fate::Exit(face) => {
+            lm.escape(grid.bound(), face, phot, detect);
+            false
+        }
+        Fate::Absorbed(_) => false,
+    }
 }
 
 /// Perform an interface hit event.
 #[inline]
-fn hit_interface(
-    mats: &MatSet,
+fn hit_interface<'m, R: Rng>(
+    mats: &'m MatSet,
     bump_dist: f64,
-    rng: &mut ThreadRng,
+    rng: &mut R,
     phot: &mut Photon,
     cr: &mut CellRec,
+    optics: &mut &'m Optics,
     env: &mut Environment,
     dist: f64,
 ) {
@@ -226,132 +447,47 @@ fn hit_interface(
     } else {
         inter.in_mat()
     };
-    let next_env = mats.get(next_mat).optics().env(phot.wavelength());
+    let next_optics = mats.get(next_mat).optics();
+    let next_env = next_optics.env(phot.wavelength());
 
     let n_curr = env.ref_index();
     let n_next = next_env.ref_index();
 
     let crossing = Crossing::new(phot.ray().dir(), &norm, n_curr, n_next);
 
-    if rng.gen_range(0.0, 1.0) <= crossing.ref_prob() {
+    let inc = *phot.ray().dir();
+    let reflect = if let Some(pol) = phot.pol_mut() {
+        pol.cross(rng, &inc, &crossing)
+    } else {
+        rng.gen_range(0.0, 1.0) <= crossing.ref_prob()
+    };
+
+    if reflect {
         let effective_dist = (dist - bump_dist).max(MIN_POSITIVE);
-        *cr.rec_mut().dist_travelled_mut() += effective_dist;
-        phot.ray_mut().travel(effective_dist);
-        *cr.rec_mut().absorptions_mut() += phot.weight() * phot.power() * env.abs_coeff() * dist;
+        cr.record(&phot, |rec| {
+            *rec.dist_travelled_mut() += phot.weight() * effective_dist
+        });
+        phot.travel(effective_dist, env.ref_index());
+        cr.record(&phot, |rec| {
+            *rec.absorptions_mut() += phot.weight() * phot.power() * env.abs_coeff() * dist
+        });
 
         *phot.ray_mut().dir_mut() = *crossing.ref_dir();
     } else {
         let effective_dist = dist + bump_dist;
-        *cr.rec_mut().dist_travelled_mut() += effective_dist;
-        phot.ray_mut().travel(effective_dist);
-        *cr.rec_mut().absorptions_mut() += phot.weight() * phot.power() * env.abs_coeff() * dist;
+        cr.record(&phot, |rec| {
+            *rec.dist_travelled_mut() += phot.weight() * effective_dist
+        });
+        phot.travel(effective_dist, env.ref_index());
+        cr.record(&phot, |rec| {
+            *rec.absorptions_mut() += phot.weight() * phot.power() * env.abs_coeff() * dist
+        });
 
         *phot.ray_mut().dir_mut() = crossing
             .trans_dir()
             .expect("Failed to determine transmission direction.");
 
+        *optics = next_optics;
         *env = next_env;
     }
 }
-
-/// Perform a peel off event.
-#[inline]
-#[must_use]
-pub fn peel_off(
-    mut phot: Photon,
-    mut env: Environment,
-    grid: &Grid,
-    mats: &MatSet,
-    pos: &Point3<f64>,
-    bump_dist: f64,
-) -> Option<f64> {
-    let g = env.asym();
-    let g2 = g.powi(2);
-
-    let dir = Unit::new_normalize(pos - phot.ray().pos());
-
-    let cos_ang = phot.ray().dir().dot(&dir);
-    let mut prob = phot.weight() * 0.5 * ((1.0 - g2) / (1.0 + g2 - (2.0 * g * cos_ang)).powf(1.5));
-    if prob < 0.00001 {
-        return None;
-    }
-
-    *phot.ray_mut().dir_mut() = dir;
-    let mut cell = get_cell(phot.ray().pos(), &grid);
-
-    loop {
-        //if prob < 0.00001 {
-        //    return None;
-        //}
-
-        let cell_dist = cell
-            .bound()
-            .dist(phot.ray())
-            .expect("Unable to determine cell distance.");
-        let inter_dist = cell.inter_dist_inside_norm_inter(phot.ray());
-
-        if let Some((dist, inside, _norm, inter)) = inter_dist {
-            if dist < cell_dist {
-                prob *= (-(dist + bump_dist) * env.inter_coeff()).exp();
-                phot.ray_mut().travel(dist + bump_dist);
-
-                if inside {
-                    env = mats
-                        .map()
-                        .get(inter.in_mat())
-                        .unwrap()
-                        .optics()
-                        .env(phot.wavelength());
-                } else {
-                    env = mats
-                        .map()
-                        .get(inter.out_mat())
-                        .unwrap()
-                        .optics()
-                        .env(phot.wavelength());
-                }
-            } else {
-                prob *= (-(cell_dist + bump_dist) * env.inter_coeff()).exp();
-                phot.ray_mut().travel(cell_dist + bump_dist);
-            }
-        } else {
-            prob *= (-(cell_dist + bump_dist) * env.inter_coeff()).exp();
-            phot.ray_mut().travel(cell_dist + bump_dist);
-        }
-
-        if !grid.bound().contains(phot.ray().pos()) {
-            break;
-        }
-
-        cell = get_cell(phot.ray().pos(), &grid);
-    }
-    //report!(prob);
-    Some(prob)
-}
-
-///Get a cell reference
-#[inline]
-#[must_use]
-pub fn get_cell<'a>(pos: &Point3<f64>, grid: &'a &Grid) -> &'a Cell<'a> {
-    let mins = grid.bound().mins();
-    let maxs = grid.bound().maxs();
-    let resolution = grid.res();
-
-    let id: Vec<usize> = pos
-        .iter()
-        .zip(mins.iter().zip(maxs.iter()))
-        .zip(&resolution)
-        .map(|((p, (min, max)), n)| (((p - min) / (max - min)) * *n as f64) as usize)
-        .collect();
-    let index = (
-        *id.get(X as usize).expect("Missing index."),
-        *id.get(Y as usize).expect("Missing index."),
-        *id.get(Z as usize).expect("Missing index."),
-    );
-
-    let cell = grid.cells().get(index).expect("Invalid grid index.");
-
-    debug_assert!(cell.bound().contains(pos));
-
-    cell
-}
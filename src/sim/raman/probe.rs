@@ -0,0 +1,89 @@
+//! Detector probe structure.
+
+use crate::{
+    access, clone,
+    err::Error,
+    geom::{Aperture, Ray},
+    world::Fibre,
+};
+use attr::json;
+use nalgebra::{Point3, Unit, Vector3};
+use std::f64::consts::FRAC_PI_2;
+
+/// Detector probe structure implementation.
+/// Shorthand for a bare fibre detector viewing the sample.
+#[json]
+pub struct Probe {
+    /// Position.
+    pos: Point3<f64>,
+    /// Viewing direction.
+    dir: Vector3<f64>,
+    /// Core radius. [m]
+    radius: f64,
+    /// Half-angle of the acceptance cone. [rad]
+    cone: f64,
+    /// Numerical aperture.
+    na: f64,
+}
+
+impl Probe {
+    access!(pos, Point3<f64>);
+    access!(dir, Vector3<f64>);
+    clone!(radius, f64);
+    clone!(cone, f64);
+    clone!(na, f64);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(pos: Point3<f64>, dir: Vector3<f64>, radius: f64, cone: f64, na: f64) -> Self {
+        debug_assert!(dir.norm() > 0.0);
+        debug_assert!(radius > 0.0);
+        debug_assert!(cone > 0.0);
+        debug_assert!(na > 0.0);
+
+        Self {
+            pos,
+            dir,
+            radius,
+            cone,
+            na,
+        }
+    }
+
+    /// Determine the acceptance half-angle.
+    /// This is the narrower of the acceptance cone and the angle implied by the numerical aperture.
+    #[inline]
+    #[must_use]
+    pub fn half_angle(&self) -> f64 {
+        self.cone.min(self.na.min(1.0).asin())
+    }
+
+    /// Construct the fibre detector of the named probe.
+    #[inline]
+    pub fn fibre(&self, name: &str) -> Result<Fibre, Error> {
+        if !(self.dir.norm() > 0.0) {
+            return Err(Error::invalid(name, "viewing direction must be non-zero"));
+        }
+        if !(self.radius > 0.0) {
+            return Err(Error::invalid(name, "core radius must be positive"));
+        }
+        let half_angle = self.half_angle();
+        if !(half_angle > 0.0 && half_angle < FRAC_PI_2) {
+            return Err(Error::invalid(
+                name,
+                "acceptance half-angle must lie between zero and a right angle",
+            ));
+        }
+
+        Ok(Fibre::new(
+            Aperture::new(
+                Ray::new(self.pos, Unit::new_normalize(self.dir)),
+                half_angle,
+            ),
+            self.radius,
+            None,
+            None,
+        ))
+    }
+}
@@ -1,11 +1,12 @@
 //! Light-Map record structure.
 
 use crate::clone;
-use std::ops::AddAssign;
+use serde::{Deserialize, Serialize};
+use std::ops::{AddAssign, MulAssign};
 
 /// Record structure implementation.
 /// Stores data about a single cell during an MCRT simulation.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Record {
     /// Total weight of photon emissions.
     emissions: f64,
@@ -17,7 +18,7 @@ pub struct Record {
     shifts: f64,
     /// Total distance travelled by photons.
     dist_travelled: f64,
-    ///Total weight of Raman photons detected.
+    ///Total power of Raman photons estimated to reach the detectors.
     det_raman: f64,
     ///Number of Raman photons made.
     ram_laser: f64,
@@ -31,6 +32,21 @@ impl Record {
     clone!(dist_travelled, dist_travelled_mut, f64);
     clone!(det_raman, det_raman_mut, f64);
     clone!(ram_laser, ram_laser_mut, f64);
+
+    /// Create a record of the squared values.
+    #[inline]
+    #[must_use]
+    pub fn sqr(&self) -> Self {
+        Self {
+            emissions: self.emissions * self.emissions,
+            scatters: self.scatters * self.scatters,
+            absorptions: self.absorptions * self.absorptions,
+            shifts: self.shifts * self.shifts,
+            dist_travelled: self.dist_travelled * self.dist_travelled,
+            det_raman: self.det_raman * self.det_raman,
+            ram_laser: self.ram_laser * self.ram_laser,
+        }
+    }
 }
 
 impl Default for Record {
@@ -61,3 +77,16 @@ impl AddAssign<Self> for Record {
         self.ram_laser += rhs.ram_laser;
     }
 }
+
+impl MulAssign<f64> for Record {
+    #[inline]
+    fn mul_assign(&mut self, rhs: f64) {
+        self.emissions *= rhs;
+        self.scatters *= rhs;
+        self.absorptions *= rhs;
+        self.shifts *= rhs;
+        self.dist_travelled *= rhs;
+        self.det_raman *= rhs;
+        self.ram_laser *= rhs;
+    }
+}
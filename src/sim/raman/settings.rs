@@ -0,0 +1,50 @@
+//! Raman settings structure.
+
+use crate::{
+    access, clone,
+    err::Error,
+    ord::{DetKey, DetSet, MatKey},
+    sim::raman::Probe,
+    world::Instrument,
+};
+use attr::json_load;
+use std::collections::BTreeMap;
+
+/// Raman settings structure implementation.
+#[json_load]
+pub struct Settings {
+    /// Material in which Raman shifting occurs.
+    /// The shifted light is re-emitted by the material's inelastic process.
+    shift_mat: MatKey,
+    /// Factor by which the shifting probability is biased.
+    bias: f64,
+    /// Optional named detector probes, each collected by a fibre detector.
+    probes: Option<BTreeMap<String, Probe>>,
+}
+
+impl Settings {
+    access!(shift_mat, MatKey);
+    clone!(bias, f64);
+    access!(probes, Option<BTreeMap<String, Probe>>);
+
+    /// Construct the set of detectors, adding a fibre detector for each probe to the given detectors.
+    #[inline]
+    pub fn dets(&self, dets: &DetSet) -> Result<DetSet, Error> {
+        let mut map = dets.map().clone();
+
+        if let Some(probes) = &self.probes {
+            for (name, probe) in probes {
+                let key = DetKey::new(name);
+                if map.contains_key(&key) {
+                    return Err(Error::invalid(
+                        name,
+                        "probe name is already used by a detector",
+                    ));
+                }
+                map.insert(key, Instrument::Fibre(probe.fibre(name)?));
+            }
+        }
+
+        Ok(DetSet::new(map))
+    }
+}
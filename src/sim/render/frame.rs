@@ -4,14 +4,14 @@ use crate::{
     access,
     geom::Ray,
     img::{AspectRatio, Quality, Shader},
-    math::sample::golden,
+    math::{sample::golden, stream, Stream},
     sim::render::{painter::Painter, Camera, Grid, Scheme},
     util::{ParProgressBar, ProgressBar},
 };
 use nalgebra::{Point3, Rotation3, Unit, Vector3};
 use ndarray::Array2;
 use palette::LinSrgba;
-use rand::{rngs::ThreadRng, thread_rng, Rng};
+use rand::Rng;
 use rayon::prelude::*;
 use std::{
     f64::consts::PI,
@@ -95,7 +95,7 @@ impl Frame {
         let fy = index / self.quality.section_splits().0;
         let start = (section_res.0 * fx, section_res.1 * fy);
 
-        let mut rng = thread_rng();
+        let mut rng = stream(self.quality.seed().unwrap_or(0), index as u64);
         let mut section = Array2::default(section_res);
 
         for xi in 0..section_res.0 {
@@ -121,7 +121,7 @@ impl Frame {
         painter: Painter,
         pixel: (usize, usize),
         grid: &Grid,
-        rng: &mut ThreadRng,
+        rng: &mut Stream,
     ) -> LinSrgba {
         let super_samples = self.quality.super_samples().unwrap_or(1);
         let dof_samples = self.quality.dof_samples().unwrap_or(1);
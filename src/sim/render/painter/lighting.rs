@@ -7,18 +7,18 @@ use crate::{
 };
 use nalgebra::{Point3, Unit};
 use palette::LinSrgba;
-use rand::rngs::ThreadRng;
+use rand::Rng;
 
 /// Paint the ray with the luminance.
 #[inline]
 #[must_use]
-pub fn paint(
+pub fn paint<R: Rng>(
     _cam_pos: &Point3<f64>,
     grid: &Grid,
     shader: &Shader,
     scheme: &Scheme,
     mut ray: Ray,
-    _rng: &mut ThreadRng,
+    _rng: &mut R,
     weighting: f64,
 ) -> LinSrgba {
     debug_assert!(shader.bump_dist() > 0.0);
@@ -8,7 +8,7 @@ use crate::{
 };
 use nalgebra::{Point3, Unit, Vector3};
 use palette::LinSrgba;
-use rand::rngs::ThreadRng;
+use rand::Rng;
 
 /// Minimum fragment weight to simulate.
 const MIN_WEIGHT: f64 = 0.01;
@@ -27,13 +27,13 @@ const PUDDLE_SHIMMER: f64 = 24.0;
 #[allow(clippy::single_match_else)]
 #[inline]
 #[must_use]
-pub fn paint(
+pub fn paint<R: Rng>(
     cam_pos: &Point3<f64>,
     grid: &Grid,
     shader: &Shader,
     scheme: &Scheme,
     mut ray: Ray,
-    rng: &mut ThreadRng,
+    rng: &mut R,
     weight: f64,
 ) -> LinSrgba {
     debug_assert!(weight > 0.0);
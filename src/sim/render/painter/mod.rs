@@ -6,12 +6,12 @@ pub mod lumin;
 use crate::{
     geom::Ray,
     img::settings::Shader,
+    math::Stream,
     sim::render::{Grid, Scheme},
 };
 use nalgebra::Point3;
 use palette::LinSrgba;
-use rand::rngs::ThreadRng;
 
 /// Fragment painter function.
 pub type Painter =
-    fn(&Point3<f64>, &Grid, &Shader, &Scheme, Ray, &mut ThreadRng, f64, bool) -> LinSrgba;
+    fn(&Point3<f64>, &Grid, &Shader, &Scheme, Ray, &mut Stream, f64, bool) -> LinSrgba;
@@ -9,20 +9,20 @@ use crate::{
 use nalgebra::{Point3, Unit};
 // use palette::{Gradient, LinSrgba};
 use palette::LinSrgba;
-use rand::rngs::ThreadRng;
+use rand::Rng;
 
 /// Paint the ray if it hits something.
 #[allow(clippy::never_loop)]
 #[allow(clippy::single_match_else)]
 #[inline]
 #[must_use]
-pub fn paint(
+pub fn paint<R: Rng>(
     cam_pos: &Point3<f64>,
     grid: &Grid,
     shader: &Shader,
     scheme: &Scheme,
     mut ray: Ray,
-    rng: &mut ThreadRng,
+    rng: &mut R,
 ) -> LinSrgba {
     let mut col = LinSrgba::default();
     while let Some(hit) = grid.observe(ray.clone(), shader.bump_dist()) {
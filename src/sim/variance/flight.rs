@@ -0,0 +1,17 @@
+//! Flight state enumeration.
+
+use nalgebra::Point3;
+
+/// Flight state of a photon, used to force the first collision of source photons.
+/// A collision is forced once per flight, over the distance to the first interface or the edge of the domain.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Flight {
+    /// Scattering distances are sampled freely.
+    Free,
+    /// Collision is forced before the photon leaves its material, and the uncollided remainder is split off.
+    Forced,
+    /// Collision has been sampled, and occurs on reaching the given position.
+    Scheduled(Point3<f64>),
+    /// Uncollided remainder, which may not collide before it crosses an interface or a face of the domain.
+    Uncollided,
+}
@@ -0,0 +1,9 @@
+//! Variance reduction sub-module.
+
+pub mod flight;
+pub mod region;
+pub mod roulette;
+pub mod variance;
+pub mod weight_window;
+
+pub use self::{flight::*, region::*, roulette::*, variance::*, weight_window::*};
@@ -0,0 +1,29 @@
+//! Importance region implementation.
+
+use crate::{access, clone, geom::Aabb};
+use attr::json;
+
+/// Importance region structure.
+/// Photons entering a region of higher importance are split, and those entering a region of lower importance play roulette.
+#[json]
+#[derive(Clone, PartialEq)]
+pub struct Region {
+    /// Bounding box of the region.
+    bound: Aabb,
+    /// Relative importance.
+    importance: f64,
+}
+
+impl Region {
+    access!(bound, Aabb);
+    clone!(importance, f64);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(bound: Aabb, importance: f64) -> Self {
+        debug_assert!(importance > 0.0);
+
+        Self { bound, importance }
+    }
+}
@@ -0,0 +1,54 @@
+//! Russian roulette implementation.
+
+use crate::{clone, phys::Photon, sim::budget::EnergyBudget};
+use attr::json;
+use rand::Rng;
+
+/// Russian roulette structure.
+/// Photons whose weight falls below the threshold survive with the given chance, and have their weight raised in proportion.
+#[json]
+#[derive(Clone, PartialEq)]
+pub struct Roulette {
+    /// Weight below which roulette is played.
+    threshold: f64,
+    /// Chance of surviving.
+    survival: f64,
+}
+
+impl Roulette {
+    clone!(threshold, f64);
+    clone!(survival, f64);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(threshold: f64, survival: f64) -> Self {
+        debug_assert!(threshold > 0.0);
+        debug_assert!(survival > 0.0);
+        debug_assert!(survival <= 1.0);
+
+        Self {
+            threshold,
+            survival,
+        }
+    }
+
+    /// Play roulette with a photon.
+    /// Returns false if the photon was killed.
+    #[inline]
+    pub fn play<R: Rng>(&self, rng: &mut R, phot: &mut Photon, budget: &mut EnergyBudget) -> bool {
+        if phot.weight() >= self.threshold {
+            return true;
+        }
+
+        if rng.gen_range(0.0_f64, 1.0) <= self.survival {
+            *budget.roulette_gained_mut() +=
+                phot.weight() * phot.power() * ((1.0 / self.survival) - 1.0);
+            *phot.weight_mut() /= self.survival;
+            true
+        } else {
+            *budget.roulette_lost_mut() += phot.weight() * phot.power();
+            false
+        }
+    }
+}
@@ -0,0 +1,216 @@
+//! Variance reduction implementation.
+
+use crate::{
+    access, clone,
+    err::Error,
+    geom::Aabb,
+    phys::Photon,
+    sim::{
+        budget::EnergyBudget,
+        estimate::Traverse,
+        variance::{Flight, Region, Roulette, WeightWindow},
+    },
+};
+use attr::json;
+use nalgebra::Point3;
+use rand::Rng;
+use std::f64::INFINITY;
+
+/// Variance reduction structure.
+/// Each technique preserves the expected weight of a photon, so the estimators remain unbiased.
+#[json]
+#[derive(Clone, PartialEq)]
+pub struct Variance {
+    /// Russian roulette, played when no weight window is given.
+    roulette: Roulette,
+    /// Importance regions used for geometric splitting.
+    /// Where regions overlap, the last one listed takes precedence.
+    regions: Vec<Region>,
+    /// Optional weight window.
+    window: Option<WeightWindow>,
+    /// Force the first collision of each source photon.
+    forced_collision: bool,
+    /// Estimate detector signals at each scattering event, rather than collecting escaping photons.
+    next_event: bool,
+}
+
+impl Variance {
+    access!(roulette, Roulette);
+    access!(regions, Vec<Region>);
+    access!(window, Option<WeightWindow>);
+    clone!(forced_collision, bool);
+    clone!(next_event, bool);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(
+        roulette: Roulette,
+        regions: Vec<Region>,
+        window: Option<WeightWindow>,
+        forced_collision: bool,
+        next_event: bool,
+    ) -> Self {
+        Self {
+            roulette,
+            regions,
+            window,
+            forced_collision,
+            next_event,
+        }
+    }
+
+    /// Construct an instance which only plays roulette with photons below a tenth of their initial weight.
+    #[inline]
+    #[must_use]
+    pub fn standard() -> Self {
+        Self::new(Roulette::new(0.1, 0.1), Vec::new(), None, false, false)
+    }
+
+    /// Determine the importance at a position.
+    #[inline]
+    #[must_use]
+    pub fn importance(&self, pos: &Point3<f64>) -> f64 {
+        self.regions
+            .iter()
+            .rev()
+            .find(|region| region.bound().contains(pos))
+            .map_or(1.0, Region::importance)
+    }
+
+    /// Determine the flight state of a newly emitted photon.
+    #[inline]
+    #[must_use]
+    pub fn launch(&self) -> Flight {
+        if self.forced_collision {
+            Flight::Forced
+        } else {
+            Flight::Free
+        }
+    }
+
+    /// Check the weight window, if any, was generated for a grid of the given resolution.
+    #[inline]
+    pub fn validate(&self, res: [usize; 3]) -> Result<(), Error> {
+        if let Some(window) = &self.window {
+            if window.targets().data().shape() != res {
+                return Err(Error::invalid(
+                    "variance",
+                    "weight window targets do not match the grid resolution",
+                ));
+            }
+            if !window.targets().data().iter().all(|t| *t >= 0.0) {
+                return Err(Error::invalid(
+                    "variance",
+                    "weight window targets must be non-negative",
+                ));
+            }
+            if !(window.ratio() > 1.0) {
+                return Err(Error::invalid(
+                    "variance",
+                    "weight window ratio must be greater than one",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Play the splitting and roulette games with a photon at the start of a step.
+    /// A photon which has moved between importance regions is split, or plays roulette, by the ratio of the importances.
+    /// Otherwise the photon is checked against the weight window of its cell, or plays roulette if there is none.
+    /// Returns the number of copies the photon should be continued as, each carrying its new weight.
+    #[inline]
+    pub fn play<R: Rng>(
+        &self,
+        rng: &mut R,
+        phot: &mut Photon,
+        importance: &mut f64,
+        bound: &Aabb,
+        budget: &mut EnergyBudget,
+    ) -> usize {
+        let next = self.importance(phot.ray().pos());
+        if (next - *importance).abs() > (f64::EPSILON * importance.max(next)) {
+            let ratio = next / *importance;
+            *importance = next;
+            return split(rng, phot, ratio, budget);
+        }
+
+        if let Some(copies) = self
+            .window
+            .as_ref()
+            .and_then(|window| window.check(rng, phot, bound, next, budget))
+        {
+            return copies;
+        }
+
+        if self.roulette.play(rng, phot, budget) {
+            1
+        } else {
+            0
+        }
+    }
+
+    /// Sample the distance to the next scattering event.
+    /// A forced photon is made to collide before it reaches the next interface or leaves the domain,
+    /// and the uncollided remainder of its weight is returned as a new photon.
+    /// The flight state should be freed once the photon scatters, crosses an interface or crosses a face of the domain.
+    #[inline]
+    pub fn scat_dist<R: Rng, G: Traverse>(
+        &self,
+        rng: &mut R,
+        grid: &G,
+        phot: &mut Photon,
+        flight: &mut Flight,
+        inter_coeff: f64,
+    ) -> (f64, Option<Photon>) {
+        match *flight {
+            Flight::Free => (-(rng.gen_range(0.0_f64, 1.0)).ln() / inter_coeff, None),
+            Flight::Uncollided => (INFINITY, None),
+            Flight::Scheduled(pos) => (nalgebra::distance(&pos, phot.ray().pos()), None),
+            Flight::Forced => {
+                let prob = -(-inter_coeff * grid.reach(phot.ray())).exp_m1();
+                if prob <= 0.0 {
+                    *flight = Flight::Uncollided;
+                    return (INFINITY, None);
+                }
+
+                let free = if prob < 1.0 {
+                    let mut free = phot.clone();
+                    *free.weight_mut() *= 1.0 - prob;
+                    Some(free)
+                } else {
+                    None
+                };
+                *phot.weight_mut() *= prob;
+
+                let rand = 1.0 - rng.gen_range(0.0_f64, 1.0);
+                let dist = -(-rand * prob).ln_1p() / inter_coeff;
+                *flight = Flight::Scheduled(phot.ray().pos() + (phot.ray().dir().as_ref() * dist));
+
+                (dist, free)
+            }
+        }
+    }
+}
+
+/// Split a photon by a ratio of importances.
+/// The photon continues as the integer part of the ratio in copies, with one more copy played for with the fractional part.
+/// Each copy carries the weight divided by the ratio.
+#[inline]
+fn split<R: Rng>(rng: &mut R, phot: &mut Photon, ratio: f64, budget: &mut EnergyBudget) -> usize {
+    debug_assert!(ratio > 0.0);
+
+    let weight = phot.weight();
+    let copies = ratio.floor() as usize + usize::from(rng.gen_range(0.0_f64, 1.0) < ratio.fract());
+    *phot.weight_mut() /= ratio;
+
+    let change = ((copies as f64 * phot.weight()) - weight) * phot.power();
+    if change > 0.0 {
+        *budget.roulette_gained_mut() += change;
+    } else {
+        *budget.roulette_lost_mut() -= change;
+    }
+
+    copies
+}
@@ -0,0 +1,113 @@
+//! Weight window implementation.
+
+use crate::{access, clone, file::Redirect, geom::Aabb, phys::Photon, sim::budget::EnergyBudget};
+use attr::json;
+use nalgebra::Point3;
+use ndarray::Array3;
+use rand::Rng;
+
+/// Maximum number of copies a photon may be split into by a single window check.
+const MAX_SPLIT: usize = 64;
+
+/// Weight window structure.
+/// Each cell of the grid is given its own window, centred on a target weight.
+/// Photons heavier than the window are split, and those lighter play roulette for the target weight.
+/// The windows are given for unit importance, and are scaled by the inverse of the local importance.
+#[json]
+#[derive(Clone, PartialEq)]
+pub struct WeightWindow {
+    /// Target weight of each cell, loaded from a netcdf datacube at the grid resolution.
+    /// Cells with a target of zero are not windowed.
+    targets: Redirect<Array3<f64>>,
+    /// Ratio of the upper to the lower weight bound.
+    ratio: f64,
+}
+
+impl WeightWindow {
+    access!(targets, Redirect<Array3<f64>>);
+    clone!(ratio, f64);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(targets: Redirect<Array3<f64>>, ratio: f64) -> Self {
+        debug_assert!(targets.data().iter().all(|t| *t >= 0.0));
+        debug_assert!(ratio > 1.0);
+
+        Self { targets, ratio }
+    }
+
+    /// Generate the target weights from the fluence of a forward run.
+    /// Targets are proportional to the fluence, so that photons are spread evenly over the grid,
+    /// and are normalised such that the brightest cell, typically containing the source, has a target of unity.
+    #[inline]
+    #[must_use]
+    pub fn generate(fluence: &Array3<f64>) -> Array3<f64> {
+        let max = fluence.iter().cloned().fold(0.0, f64::max);
+        if max <= 0.0 {
+            return Array3::zeros(fluence.raw_dim());
+        }
+
+        fluence.map(|f| f.max(0.0) / max)
+    }
+
+    /// Determine the target weight of the cell of the given bound containing a position.
+    #[inline]
+    #[must_use]
+    pub fn target(&self, bound: &Aabb, pos: &Point3<f64>) -> f64 {
+        let targets = self.targets.data();
+        let res = targets.shape();
+        let mins = bound.mins();
+        let widths = bound.widths();
+
+        let mut index = [0; 3];
+        for (i, n) in index.iter_mut().enumerate() {
+            let frac = ((pos[i] - mins[i]) / widths[i]).max(0.0);
+            *n = ((frac * res[i] as f64) as usize).min(res[i] - 1);
+        }
+
+        targets[index]
+    }
+
+    /// Check a photon against the window of its cell at the given importance.
+    /// Returns the number of copies the photon should be continued as, each carrying its new weight,
+    /// or None if the cell is not windowed.
+    #[inline]
+    pub fn check<R: Rng>(
+        &self,
+        rng: &mut R,
+        phot: &mut Photon,
+        bound: &Aabb,
+        importance: f64,
+        budget: &mut EnergyBudget,
+    ) -> Option<usize> {
+        debug_assert!(importance > 0.0);
+
+        let target = self.target(bound, phot.ray().pos()) / importance;
+        if target <= 0.0 {
+            return None;
+        }
+
+        let lower = 2.0 * target / (1.0 + self.ratio);
+        let upper = lower * self.ratio;
+        let weight = phot.weight();
+
+        if weight > upper {
+            let copies = ((weight / upper).ceil() as usize).min(MAX_SPLIT);
+            *phot.weight_mut() /= copies as f64;
+            return Some(copies);
+        }
+
+        if weight < lower {
+            if rng.gen_range(0.0_f64, 1.0) <= weight / target {
+                *budget.roulette_gained_mut() += (target - weight) * phot.power();
+                *phot.weight_mut() = target;
+            } else {
+                *budget.roulette_lost_mut() += weight * phot.power();
+                return Some(0);
+            }
+        }
+
+        Some(1)
+    }
+}
@@ -0,0 +1,380 @@
+//! Voxelisation cache implementation.
+
+use crate::{
+    access,
+    err::Error,
+    geom::{Aabb, Patch, SmoothTriangle, Surface},
+    math::indexer,
+    ord::{InterKey, InterSet, MatKey, RegionKey, RegionSet, StateKey, SurfKey, SurfSet},
+    sim::vox::Voxelisation,
+};
+use attr::json;
+use ndarray::Array3;
+use std::collections::BTreeMap;
+
+/// Key used to report errors in the cache.
+const CACHE: &str = "voxelisation cache";
+
+/// Initial state of the checksum hash.
+const HASH_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// Multiplier of the checksum hash.
+const HASH_PRIME: u64 = 0x0100_0000_01b3;
+
+/// Voxelisation cache structure.
+/// Stores a voxelisation by key and patch index so that it may be reloaded against the same geometry.
+#[json]
+pub struct Cache {
+    /// Boundary.
+    bound: Aabb,
+    /// Resolution.
+    res: [usize; 3],
+    /// Inside and outside materials, patch count and checksum of each interface surface.
+    inters: Vec<(InterKey, [MatKey; 2], usize, String)>,
+    /// Inside and outside states, patch count and checksum of each region surface.
+    regions: Vec<(RegionKey, [StateKey; 2], usize, String)>,
+    /// Material keys indexed by the material map.
+    mat_keys: Vec<MatKey>,
+    /// Material index of each cell.
+    mats: Vec<usize>,
+    /// State keys indexed by the state map.
+    state_keys: Vec<StateKey>,
+    /// State index of each cell, if there are any regions.
+    states: Option<Vec<usize>>,
+    /// Interface and patch indices of each cell intersecting any interface.
+    inter_tris: Vec<(usize, Vec<(usize, Vec<usize>)>)>,
+}
+
+impl Cache {
+    access!(bound, Aabb);
+    access!(res, [usize; 3]);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(
+        vox: &Voxelisation,
+        inters: &InterSet,
+        regions: &RegionSet,
+        surfs: &SurfSet,
+    ) -> Self {
+        let mut mat_keys: Vec<MatKey> = vox.mats().iter().map(|key| (*key).clone()).collect();
+        mat_keys.sort();
+        mat_keys.dedup();
+        let mats = vox
+            .mats()
+            .iter()
+            .map(|key| mat_keys.binary_search(*key).expect("Missing material key."))
+            .collect();
+
+        let mut state_keys: Vec<StateKey> = Vec::new();
+        let states = vox.states().as_ref().map(|states| {
+            state_keys = states.iter().map(|key| (*key).clone()).collect();
+            state_keys.sort();
+            state_keys.dedup();
+            states
+                .iter()
+                .map(|key| state_keys.binary_search(*key).expect("Missing state key."))
+                .collect()
+        });
+
+        let inter_keys: Vec<_> = inters.map().keys().collect();
+        let tri_indices: BTreeMap<*const SmoothTriangle, usize> = inters
+            .map()
+            .values()
+            .filter_map(|inter| match surfs.get(inter.surf()) {
+                Surface::Mesh(mesh) => Some(mesh.tris()),
+                _ => None,
+            })
+            .flat_map(|tris| {
+                tris.iter()
+                    .enumerate()
+                    .map(|(i, tri)| (tri as *const SmoothTriangle, i))
+            })
+            .collect();
+        let inter_tris = vox
+            .inter_tris()
+            .iter()
+            .enumerate()
+            .filter(|(_, list)| !list.is_empty())
+            .map(|(n, list)| {
+                (
+                    n,
+                    list.iter()
+                        .map(|((key, _), tris)| {
+                            (
+                                inter_keys
+                                    .binary_search(key)
+                                    .expect("Missing interface key."),
+                                tris.iter()
+                                    .map(|patch| match patch {
+                                        Patch::Tri(tri) => *tri_indices
+                                            .get(&(*tri as *const SmoothTriangle))
+                                            .expect("Missing triangle."),
+                                        Patch::Prim(_) => 0,
+                                    })
+                                    .collect(),
+                            )
+                        })
+                        .collect(),
+                )
+            })
+            .collect();
+
+        Self {
+            bound: vox.bound().clone(),
+            res: vox.res(),
+            inters: Self::signature(
+                inters.map().iter().map(|(key, inter)| {
+                    (
+                        key.clone(),
+                        [inter.in_mat().clone(), inter.out_mat().clone()],
+                        inter.surf(),
+                    )
+                }),
+                surfs,
+            ),
+            regions: Self::signature(
+                regions.map().iter().map(|(key, region)| {
+                    (
+                        key.clone(),
+                        [region.in_state().clone(), region.out_state().clone()],
+                        region.surf(),
+                    )
+                }),
+                surfs,
+            ),
+            mat_keys,
+            mats,
+            state_keys,
+            states,
+            inter_tris,
+        }
+    }
+
+    /// Determine the side keys, patch count and checksum of each surface.
+    #[inline]
+    #[must_use]
+    fn signature<'s, K, S, I>(keys: I, surfs: &SurfSet) -> Vec<(K, [S; 2], usize, String)>
+    where
+        I: Iterator<Item = (K, [S; 2], &'s SurfKey)>,
+    {
+        keys.map(|(key, sides, surf)| {
+            let surf = surfs.get(surf);
+            (key, sides, surf.patches().len(), Self::checksum(surf))
+        })
+        .collect()
+    }
+
+    /// Determine the checksum of a single surface.
+    /// This is an FNV-1a hash of the bits of every vertex coordinate or primitive parameter,
+    /// so any change to the geometry is detected.
+    /// It is written in hexadecimal as json integers are limited to the signed range.
+    #[inline]
+    #[must_use]
+    fn checksum(surf: &Surface) -> String {
+        let mut vals = Vec::new();
+        Self::params(surf, &mut vals);
+
+        let hash = vals.iter().fold(HASH_OFFSET, |hash, val| {
+            val.to_bits().to_le_bytes().iter().fold(hash, |hash, byte| {
+                (hash ^ u64::from(*byte)).wrapping_mul(HASH_PRIME)
+            })
+        });
+        format!("{:016x}", hash)
+    }
+
+    /// Collect the defining parameters of a surface.
+    /// Each kind of surface is preceded by a distinct tag, and meshes include their vertex normals.
+    #[inline]
+    fn params(surf: &Surface, vals: &mut Vec<f64>) {
+        match surf {
+            Surface::Mesh(mesh) => {
+                vals.push(0.0);
+                for tri in mesh.tris() {
+                    for v in tri.tri().verts() {
+                        vals.extend(v.iter());
+                    }
+                    for n in tri.norms() {
+                        vals.extend(n.iter());
+                    }
+                }
+            }
+            Surface::Sphere(sphere) => {
+                vals.push(1.0);
+                vals.extend(sphere.pos().iter());
+                vals.push(sphere.rad());
+            }
+            Surface::Cylinder(cylinder) => {
+                vals.push(2.0);
+                vals.extend(cylinder.base().iter());
+                vals.extend(cylinder.axis().iter());
+                vals.push(cylinder.len());
+                vals.push(cylinder.rad());
+            }
+            Surface::Cuboid(cuboid) => {
+                vals.push(3.0);
+                vals.extend(cuboid.centre().iter());
+                vals.extend(cuboid.hws().iter());
+                vals.extend(cuboid.rot().coords.iter());
+            }
+            Surface::Disc(disc) => {
+                vals.push(4.0);
+                vals.extend(disc.centre().iter());
+                vals.extend(disc.norm().iter());
+                vals.push(disc.rad());
+            }
+            Surface::Plane(plane) => {
+                vals.push(5.0);
+                vals.extend(plane.pos().iter());
+                vals.extend(plane.norm().iter());
+            }
+            Surface::Csg(csg) => {
+                vals.push(6.0);
+                vals.push(f64::from(csg.op() as u8));
+                vals.push(csg.parts().len() as f64);
+                for part in csg.parts() {
+                    Self::params(part, vals);
+                }
+            }
+        }
+    }
+
+    /// Check if the cache was built from the given geometry.
+    #[inline]
+    #[must_use]
+    pub fn matches(
+        &self,
+        res: [usize; 3],
+        bound: &Aabb,
+        inters: &InterSet,
+        regions: &RegionSet,
+        surfs: &SurfSet,
+    ) -> bool {
+        let inter_sig = Self::signature(
+            inters.map().iter().map(|(key, inter)| {
+                (
+                    key.clone(),
+                    [inter.in_mat().clone(), inter.out_mat().clone()],
+                    inter.surf(),
+                )
+            }),
+            surfs,
+        );
+        let region_sig = Self::signature(
+            regions.map().iter().map(|(key, region)| {
+                (
+                    key.clone(),
+                    [region.in_state().clone(), region.out_state().clone()],
+                    region.surf(),
+                )
+            }),
+            surfs,
+        );
+
+        self.res == res
+            && self.bound.mins() == bound.mins()
+            && self.bound.maxs() == bound.maxs()
+            && self.inters == inter_sig
+            && self.regions == region_sig
+    }
+
+    /// Reconstruct the voxelisation, borrowing from the given geometry.
+    /// Fails if the cache refers to keys, or indices, which are not present in the geometry.
+    #[inline]
+    pub fn into_vox<'a>(
+        self,
+        inters: &'a InterSet,
+        regions: &'a RegionSet,
+        surfs: &'a SurfSet,
+    ) -> Result<Voxelisation<'a>, Error> {
+        let mat_refs = self
+            .mat_keys
+            .iter()
+            .map(|key| {
+                inters
+                    .map()
+                    .values()
+                    .flat_map(|inter| vec![inter.in_mat(), inter.out_mat()])
+                    .find(|mat| *mat == key)
+                    .ok_or_else(|| {
+                        Error::invalid(key.str(), "cached material is not used by any interface")
+                    })
+            })
+            .collect::<Result<Vec<&'a MatKey>, Error>>()?;
+        let mats = Array3::from_shape_vec(
+            self.res,
+            self.mats
+                .iter()
+                .map(|i| lookup(&mat_refs, *i).map(|mat| *mat))
+                .collect::<Result<_, _>>()?,
+        )
+        .map_err(|_| Error::invalid(CACHE, "material array does not match the resolution"))?;
+
+        let state_refs = self
+            .state_keys
+            .iter()
+            .map(|key| {
+                regions
+                    .map()
+                    .values()
+                    .flat_map(|region| vec![region.in_state(), region.out_state()])
+                    .find(|state| *state == key)
+                    .ok_or_else(|| {
+                        Error::invalid(key.str(), "cached state is not used by any region")
+                    })
+            })
+            .collect::<Result<Vec<&'a StateKey>, Error>>()?;
+        let states = if let Some(states) = &self.states {
+            Some(
+                Array3::from_shape_vec(
+                    self.res,
+                    states
+                        .iter()
+                        .map(|i| lookup(&state_refs, *i).map(|state| *state))
+                        .collect::<Result<_, _>>()?,
+                )
+                .map_err(|_| Error::invalid(CACHE, "state array does not match the resolution"))?,
+            )
+        } else {
+            None
+        };
+
+        let inter_list: Vec<_> = inters.map().iter().collect();
+        let inter_patches = inter_list
+            .iter()
+            .map(|(_, inter)| Ok(surfs.try_get(inter.surf())?.patches()))
+            .collect::<Result<Vec<_>, Error>>()?;
+        let mut inter_tris = Array3::from_elem(self.res, Vec::new());
+        for (n, list) in self.inter_tris {
+            if n >= inter_tris.len() {
+                return Err(Error::invalid(CACHE, "cell index lies outside of the grid"));
+            }
+            let index = indexer::three_dim(n, self.res);
+            inter_tris[index] = list
+                .into_iter()
+                .map(|(i, tris)| {
+                    let (key, inter) = *lookup(&inter_list, i)?;
+                    let patches = lookup(&inter_patches, i)?;
+                    Ok((
+                        (key, inter),
+                        tris.iter()
+                            .map(|t| lookup(patches, *t).map(|patch| *patch))
+                            .collect::<Result<_, _>>()?,
+                    ))
+                })
+                .collect::<Result<_, Error>>()?;
+        }
+
+        Ok(Voxelisation::from_parts(
+            self.bound, mats, states, inter_tris, inters, surfs,
+        ))
+    }
+}
+
+/// Access a cached index into a list, failing if it lies outside of the list.
+#[inline]
+fn lookup<T>(list: &[T], index: usize) -> Result<&T, Error> {
+    list.get(index)
+        .ok_or_else(|| Error::invalid(CACHE, "index lies outside of the geometry"))
+}
@@ -0,0 +1,6 @@
+//! Voxelisation sub-module.
+
+pub mod cache;
+pub mod voxelisation;
+
+pub use self::{cache::*, voxelisation::*};
@@ -0,0 +1,329 @@
+//! Voxelisation implementation.
+
+use crate::{
+    access,
+    file::{Load, Save},
+    geom::{Aabb, Collide, Patch, Scan},
+    list::Cartesian::{X, Y, Z},
+    math::indexer,
+    ord::{sort, InterKey, InterSet, MatKey, RegionSet, StateKey, SurfSet},
+    sim::vox::Cache,
+    util::ParProgressBar,
+    world::Interface,
+};
+use log::{info, warn};
+use nalgebra::{Point3, Vector3};
+use ndarray::Array3;
+use num_cpus;
+use rayon::prelude::*;
+use std::{
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+/// Interface patches intersecting a cell, grouped by interface.
+/// Mesh interfaces contribute their overlapping triangles, and primitive interfaces themselves.
+pub type InterTris<'a> = Vec<((&'a InterKey, &'a Interface), Vec<Patch<'a>>)>;
+
+/// Interface with the bounding box and patches of its surface.
+type InterPatches<'a> = ((&'a InterKey, &'a Interface), Aabb, Vec<Patch<'a>>);
+
+/// Voxelisation structure.
+/// Holds the material keys, state keys and intersecting interface patches of each cell of a regular grid.
+/// Simulation grids built over the same geometry borrow from a single instance.
+pub struct Voxelisation<'a> {
+    /// Boundary.
+    bound: Aabb,
+    /// Central material of each cell.
+    mats: Array3<&'a MatKey>,
+    /// Central initial state of each cell, if there are any regions.
+    states: Option<Array3<&'a StateKey>>,
+    /// Intersecting interface patches of each cell.
+    inter_tris: Array3<InterTris<'a>>,
+    /// Scanline structure used to observe materials at arbitrary points.
+    scan: Scan<'a, &'a Interface>,
+    /// Material assigned to points which can not be resolved.
+    ambient: &'a MatKey,
+}
+
+impl<'a> Voxelisation<'a> {
+    access!(bound, Aabb);
+    access!(mats, Array3<&'a MatKey>);
+    access!(states, Option<Array3<&'a StateKey>>);
+    access!(inter_tris, Array3<InterTris<'a>>);
+
+    /// Construct a new instance.
+    /// Cells which can not be resolved are assigned the ambient material and state.
+    #[inline]
+    #[must_use]
+    pub fn new(
+        res: [usize; 3],
+        bound: Aabb,
+        inters: &'a InterSet,
+        regions: &'a RegionSet,
+        surfs: &'a SurfSet,
+    ) -> Self {
+        let total_cells = res.get(X as usize).expect("Missing resolution index.")
+            * res.get(Y as usize).expect("Missing resolution index.")
+            * res.get(Z as usize).expect("Missing resolution index.");
+        let col_res = [
+            *res.get(X as usize).expect("Missing resolution index."),
+            *res.get(Y as usize).expect("Missing resolution index."),
+        ];
+        let res_z = *res.get(Z as usize).expect("Missing resolution index.");
+
+        let scan = inters.scan(surfs, bound.clone(), col_res);
+        let ambient = inters.ambient_mat();
+        let mats = scan
+            .cells(res_z)
+            .map(|obs| obs.map(|(inter, inside)| inter.mat(inside)));
+        let num_unresolved = mats.iter().filter(|mat| mat.is_none()).count();
+        if num_unresolved > 0 {
+            warn!(
+                "{} of {} cells could not be resolved and have been assigned the ambient material: {}",
+                num_unresolved, total_cells, ambient
+            );
+        }
+        let mats = mats.map(|mat| mat.unwrap_or(ambient));
+
+        let states = if regions.map().is_empty() {
+            None
+        } else {
+            let ambient_state = regions.ambient_state();
+            let states = regions
+                .scan(surfs, bound.clone(), col_res)
+                .cells(res_z)
+                .map(|obs| obs.map(|(region, inside)| region.state(inside)));
+            let num_unresolved = states.iter().filter(|state| state.is_none()).count();
+            if num_unresolved > 0 {
+                warn!(
+                    "{} of {} cells could not be resolved and have been assigned the ambient state: {}",
+                    num_unresolved, total_cells, ambient_state
+                );
+            }
+            Some(states.map(|state| state.unwrap_or(ambient_state)))
+        };
+
+        let inter_patches: Vec<_> = inters
+            .map()
+            .iter()
+            .map(|(key, inter)| {
+                let surf = surfs.get(inter.surf());
+                ((key, inter), surf.bounding_box(), surf.patches())
+            })
+            .collect();
+
+        let pb = ParProgressBar::new("Voxelising", total_cells as u64);
+        let pb = Arc::new(Mutex::new(pb));
+        let thread_ids: Vec<usize> = (0..num_cpus::get()).collect();
+
+        let tri_blocks: Vec<_> = thread_ids
+            .par_iter()
+            .map(|_| {
+                Self::init_tri_blocks(
+                    &Arc::clone(&pb),
+                    ((total_cells / num_cpus::get()) / 100).max(1) as u64,
+                    &res,
+                    &bound,
+                    &inter_patches,
+                )
+            })
+            .collect();
+        pb.lock()
+            .expect("Could not lock progress bar.")
+            .finish_with_message("Voxelisation complete.");
+
+        let inter_tris = Array3::from_shape_vec(res, sort::stitch(tri_blocks))
+            .expect("Unable to construct triangle array.");
+
+        Self {
+            bound,
+            mats,
+            states,
+            inter_tris,
+            scan,
+            ambient,
+        }
+    }
+
+    /// Construct an instance from its parts.
+    #[inline]
+    #[must_use]
+    pub(crate) fn from_parts(
+        bound: Aabb,
+        mats: Array3<&'a MatKey>,
+        states: Option<Array3<&'a StateKey>>,
+        inter_tris: Array3<InterTris<'a>>,
+        inters: &'a InterSet,
+        surfs: &'a SurfSet,
+    ) -> Self {
+        let res = mats.shape();
+        let scan = inters.scan(
+            surfs,
+            bound.clone(),
+            [
+                *res.get(X as usize).expect("Missing resolution index."),
+                *res.get(Y as usize).expect("Missing resolution index."),
+            ],
+        );
+
+        Self {
+            bound,
+            mats,
+            states,
+            inter_tris,
+            scan,
+            ambient: inters.ambient_mat(),
+        }
+    }
+
+    /// Load an instance from a cache file if it matches the geometry,
+    /// otherwise construct a new instance and save it to the cache file.
+    #[inline]
+    #[must_use]
+    pub fn load_or_new(
+        path: &Path,
+        res: [usize; 3],
+        bound: Aabb,
+        inters: &'a InterSet,
+        regions: &'a RegionSet,
+        surfs: &'a SurfSet,
+    ) -> Self {
+        if path.exists() {
+            info!("Loading voxelisation cache: {}", path.display());
+            match Cache::load(path) {
+                Ok(cache) if cache.matches(res, &bound, inters, regions, surfs) => {
+                    match cache.into_vox(inters, regions, surfs) {
+                        Ok(vox) => return vox,
+                        Err(err) => warn!(
+                            "Voxelisation cache is inconsistent and will be rebuilt: {}",
+                            err
+                        ),
+                    }
+                }
+                Ok(_) => {
+                    warn!("Voxelisation cache does not match the geometry and will be rebuilt.")
+                }
+                Err(err) => warn!(
+                    "Voxelisation cache is unreadable and will be rebuilt: {}",
+                    err
+                ),
+            }
+        }
+
+        let vox = Self::new(res, bound, inters, regions, surfs);
+        info!("Saving voxelisation cache: {}", path.display());
+        if let Err(err) = Cache::new(&vox, inters, regions, surfs).save(path) {
+            warn!("Unable to save voxelisation cache: {}", err);
+        }
+
+        vox
+    }
+
+    /// Initialise the intersecting interface patches of the grid cells.
+    #[inline]
+    #[must_use]
+    fn init_tri_blocks(
+        pb: &Arc<Mutex<ParProgressBar>>,
+        block_size: u64,
+        res: &[usize; 3],
+        bound: &Aabb,
+        inter_patches: &[InterPatches<'a>],
+    ) -> Vec<(usize, Vec<InterTris<'a>>)> {
+        let mut cell_size = bound.widths();
+        for (w, n) in cell_size.iter_mut().zip(res) {
+            *w /= *n as f64;
+        }
+
+        let mut tri_blocks = Vec::new();
+        while let Some((start, end)) = {
+            let mut pb = pb.lock().expect("Could not lock progress bar.");
+            let b = pb.block(block_size);
+            std::mem::drop(pb);
+            b
+        } {
+            let mut tris: Vec<_> = Vec::with_capacity((end - start) as usize);
+            for n in start..end {
+                let index = indexer::three_dim(n as usize, *res);
+                let mins = bound.mins()
+                    + Vector3::new(
+                        cell_size.x * index[0] as f64,
+                        cell_size.y * index[1] as f64,
+                        cell_size.z * index[2] as f64,
+                    );
+
+                tris.push(Self::cell_inter_tris(
+                    &Aabb::new(mins, mins + cell_size),
+                    inter_patches,
+                ));
+            }
+            tri_blocks.push((start as usize, tris));
+        }
+
+        tri_blocks
+    }
+
+    /// Determine the interface patches intersecting a cell.
+    #[inline]
+    #[must_use]
+    fn cell_inter_tris(bound: &Aabb, inter_patches: &[InterPatches<'a>]) -> InterTris<'a> {
+        let mut inter_tris = Vec::new();
+
+        for (inter, surf_bound, patches) in inter_patches {
+            if bound.overlap(surf_bound) {
+                let intersections: Vec<_> = patches
+                    .iter()
+                    .filter(|patch| patch.overlap(bound))
+                    .copied()
+                    .collect();
+
+                if !intersections.is_empty() {
+                    inter_tris.push((*inter, intersections));
+                }
+            }
+        }
+
+        inter_tris
+    }
+
+    /// Get the resolution of the grid.
+    #[inline]
+    #[must_use]
+    pub fn res(&self) -> [usize; 3] {
+        let res = self.mats.shape();
+
+        [
+            *res.get(X as usize).expect("Missing resolution index."),
+            *res.get(Y as usize).expect("Missing resolution index."),
+            *res.get(Z as usize).expect("Missing resolution index."),
+        ]
+    }
+
+    /// Determine the boundary of the cell at the given index.
+    #[inline]
+    #[must_use]
+    pub fn cell_bound(&self, index: [usize; 3]) -> Aabb {
+        let mut cell_size = self.bound.widths();
+        for (w, n) in cell_size.iter_mut().zip(&self.res()) {
+            *w /= *n as f64;
+        }
+
+        let mins = self.bound.mins()
+            + Vector3::new(
+                cell_size.x * index[0] as f64,
+                cell_size.y * index[1] as f64,
+                cell_size.z * index[2] as f64,
+            );
+
+        Aabb::new(mins, mins + cell_size)
+    }
+
+    /// Determine the material observed at a point within the boundary.
+    #[inline]
+    #[must_use]
+    pub fn observe_mat(&self, p: &Point3<f64>) -> &'a MatKey {
+        self.scan
+            .observe(p)
+            .map_or(self.ambient, |(inter, inside)| inter.mat(inside))
+    }
+}
@@ -33,6 +33,19 @@ impl ParProgressBar {
         }
     }
 
+    /// Construct a new instance with a number of values already complete.
+    #[inline]
+    #[must_use]
+    pub fn resume(msg: &str, count: u64, total: u64) -> Self {
+        debug_assert!(count <= total);
+
+        let mut bar = Self::new(msg, total);
+        bar.count = count;
+        bar.pb.set_position(count);
+
+        bar
+    }
+
     /// Tick the bar forward once.
     #[inline]
     pub fn tick(&mut self) {
@@ -0,0 +1,175 @@
+//! Charge-coupled device detector implementation.
+
+use crate::{
+    access, clone,
+    data::Histogram,
+    err::Error,
+    file::Save,
+    phys::Photon,
+    world::{intensity, Detector},
+};
+use nalgebra::{Point3, Unit, Vector3};
+use ndarray::{s, Array3, Axis};
+use physical_constants::SPEED_OF_LIGHT_IN_VACUUM;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::{
+    ops::{AddAssign, MulAssign},
+    path::Path,
+};
+
+/// Planar charge-coupled device structure.
+/// Escaping photons are traced to the sensor plane and binned by position and angle of incidence into an image.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Ccd {
+    /// Centre of the sensor.
+    centre: Point3<f64>,
+    /// Sensor normal, facing the sample.
+    norm: Unit<Vector3<f64>>,
+    /// Horizontal sensor axis.
+    horz: Unit<Vector3<f64>>,
+    /// Half-widths of the sensor along the horizontal and vertical axes. [m]
+    half_widths: [f64; 2],
+    /// Maximum angle of incidence accepted. [rad]
+    max_ang: f64,
+    /// Optional linear polariser axis.
+    polariser: Option<Vector3<f64>>,
+    /// Collected power image, with angles of incidence binned uniformly between zero and the maximum along the last axis. [J/s]
+    image: Array3<f64>,
+    /// Optional temporal point spread function of the collected power. [J/s]
+    tpsf: Option<Histogram>,
+}
+
+impl Ccd {
+    access!(centre, Point3<f64>);
+    access!(norm, Unit<Vector3<f64>>);
+    access!(horz, Unit<Vector3<f64>>);
+    clone!(half_widths, [f64; 2]);
+    clone!(max_ang, f64);
+    access!(polariser, Option<Vector3<f64>>);
+    access!(image, Array3<f64>);
+    access!(tpsf, Option<Histogram>);
+
+    /// Construct a new instance.
+    /// The horizontal axis is projected into the sensor plane.
+    #[inline]
+    #[must_use]
+    pub fn new(
+        centre: Point3<f64>,
+        norm: Unit<Vector3<f64>>,
+        horz: &Vector3<f64>,
+        half_widths: [f64; 2],
+        res: [usize; 2],
+        ang_bins: usize,
+        max_ang: f64,
+        polariser: Option<Vector3<f64>>,
+        tpsf: Option<Histogram>,
+    ) -> Self {
+        debug_assert!(half_widths.iter().all(|w| *w > 0.0));
+        debug_assert!(res.iter().all(|n| *n > 0));
+        debug_assert!(ang_bins > 0);
+        debug_assert!(max_ang > 0.0);
+
+        let horz = Unit::new_normalize(horz - (norm.as_ref() * norm.dot(horz)));
+
+        Self {
+            centre,
+            norm,
+            horz,
+            half_widths,
+            max_ang,
+            polariser,
+            image: Array3::zeros([res[0], res[1], ang_bins]),
+            tpsf,
+        }
+    }
+}
+
+impl Detector for Ccd {
+    #[inline]
+    fn detect(&mut self, phot: &Photon) -> bool {
+        let dir = phot.ray().dir();
+        let cos_inc = -dir.dot(&self.norm);
+        if cos_inc < self.max_ang.cos() {
+            return false;
+        }
+
+        let dist = (phot.ray().pos() - self.centre).dot(&self.norm) / cos_inc;
+        if dist < 0.0 {
+            return false;
+        }
+
+        let hit = phot.ray().pos() + (dir.as_ref() * dist);
+        let vert = self.norm.cross(&self.horz);
+        let x = ((hit - self.centre).dot(&self.horz) / self.half_widths[0]).mul_add(0.5, 0.5);
+        let y = ((hit - self.centre).dot(&vert) / self.half_widths[1]).mul_add(0.5, 0.5);
+        if x < 0.0 || x >= 1.0 || y < 0.0 || y >= 1.0 {
+            return false;
+        }
+
+        let (nx, ny, na) = self.image.dim();
+        let ang = cos_inc.min(1.0).acos() / self.max_ang;
+        let index = (
+            (x * nx as f64) as usize,
+            (y * ny as f64) as usize,
+            ((ang * na as f64) as usize).min(na - 1),
+        );
+        let power = intensity(phot, self.polariser.as_ref());
+        *self.image.get_mut(index).expect("Invalid pixel index.") += power;
+        if let Some(tpsf) = &mut self.tpsf {
+            tpsf.try_collect_weight(phot.time() + (dist / SPEED_OF_LIGHT_IN_VACUUM), power);
+        }
+
+        true
+    }
+
+    #[inline]
+    fn sample_face<R: Rng>(&self, rng: &mut R) -> (Point3<f64>, Unit<Vector3<f64>>, f64) {
+        let vert = self.norm.cross(&self.horz);
+        let [hw, hh] = self.half_widths;
+        let point = self.centre
+            + (self.horz.as_ref() * rng.gen_range(-hw, hw))
+            + (vert * rng.gen_range(-hh, hh));
+
+        (point, self.norm, 4.0 * hw * hh)
+    }
+}
+
+impl AddAssign<&Self> for Ccd {
+    #[inline]
+    fn add_assign(&mut self, rhs: &Self) {
+        debug_assert!(self.image.dim() == rhs.image.dim());
+
+        self.image += &rhs.image;
+        if let (Some(tpsf), Some(rhs)) = (&mut self.tpsf, &rhs.tpsf) {
+            *tpsf += rhs;
+        }
+    }
+}
+
+impl MulAssign<f64> for Ccd {
+    #[inline]
+    fn mul_assign(&mut self, rhs: f64) {
+        self.image *= rhs;
+        if let Some(tpsf) = &mut self.tpsf {
+            *tpsf *= rhs;
+        }
+    }
+}
+
+impl Save for Ccd {
+    #[inline]
+    fn save(&self, path: &Path) -> Result<(), Error> {
+        if let Some(tpsf) = &self.tpsf {
+            tpsf.save(&path.with_extension("tpsf.csv"))?;
+        }
+        if self.image.len_of(Axis(2)) == 1 {
+            self.image
+                .slice(s![.., .., 0])
+                .to_owned()
+                .save(&path.with_extension("nc"))
+        } else {
+            self.image.save(&path.with_extension("nc"))
+        }
+    }
+}
@@ -0,0 +1,31 @@
+//! Detector trait.
+
+use crate::phys::Photon;
+use nalgebra::{Point3, Unit, Vector3};
+use rand::Rng;
+
+/// Types implementing this trait may collect photons escaping the simulation grid.
+pub trait Detector {
+    /// Attempt to detect an escaping photon.
+    /// Returns true if the photon was collected.
+    fn detect(&mut self, phot: &Photon) -> bool;
+
+    /// Sample a point uniformly over the collecting face, for next-event estimation.
+    /// Returns the point, the face normal directed towards the sample, and the area of the face.
+    fn sample_face<R: Rng>(&self, rng: &mut R) -> (Point3<f64>, Unit<Vector3<f64>>, f64);
+}
+
+/// Determine the power carried by a photon through an optional linear polariser.
+/// Unpolarised photons transmit half of their power through a polariser.
+#[inline]
+#[must_use]
+pub fn intensity(phot: &Photon, polariser: Option<&Vector3<f64>>) -> f64 {
+    let power = phot.weight() * phot.power();
+
+    polariser.map_or(power, |axis| {
+        phot.pol()
+            .as_ref()
+            .map_or(0.5, |pol| pol.linear_frac(phot.ray().dir(), axis))
+            * power
+    })
+}
@@ -0,0 +1,154 @@
+//! Optical fibre detector implementation.
+
+use crate::{
+    access, clone,
+    data::Histogram,
+    err::Error,
+    file::{as_json, Save},
+    geom::Aperture,
+    phys::Photon,
+    world::{intensity, Detector},
+};
+use nalgebra::{Point3, Unit, Vector3};
+use physical_constants::SPEED_OF_LIGHT_IN_VACUUM;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::{
+    f64::consts::PI,
+    ops::{AddAssign, MulAssign},
+    path::Path,
+};
+
+/// Optical fibre structure.
+/// Escaping photons are traced to the fibre face, and collected if within its core and acceptance cone.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Fibre {
+    /// Fibre face, directed towards the sample.
+    aperture: Aperture,
+    /// Core radius. [m]
+    radius: f64,
+    /// Optional linear polariser axis.
+    polariser: Option<Vector3<f64>>,
+    /// Collected power. [J/s]
+    total: f64,
+    /// Optional temporal point spread function of the collected power. [J/s]
+    tpsf: Option<Histogram>,
+}
+
+impl Fibre {
+    access!(aperture, Aperture);
+    clone!(radius, f64);
+    access!(polariser, Option<Vector3<f64>>);
+    clone!(total, f64);
+    access!(tpsf, Option<Histogram>);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(
+        aperture: Aperture,
+        radius: f64,
+        polariser: Option<Vector3<f64>>,
+        tpsf: Option<Histogram>,
+    ) -> Self {
+        debug_assert!(radius > 0.0);
+
+        Self {
+            aperture,
+            radius,
+            polariser,
+            total: 0.0,
+            tpsf,
+        }
+    }
+
+    /// Determine the distance a photon travels to reach the fibre face, if it would be accepted by the fibre.
+    #[inline]
+    #[must_use]
+    pub fn accepts(&self, phot: &Photon) -> Option<f64> {
+        let dir = phot.ray().dir();
+        if !self.aperture.accepts(dir) {
+            return None;
+        }
+
+        let face = self.aperture.ray();
+        let dist = (face.pos() - phot.ray().pos()).dot(face.dir()) / dir.dot(face.dir());
+        if dist < 0.0 {
+            return None;
+        }
+
+        let hit = phot.ray().pos() + (dir.as_ref() * dist);
+        if nalgebra::distance(&hit, face.pos()) <= self.radius {
+            Some(dist)
+        } else {
+            None
+        }
+    }
+}
+
+impl Detector for Fibre {
+    #[inline]
+    fn detect(&mut self, phot: &Photon) -> bool {
+        let dist = if let Some(dist) = self.accepts(phot) {
+            dist
+        } else {
+            return false;
+        };
+
+        let power = intensity(phot, self.polariser.as_ref());
+        self.total += power;
+        if let Some(tpsf) = &mut self.tpsf {
+            tpsf.try_collect_weight(phot.time() + (dist / SPEED_OF_LIGHT_IN_VACUUM), power);
+        }
+        true
+    }
+
+    #[inline]
+    fn sample_face<R: Rng>(&self, rng: &mut R) -> (Point3<f64>, Unit<Vector3<f64>>, f64) {
+        let face = self.aperture.ray();
+        let norm = *face.dir();
+        let other = if norm.x.abs() < 0.9 {
+            Vector3::x()
+        } else {
+            Vector3::y()
+        };
+        let u = Unit::new_normalize(norm.cross(&other));
+        let v = norm.cross(&u);
+
+        let r = self.radius * rng.gen_range(0.0_f64, 1.0).sqrt();
+        let (s, c) = rng.gen_range(0.0, 2.0 * PI).sin_cos();
+        let point = face.pos() + (u.as_ref() * (r * c)) + (v * (r * s));
+
+        (point, norm, PI * self.radius.powi(2))
+    }
+}
+
+impl AddAssign<&Self> for Fibre {
+    #[inline]
+    fn add_assign(&mut self, rhs: &Self) {
+        self.total += rhs.total;
+        if let (Some(tpsf), Some(rhs)) = (&mut self.tpsf, &rhs.tpsf) {
+            *tpsf += rhs;
+        }
+    }
+}
+
+impl MulAssign<f64> for Fibre {
+    #[inline]
+    fn mul_assign(&mut self, rhs: f64) {
+        self.total *= rhs;
+        if let Some(tpsf) = &mut self.tpsf {
+            *tpsf *= rhs;
+        }
+    }
+}
+
+impl Save for Fibre {
+    #[inline]
+    fn save(&self, path: &Path) -> Result<(), Error> {
+        if let Some(tpsf) = &self.tpsf {
+            tpsf.save(&path.with_extension("tpsf.csv"))?;
+        }
+        as_json(self, &path.with_extension("json"))
+    }
+}
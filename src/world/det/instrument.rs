@@ -0,0 +1,81 @@
+//! Instrument implementation.
+
+use crate::{
+    err::Error,
+    file::Save,
+    phys::Photon,
+    world::{Ccd, Detector, Fibre, Spectrometer},
+};
+use nalgebra::{Point3, Unit, Vector3};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::{
+    ops::{AddAssign, MulAssign},
+    path::Path,
+};
+
+/// Instrument enumeration implementation.
+/// Collects the detector kinds so they may be stored, merged and saved together.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Instrument {
+    /// Planar charge-coupled device.
+    Ccd(Ccd),
+    /// Optical fibre.
+    Fibre(Fibre),
+    /// Fibre-coupled spectrometer.
+    Spectrometer(Spectrometer),
+}
+
+impl Detector for Instrument {
+    #[inline]
+    fn detect(&mut self, phot: &Photon) -> bool {
+        match self {
+            Self::Ccd(det) => det.detect(phot),
+            Self::Fibre(det) => det.detect(phot),
+            Self::Spectrometer(det) => det.detect(phot),
+        }
+    }
+
+    #[inline]
+    fn sample_face<R: Rng>(&self, rng: &mut R) -> (Point3<f64>, Unit<Vector3<f64>>, f64) {
+        match self {
+            Self::Ccd(det) => det.sample_face(rng),
+            Self::Fibre(det) => det.sample_face(rng),
+            Self::Spectrometer(det) => det.sample_face(rng),
+        }
+    }
+}
+
+impl AddAssign<&Self> for Instrument {
+    #[inline]
+    fn add_assign(&mut self, rhs: &Self) {
+        match (self, rhs) {
+            (Self::Ccd(det), Self::Ccd(rhs)) => *det += rhs,
+            (Self::Fibre(det), Self::Fibre(rhs)) => *det += rhs,
+            (Self::Spectrometer(det), Self::Spectrometer(rhs)) => *det += rhs,
+            _ => panic!("Unable to add instruments of differing kinds."),
+        }
+    }
+}
+
+impl MulAssign<f64> for Instrument {
+    #[inline]
+    fn mul_assign(&mut self, rhs: f64) {
+        match self {
+            Self::Ccd(det) => *det *= rhs,
+            Self::Fibre(det) => *det *= rhs,
+            Self::Spectrometer(det) => *det *= rhs,
+        }
+    }
+}
+
+impl Save for Instrument {
+    #[inline]
+    fn save(&self, path: &Path) -> Result<(), Error> {
+        match self {
+            Self::Ccd(det) => det.save(path),
+            Self::Fibre(det) => det.save(path),
+            Self::Spectrometer(det) => det.save(path),
+        }
+    }
+}
@@ -0,0 +1,9 @@
+//! Detector sub-module.
+
+pub mod ccd;
+pub mod detector;
+pub mod fibre;
+pub mod instrument;
+pub mod spectrometer;
+
+pub use self::{ccd::*, detector::*, fibre::*, instrument::*, spectrometer::*};
@@ -0,0 +1,85 @@
+//! Spectrometer detector implementation.
+
+use crate::{
+    access,
+    data::Histogram,
+    err::Error,
+    file::Save,
+    phys::Photon,
+    world::{intensity, Detector, Fibre},
+};
+use nalgebra::{Point3, Unit, Vector3};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::{
+    ops::{AddAssign, MulAssign},
+    path::Path,
+};
+
+/// Fibre-coupled spectrometer structure.
+/// Photons accepted by the fibre are binned by wavelength, and by time of flight if the fibre records a TPSF.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Spectrometer {
+    /// Coupling fibre.
+    fibre: Fibre,
+    /// Collected power spectrum. [J/s]
+    hist: Histogram,
+}
+
+impl Spectrometer {
+    access!(fibre, Fibre);
+    access!(hist, Histogram);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(fibre: Fibre, hist: Histogram) -> Self {
+        Self { fibre, hist }
+    }
+}
+
+impl Detector for Spectrometer {
+    #[inline]
+    fn detect(&mut self, phot: &Photon) -> bool {
+        if !self.fibre.detect(phot) {
+            return false;
+        }
+
+        self.hist.try_collect_weight(
+            phot.wavelength(),
+            intensity(phot, self.fibre.polariser().as_ref()),
+        );
+        true
+    }
+
+    #[inline]
+    fn sample_face<R: Rng>(&self, rng: &mut R) -> (Point3<f64>, Unit<Vector3<f64>>, f64) {
+        self.fibre.sample_face(rng)
+    }
+}
+
+impl AddAssign<&Self> for Spectrometer {
+    #[inline]
+    fn add_assign(&mut self, rhs: &Self) {
+        self.fibre += &rhs.fibre;
+        self.hist += &rhs.hist;
+    }
+}
+
+impl MulAssign<f64> for Spectrometer {
+    #[inline]
+    fn mul_assign(&mut self, rhs: f64) {
+        self.fibre *= rhs;
+        self.hist *= rhs;
+    }
+}
+
+impl Save for Spectrometer {
+    #[inline]
+    fn save(&self, path: &Path) -> Result<(), Error> {
+        if let Some(tpsf) = self.fibre.tpsf() {
+            tpsf.save(&path.with_extension("tpsf.csv"))?;
+        }
+        self.hist.save(&path.with_extension("csv"))
+    }
+}
@@ -1,6 +1,7 @@
 //! World module.
 
+pub mod det;
 pub mod part;
 pub mod uni;
 
-pub use self::{part::*, uni::*};
+pub use self::{det::*, part::*, uni::*};
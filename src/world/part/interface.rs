@@ -32,4 +32,15 @@ impl Interface {
             out_mat,
         }
     }
+
+    /// Reference the material on the given side of the surface.
+    #[inline]
+    #[must_use]
+    pub fn mat(&self, inside: bool) -> &MatKey {
+        if inside {
+            &self.in_mat
+        } else {
+            &self.out_mat
+        }
+    }
 }
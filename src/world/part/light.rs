@@ -4,10 +4,11 @@ use crate::{
     access,
     geom::Emit,
     ord::{SurfKey, SurfSet},
-    phys::{Photon, Spectrum},
+    phys::{Photon, Polarisation, Spectrum},
 };
 use attr::json;
-use rand::rngs::ThreadRng;
+use nalgebra::{Vector3, Vector4};
+use rand::Rng;
 
 /// Light structure implementation.
 #[json]
@@ -18,28 +19,41 @@ pub struct Light {
     spec: Spectrum,
     /// Power. [J/s]
     power: f64,
+    /// Optional Stokes vector of emitted photons, referenced to the global x-axis.
+    stokes: Option<[f64; 4]>,
 }
 
 impl Light {
     access!(surf, SurfKey);
     access!(spec, Spectrum);
     access!(power, f64);
+    access!(stokes, Option<[f64; 4]>);
 
     /// Construct a new instance.
     #[inline]
     #[must_use]
-    pub fn new(surf: SurfKey, spec: Spectrum, power: f64) -> Self {
+    pub fn new(surf: SurfKey, spec: Spectrum, power: f64, stokes: Option<[f64; 4]>) -> Self {
         debug_assert!(power > 0.0);
 
-        Self { surf, spec, power }
+        Self {
+            surf,
+            spec,
+            power,
+            stokes,
+        }
     }
 
     /// Emit a new photon.
-    pub fn emit(&self, rng: &mut ThreadRng, total_phot: u64, surfs: &SurfSet) -> Photon {
-        Photon::new(
-            self.spec.sample(rng),
-            self.power / total_phot as f64,
-            surfs.get(&self.surf).cast(rng),
-        )
+    pub fn emit<R: Rng>(&self, rng: &mut R, total_phot: u64, surfs: &SurfSet) -> Photon {
+        let wavelength = self.spec.sample(rng);
+        let power = self.power / total_phot as f64;
+        let ray = surfs.get(&self.surf).cast(rng);
+
+        if let Some([i, q, u, v]) = self.stokes {
+            let pol = Polarisation::new(Vector4::new(i, q, u, v), ray.dir(), &Vector3::x());
+            return Photon::new_polarised(wavelength, power, ray, pol);
+        }
+
+        Photon::new(wavelength, power, ray)
     }
 }
@@ -32,4 +32,15 @@ impl Region {
             out_state,
         }
     }
+
+    /// Reference the state on the given side of the surface.
+    #[inline]
+    #[must_use]
+    pub fn state(&self, inside: bool) -> &StateKey {
+        if inside {
+            &self.in_state
+        } else {
+            &self.out_state
+        }
+    }
 }
@@ -2,7 +2,7 @@
 
 use crate::{
     access,
-    ord::{InterSet, LightSet, MatSet, ReactSet, RegionSet, SpecSet, StateSet, SurfSet},
+    ord::{DetSet, InterSet, LightSet, MatSet, ReactSet, RegionSet, SpecSet, StateSet, SurfSet},
 };
 use log::info;
 
@@ -16,6 +16,8 @@ pub struct Verse {
     reacts: ReactSet,
     /// Lights.
     lights: LightSet,
+    /// Detectors.
+    dets: DetSet,
     /// Meshes.
     mats: MatSet,
     /// States.
@@ -31,6 +33,7 @@ impl Verse {
     access!(regions, RegionSet);
     access!(reacts, ReactSet);
     access!(lights, LightSet);
+    access!(dets, DetSet);
     access!(mats, MatSet);
     access!(states, StateSet);
     access!(specs, SpecSet);
@@ -45,6 +48,7 @@ impl Verse {
         regions: RegionSet,
         reacts: ReactSet,
         lights: LightSet,
+        dets: DetSet,
         mats: MatSet,
         states: StateSet,
         specs: SpecSet,
@@ -55,6 +59,7 @@ impl Verse {
             regions,
             reacts,
             lights,
+            dets,
             mats,
             states,
             specs,
@@ -89,6 +94,14 @@ impl Verse {
             }
         }
 
+        let num_dets = self.dets().map().len();
+        if num_dets > 0 {
+            info!("{} detectors:", num_dets);
+            for key in self.dets.map().keys() {
+                println!("\t{}", key);
+            }
+        }
+
         let num_mats = self.mats().map().len();
         if num_mats > 0 {
             info!("{} materials:", num_mats);